@@ -0,0 +1,560 @@
+use crate::geometry::{distance, integrate};
+use crate::types::{PathSegment, Point};
+
+/// Control points of a Bézier segment as a cubic
+///
+/// A quadratic is raised to the cubic tracing the same curve, so everything
+/// below only has to handle cubics. Other segments give `None`.
+pub fn cubic_control_points(segment: &PathSegment) -> Option<[Point; 4]> {
+    match segment {
+        PathSegment::QuadBezier(start, control, end) => Some([
+            *start,
+            lerp(*start, *control, 2.0 / 3.0),
+            lerp(*end, *control, 2.0 / 3.0),
+            *end,
+        ]),
+        PathSegment::CubicBezier(start, control1, control2, end) => {
+            Some([*start, *control1, *control2, *end])
+        }
+        _ => None,
+    }
+}
+
+fn lerp(a: Point, b: Point, t: f64) -> Point {
+    Point {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+    }
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    Point {
+        x: a.x - b.x,
+        y: a.y - b.y,
+    }
+}
+
+fn dot(a: Point, b: Point) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Point, b: Point) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Point of a cubic at parameter `t`
+pub fn cubic_point(curve: &[Point; 4], t: f64) -> Point {
+    let s = 1.0 - t;
+    let (b0, b1, b2, b3) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+    Point {
+        x: b0 * curve[0].x + b1 * curve[1].x + b2 * curve[2].x + b3 * curve[3].x,
+        y: b0 * curve[0].y + b1 * curve[1].y + b2 * curve[2].y + b3 * curve[3].y,
+    }
+}
+
+/// First derivative of a cubic with respect to `t`
+pub fn cubic_derivative(curve: &[Point; 4], t: f64) -> Point {
+    let s = 1.0 - t;
+    let (d0, d1, d2) = (
+        sub(curve[1], curve[0]),
+        sub(curve[2], curve[1]),
+        sub(curve[3], curve[2]),
+    );
+    Point {
+        x: 3.0 * (s * s * d0.x + 2.0 * s * t * d1.x + t * t * d2.x),
+        y: 3.0 * (s * s * d0.y + 2.0 * s * t * d1.y + t * t * d2.y),
+    }
+}
+
+/// Second derivative of a cubic with respect to `t`
+fn cubic_second_derivative(curve: &[Point; 4], t: f64) -> Point {
+    let a = sub(sub(curve[2], curve[1]), sub(curve[1], curve[0]));
+    let b = sub(sub(curve[3], curve[2]), sub(curve[2], curve[1]));
+    Point {
+        x: 6.0 * ((1.0 - t) * a.x + t * b.x),
+        y: 6.0 * ((1.0 - t) * a.y + t * b.y),
+    }
+}
+
+/// Direction of travel along a cubic at `t`, not normalized
+///
+/// Where a control point coincides with its end the derivative vanishes, and
+/// the direction the curve leaves or enters in is taken from the second
+/// derivative instead.
+pub fn cubic_tangent(curve: &[Point; 4], t: f64) -> Point {
+    let derivative = cubic_derivative(curve, t);
+    let scale = control_polygon_length(curve);
+    if derivative.x.hypot(derivative.y) > scale * 1e-9 {
+        return derivative;
+    }
+    let second = cubic_second_derivative(curve, t);
+    if second.x.hypot(second.y) > scale * 1e-9 {
+        // Near the end the curve arrives against the second derivative
+        let sign = if t < 0.5 { 1.0 } else { -1.0 };
+        return Point {
+            x: sign * second.x,
+            y: sign * second.y,
+        };
+    }
+    sub(curve[3], curve[0])
+}
+
+fn control_polygon_length(curve: &[Point; 4]) -> f64 {
+    curve
+        .windows(2)
+        .map(|pair| distance(pair[0], pair[1]))
+        .sum()
+}
+
+/// Split Bézier control points at `t` with de Casteljau's algorithm
+pub fn split_control_points<const N: usize>(
+    points: [Point; N],
+    t: f64,
+) -> ([Point; N], [Point; N]) {
+    let mut left = points;
+    let mut right = points;
+    let mut work = points;
+    for level in 0..N {
+        left[level] = work[0];
+        right[N - 1 - level] = work[N - 1 - level];
+        for i in 0..N - 1 - level {
+            work[i] = lerp(work[i], work[i + 1], t);
+        }
+    }
+    (left, right)
+}
+
+/// The part of Bézier control points between parameters `t0` and `t1`
+fn sub_control_points<const N: usize>(points: [Point; N], t0: f64, t1: f64) -> [Point; N] {
+    if t1 <= 0.0 {
+        return [points[0]; N];
+    }
+    let (head, _) = split_control_points(points, t1);
+    split_control_points(head, t0 / t1).1
+}
+
+/// The part of a Bézier segment between parameters `t0` and `t1`, keeping its
+/// degree; `t0 > t1` gives the part reversed
+///
+/// The ends of the piece are set to `start` and `end`, so pieces cut at the
+/// same points share their endpoints exactly.
+pub fn bezier_piece(
+    segment: &PathSegment,
+    t0: f64,
+    t1: f64,
+    start: Point,
+    end: Point,
+) -> PathSegment {
+    let (low, high) = (t0.min(t1), t0.max(t1));
+    let piece = match segment {
+        PathSegment::QuadBezier(p0, p1, p2) => {
+            let [_, control, _] = sub_control_points([*p0, *p1, *p2], low, high);
+            PathSegment::QuadBezier(start, control, end)
+        }
+        PathSegment::CubicBezier(p0, p1, p2, p3) => {
+            let [_, control1, control2, _] = sub_control_points([*p0, *p1, *p2, *p3], low, high);
+            PathSegment::CubicBezier(start, control1, control2, end)
+        }
+        _ => return *segment,
+    };
+    if t0 <= t1 {
+        piece
+    } else {
+        match piece {
+            PathSegment::QuadBezier(_, control, _) => PathSegment::QuadBezier(start, control, end),
+            PathSegment::CubicBezier(_, control1, control2, _) => {
+                PathSegment::CubicBezier(start, control2, control1, end)
+            }
+            _ => piece,
+        }
+    }
+}
+
+/// Real roots of `a·t² + b·t + c` strictly inside `(0, 1)`
+fn unit_quadratic_roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let scale = a.abs().max(b.abs()).max(c.abs());
+    if scale == 0.0 {
+        return Vec::new();
+    }
+    let roots = if a.abs() <= scale * 1e-12 {
+        if b == 0.0 { Vec::new() } else { vec![-c / b] }
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            Vec::new()
+        } else {
+            // Avoid cancellation by taking the larger root first
+            let q = -(b + b.signum() * discriminant.sqrt()) / 2.0;
+            if q == 0.0 {
+                vec![0.0]
+            } else {
+                vec![q / a, c / q]
+            }
+        }
+    };
+    roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
+
+/// Parameters in `(0, 1)` where a cubic turns around in x, then in y
+pub fn cubic_axis_extremes(curve: &[Point; 4]) -> (Vec<f64>, Vec<f64>) {
+    // B'(t)/3 = (d0 - 2·d1 + d2)·t² + 2·(d1 - d0)·t + d0
+    let roots =
+        |d0: f64, d1: f64, d2: f64| unit_quadratic_roots(d0 - 2.0 * d1 + d2, 2.0 * (d1 - d0), d0);
+    let d = [
+        sub(curve[1], curve[0]),
+        sub(curve[2], curve[1]),
+        sub(curve[3], curve[2]),
+    ];
+    (roots(d[0].x, d[1].x, d[2].x), roots(d[0].y, d[1].y, d[2].y))
+}
+
+/// Length of a cubic between parameters `t0` and `t1`
+pub fn cubic_length(curve: &[Point; 4], t0: f64, t1: f64) -> f64 {
+    let (low, high) = (t0.min(t1), t0.max(t1));
+    let speed = |t: f64| {
+        let d = cubic_derivative(curve, t);
+        d.x.hypot(d.y)
+    };
+    integrate(speed, low, high, ((high - low) * 16.0).ceil() as usize)
+}
+
+/// Parameter of the point of a cubic closest to `point`
+///
+/// A coarse sample gives the starting guess, which Newton's method refines on
+/// the condition that the offset to `point` is normal to the curve.
+pub fn closest_cubic_parameter(curve: &[Point; 4], point: Point) -> f64 {
+    const SAMPLES: usize = 32;
+    let mut best = (0..=SAMPLES)
+        .map(|i| i as f64 / SAMPLES as f64)
+        .min_by(|a, b| {
+            distance(cubic_point(curve, *a), point)
+                .total_cmp(&distance(cubic_point(curve, *b), point))
+        })
+        .unwrap_or(0.0);
+
+    for _ in 0..16 {
+        let offset = sub(cubic_point(curve, best), point);
+        let d1 = cubic_derivative(curve, best);
+        let d2 = cubic_second_derivative(curve, best);
+        let g = dot(offset, d1);
+        let dg = dot(d1, d1) + dot(offset, d2);
+        if dg <= 0.0 {
+            break;
+        }
+        let next = (best - g / dg).clamp(0.0, 1.0);
+        let step = (next - best).abs();
+        best = next;
+        if step < 1e-15 {
+            break;
+        }
+    }
+    best
+}
+
+/// Green's theorem contribution `½∫(x dy - y dx)` of a cubic
+///
+/// In the power basis `x(t) = Σ xᵢ·tⁱ`, `y(t) = Σ yᵢ·tⁱ` the integrand is a
+/// polynomial, so the integral over `[0, 1]` is the exact sum
+/// `Σ j·(xᵢ·yⱼ - yᵢ·xⱼ) / (i + j)`.
+pub fn cubic_area_term(curve: &[Point; 4]) -> f64 {
+    let [p0, p1, p2, p3] = *curve;
+    let power = |a: f64, b: f64, c: f64, d: f64| {
+        [
+            a,
+            3.0 * (b - a),
+            3.0 * (c - 2.0 * b + a),
+            d - 3.0 * c + 3.0 * b - a,
+        ]
+    };
+    let x = power(p0.x, p1.x, p2.x, p3.x);
+    let y = power(p0.y, p1.y, p2.y, p3.y);
+    let mut area = 0.0;
+    for i in 0..4 {
+        for j in 1..4 {
+            area += j as f64 * (x[i] * y[j] - y[i] * x[j]) / (i + j) as f64;
+        }
+    }
+    area / 2.0
+}
+
+/// Winding contribution of a cubic around `point`
+///
+/// The curve is split where it turns around in y into y-monotone pieces, and
+/// each piece is tested like a line using the curve's own x-coordinate at the
+/// height of `point`, found by bisection.
+pub fn cubic_winding(curve: &[Point; 4], point: Point) -> i32 {
+    let mut breaks = vec![0.0];
+    breaks.extend(cubic_axis_extremes(curve).1);
+    breaks.sort_by(|a, b| a.total_cmp(b));
+    breaks.push(1.0);
+    let at = |t: f64| {
+        if t == 0.0 {
+            curve[0]
+        } else if t == 1.0 {
+            curve[3]
+        } else {
+            cubic_point(curve, t)
+        }
+    };
+
+    let mut winding = 0;
+    for pair in breaks.windows(2) {
+        let (mut t0, mut t1) = (pair[0], pair[1]);
+        let (a, b) = (at(t0), at(t1));
+        let upward = a.y <= point.y && point.y < b.y;
+        let downward = b.y <= point.y && point.y < a.y;
+        if !upward && !downward {
+            continue;
+        }
+        // Keep t0 on the side below point.y
+        if downward {
+            std::mem::swap(&mut t0, &mut t1);
+        }
+        for _ in 0..64 {
+            let middle = (t0 + t1) / 2.0;
+            if cubic_point(curve, middle).y < point.y {
+                t0 = middle;
+            } else {
+                t1 = middle;
+            }
+        }
+        if cubic_point(curve, (t0 + t1) / 2.0).x > point.x {
+            winding += if upward { 1 } else { -1 };
+        }
+    }
+    winding
+}
+
+/// Whether a cubic is within `tolerance` of the chord between its ends
+fn is_flat(curve: &[Point; 4], tolerance: f64) -> bool {
+    let chord = sub(curve[3], curve[0]);
+    let length = chord.x.hypot(chord.y);
+    [curve[1], curve[2]].iter().all(|control| {
+        let offset = sub(*control, curve[0]);
+        let gap = if length == 0.0 {
+            offset.x.hypot(offset.y)
+        } else {
+            cross(chord, offset).abs() / length
+        };
+        gap <= tolerance
+    })
+}
+
+/// Axis-aligned box around the control points, which contains the curve
+pub fn control_box(curve: &[Point; 4]) -> (Point, Point) {
+    let mut min = curve[0];
+    let mut max = curve[0];
+    for point in &curve[1..] {
+        min.x = min.x.min(point.x);
+        min.y = min.y.min(point.y);
+        max.x = max.x.max(point.x);
+        max.y = max.y.max(point.y);
+    }
+    (min, max)
+}
+
+/// Parameter pairs `(t1, t2)` near which two cubics may meet
+///
+/// Both curves are halved recursively while their control boxes, grown by
+/// `margin`, overlap, until both halves are flat; the middles of those flat
+/// pairs are the starting guesses for `refine_cubic_intersection`.
+pub fn cubic_cubic_candidates(
+    curve1: &[Point; 4],
+    curve2: &[Point; 4],
+    margin: f64,
+) -> Vec<(f64, f64)> {
+    const MAX_DEPTH: usize = 40;
+    let size = {
+        let (min1, max1) = control_box(curve1);
+        let (min2, max2) = control_box(curve2);
+        distance(min1, max1).max(distance(min2, max2))
+    };
+    let flatness = margin.max(size * 1e-6);
+
+    let mut candidates = Vec::new();
+    // (curve, t range) of both sides, and the depth
+    let mut stack = vec![(*curve1, (0.0, 1.0), *curve2, (0.0, 1.0), 0)];
+    while let Some((a, range_a, b, range_b, depth)) = stack.pop() {
+        let (min_a, max_a) = control_box(&a);
+        let (min_b, max_b) = control_box(&b);
+        if min_a.x > max_b.x + margin
+            || min_b.x > max_a.x + margin
+            || min_a.y > max_b.y + margin
+            || min_b.y > max_a.y + margin
+        {
+            continue;
+        }
+        let flat_a = is_flat(&a, flatness);
+        let flat_b = is_flat(&b, flatness);
+        if (flat_a && flat_b) || depth == MAX_DEPTH {
+            candidates.push(((range_a.0 + range_a.1) / 2.0, (range_b.0 + range_b.1) / 2.0));
+            continue;
+        }
+        let halves = |curve: [Point; 4], range: (f64, f64), flat: bool| {
+            if flat {
+                vec![(curve, range)]
+            } else {
+                let (left, right) = split_control_points(curve, 0.5);
+                let middle = (range.0 + range.1) / 2.0;
+                vec![(left, (range.0, middle)), (right, (middle, range.1))]
+            }
+        };
+        for (a, range_a) in halves(a, range_a, flat_a) {
+            for (b, range_b) in halves(b, range_b, flat_b) {
+                stack.push((a, range_a, b, range_b, depth + 1));
+            }
+        }
+    }
+    candidates
+}
+
+/// Refine a guess `(t1, t2)` for a point shared by two cubics
+///
+/// Newton's method on `B1(t1) - B2(t2) = 0` converges quickly where the
+/// curves cross. Where they only touch the system is singular, and the guess
+/// is improved instead by projecting alternately onto each curve. Returns the
+/// parameters and the remaining gap between the two points.
+pub fn refine_cubic_intersection(
+    curve1: &[Point; 4],
+    curve2: &[Point; 4],
+    guess: (f64, f64),
+) -> (f64, f64, f64) {
+    let gap = |t1: f64, t2: f64| distance(cubic_point(curve1, t1), cubic_point(curve2, t2));
+    let (mut t1, mut t2) = guess;
+    for _ in 0..32 {
+        let f = sub(cubic_point(curve1, t1), cubic_point(curve2, t2));
+        let d1 = cubic_derivative(curve1, t1);
+        let d2 = cubic_derivative(curve2, t2);
+        let det = cross(d2, d1);
+        if det.abs() <= 1e-12 * d1.x.hypot(d1.y) * d2.x.hypot(d2.y) {
+            break;
+        }
+        // Solve d1·Δ1 - d2·Δ2 = f by Cramer's rule
+        let delta1 = cross(d2, f) / det;
+        let delta2 = cross(d1, f) / det;
+        t1 = (t1 - delta1).clamp(0.0, 1.0);
+        t2 = (t2 - delta2).clamp(0.0, 1.0);
+        if delta1.abs() < 1e-15 && delta2.abs() < 1e-15 {
+            break;
+        }
+    }
+
+    let (start1, start2) = (t1, t2);
+    for _ in 0..64 {
+        let previous = gap(t1, t2);
+        t2 = closest_parameter_near(curve2, cubic_point(curve1, t1), t2);
+        t1 = closest_parameter_near(curve1, cubic_point(curve2, t2), t1);
+        if gap(t1, t2) >= previous {
+            break;
+        }
+    }
+    if gap(start1, start2) <= gap(t1, t2) {
+        (start1, start2, gap(start1, start2))
+    } else {
+        (t1, t2, gap(t1, t2))
+    }
+}
+
+/// Newton refinement of the closest point of a cubic to `point`, starting
+/// from `t` rather than from a global search
+fn closest_parameter_near(curve: &[Point; 4], point: Point, mut t: f64) -> f64 {
+    for _ in 0..8 {
+        let offset = sub(cubic_point(curve, t), point);
+        let d1 = cubic_derivative(curve, t);
+        let d2 = cubic_second_derivative(curve, t);
+        let dg = dot(d1, d1) + dot(offset, d2);
+        if dg <= 0.0 {
+            break;
+        }
+        t = (t - dot(offset, d1) / dg).clamp(0.0, 1.0);
+    }
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_quadratic_raised_to_cubic() {
+        let quad = PathSegment::QuadBezier(point(0.0, 0.0), point(1.0, 2.0), point(2.0, 0.0));
+        let cubic = cubic_control_points(&quad).unwrap();
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            let s = 1.0 - t;
+            let expected = point(2.0 * s * t * 1.0 + t * t * 2.0, 2.0 * s * t * 2.0);
+            assert!(distance(cubic_point(&cubic, t), expected) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bezier_piece() {
+        let cubic = PathSegment::CubicBezier(
+            point(0.0, 0.0),
+            point(1.0, 3.0),
+            point(3.0, -1.0),
+            point(4.0, 2.0),
+        );
+        let curve = cubic_control_points(&cubic).unwrap();
+        let (a, b) = (0.2, 0.7);
+        let (start, end) = (cubic_point(&curve, a), cubic_point(&curve, b));
+        let piece = cubic_control_points(&bezier_piece(&cubic, a, b, start, end)).unwrap();
+        let reversed = cubic_control_points(&bezier_piece(&cubic, b, a, end, start)).unwrap();
+        for i in 0..=10 {
+            let u = i as f64 / 10.0;
+            let expected = cubic_point(&curve, a + (b - a) * u);
+            assert!(distance(cubic_point(&piece, u), expected) < 1e-12);
+            assert!(distance(cubic_point(&reversed, 1.0 - u), expected) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cubic_area_term_matches_polygon_limit() {
+        // A closed cubic loop against a fine polygon approximation
+        let curve = [
+            point(0.0, 0.0),
+            point(4.0, 3.0),
+            point(-1.0, 3.0),
+            point(0.0, 0.0),
+        ];
+        let n = 20_000;
+        let polygon: f64 = (0..n)
+            .map(|i| {
+                let p = cubic_point(&curve, i as f64 / n as f64);
+                let q = cubic_point(&curve, (i + 1) as f64 / n as f64);
+                (p.x * q.y - q.x * p.y) / 2.0
+            })
+            .sum();
+        assert!((cubic_area_term(&curve) - polygon).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cubic_length_and_closest_parameter() {
+        // A straight cubic with evenly spaced control points has unit speed
+        // scaled by the chord
+        let line = [
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(2.0, 2.0),
+            point(3.0, 3.0),
+        ];
+        assert!((cubic_length(&line, 0.0, 1.0) - 18f64.sqrt()).abs() < 1e-12);
+        assert!((cubic_length(&line, 0.25, 0.75) - 18f64.sqrt() / 2.0).abs() < 1e-12);
+
+        let curve = [
+            point(0.0, 0.0),
+            point(1.0, 2.0),
+            point(3.0, 2.0),
+            point(4.0, 0.0),
+        ];
+        let on_curve = cubic_point(&curve, 0.3);
+        assert!((closest_cubic_parameter(&curve, on_curve) - 0.3).abs() < 1e-9);
+        // The top of the symmetric arch is at t = ½, y = 1.5
+        let above = point(2.0, 5.0);
+        assert!((closest_cubic_parameter(&curve, above) - 0.5).abs() < 1e-9);
+    }
+}
@@ -1,261 +1,1286 @@
+use crate::bezier::{bezier_piece, cubic_control_points, cubic_length};
+use crate::broad_phase::{candidate_segment_pairs, overlapping_pairs, segment_boxes};
 use crate::geometry::{
-    area_of_path, distance, get_segment_midpoint, is_counter_clockwise, signed_area_of_path,
+    arc_angle_range, arc_span, arc_sweep, area_of_path, distance, distance_to_segment,
+    distance_to_segment_with_tolerance, elliptical_arc_length, get_segment_end_point,
+    get_segment_midpoint, get_segment_start_point, get_shape_bounding_box,
+    get_shape_bounding_box_with_tolerance, orient_path, reverse_segment, segment_parameter,
+    segment_tangent, signed_area_of_path, winding_number,
 };
-use crate::intersection::{arc_arc_intersection, line_arc_intersection, line_line_intersection};
-use crate::types::{PathSegment, Point, ResolvedShape};
-
-/// Find all intersection points between two resolved shapes
-pub fn find_shape_intersections(shape1: &ResolvedShape, shape2: &ResolvedShape) -> Vec<Point> {
-    let mut intersections = Vec::new();
-
-    for seg1 in &shape1.segments {
-        for seg2 in &shape2.segments {
-            let pts = match (seg1, seg2) {
-                (PathSegment::Line(s1, e1), PathSegment::Line(s2, e2)) => {
-                    line_line_intersection(*s1, *e1, *s2, *e2)
+use crate::intersection::{
+    arc_arc_intersection, arc_arc_overlap, bezier_bezier_intersection, bezier_bezier_overlap,
+    ellipse_bezier_intersection, ellipse_ellipse_intersection, ellipse_ellipse_overlap,
+    line_arc_intersection, line_bezier_intersection, line_ellipse_intersection,
+    line_line_intersection, line_line_overlap,
+};
+use crate::types::{
+    Angle, BoundingBox, Contour, ContourRole, Ellipse, FillRule, Intersection, IntersectionKind,
+    PathSegment, Point, PointLocation, ResolvedShape, Tolerance,
+};
+
+/// Which pieces lying on the other shape's boundary are kept
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SharedEdges {
+    None,
+    SameDirection,     // both shapes are filled on the same side of the edge
+    OppositeDirection, // the shapes are filled on opposite sides of the edge
+}
+
+/// Circle geometry of an arc-like segment as `(center, radius, from, to)`,
+/// with the angle range normalized to a counter-clockwise sweep
+fn arc_geometry(segment: &PathSegment) -> Option<(Point, f64, f64, f64)> {
+    match segment {
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let (from, to) = arc_angle_range(start_angle, end_angle);
+            Some((*center, *radius, from, to))
+        }
+        _ => None,
+    }
+}
+
+/// Ellipse geometry of a curved segment as `(ellipse, from, to)`, with the
+/// parametric range normalized to a counter-clockwise sweep
+///
+/// Circle arcs become circles, whose parametric angle is the polar angle.
+fn conic_geometry(segment: &PathSegment) -> Option<(Ellipse, f64, f64)> {
+    match segment {
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let (from, to) = arc_angle_range(start_angle, end_angle);
+            Some((*ellipse, from, to))
+        }
+        _ => arc_geometry(segment)
+            .map(|(center, radius, from, to)| (Ellipse::circle(center, radius), from, to)),
+    }
+}
+
+/// Find intersection points between two path segments
+pub fn find_segment_intersections(
+    seg1: &PathSegment,
+    seg2: &PathSegment,
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    match (seg1, seg2) {
+        (PathSegment::Line(s1, e1), PathSegment::Line(s2, e2)) => {
+            line_line_intersection(*s1, *e1, *s2, *e2, tolerance)
+        }
+        (PathSegment::Line(s, e), other) | (other, PathSegment::Line(s, e)) => {
+            if let Some((c, r, from, to)) = arc_geometry(other) {
+                line_arc_intersection(*s, *e, c, r, from, to, tolerance)
+            } else if let Some((ellipse, from, to)) = conic_geometry(other) {
+                line_ellipse_intersection(*s, *e, &ellipse, from, to, tolerance)
+            } else if let Some(curve) = cubic_control_points(other) {
+                line_bezier_intersection(*s, *e, &curve, tolerance)
+            } else {
+                Vec::new()
+            }
+        }
+        _ => match (arc_geometry(seg1), arc_geometry(seg2)) {
+            (Some((c1, r1, from1, to1)), Some((c2, r2, from2, to2))) => {
+                arc_arc_intersection(c1, r1, from1, to1, c2, r2, from2, to2, tolerance)
+            }
+            _ => match (
+                conic_geometry(seg1),
+                conic_geometry(seg2),
+                cubic_control_points(seg1),
+                cubic_control_points(seg2),
+            ) {
+                (Some((e1, from1, to1)), Some((e2, from2, to2)), _, _) => {
+                    ellipse_ellipse_intersection(&e1, from1, to1, &e2, from2, to2, tolerance)
                 }
-                (PathSegment::Line(s, e), PathSegment::Arc(c, r, start_angle, end_angle)) => {
-                    line_arc_intersection(*s, *e, *c, *r, *start_angle, *end_angle)
+                (Some((ellipse, from, to)), _, _, Some(curve))
+                | (_, Some((ellipse, from, to)), Some(curve), _) => {
+                    ellipse_bezier_intersection(&ellipse, from, to, &curve, tolerance)
                 }
-                (PathSegment::Arc(c, r, start_angle, end_angle), PathSegment::Line(s, e)) => {
-                    line_arc_intersection(*s, *e, *c, *r, *start_angle, *end_angle)
+                (_, _, Some(curve1), Some(curve2)) => {
+                    bezier_bezier_intersection(&curve1, &curve2, tolerance)
                 }
-                (
-                    PathSegment::Arc(c1, r1, start1, end1),
-                    PathSegment::Arc(c2, r2, start2, end2),
-                ) => arc_arc_intersection(*c1, *r1, *start1, *end1, *c2, *r2, *start2, *end2),
-                (
-                    PathSegment::Line(s, e),
-                    PathSegment::ConnectedArc(c, r, start_angle, end_angle, _, _),
-                ) => line_arc_intersection(*s, *e, *c, *r, *start_angle, *end_angle),
-                (
-                    PathSegment::ConnectedArc(c, r, start_angle, end_angle, _, _),
-                    PathSegment::Line(s, e),
-                ) => line_arc_intersection(*s, *e, *c, *r, *start_angle, *end_angle),
-                (
-                    PathSegment::Arc(c1, r1, start1, end1),
-                    PathSegment::ConnectedArc(c2, r2, start2, end2, _, _),
-                ) => arc_arc_intersection(*c1, *r1, *start1, *end1, *c2, *r2, *start2, *end2),
-                (
-                    PathSegment::ConnectedArc(c1, r1, start1, end1, _, _),
-                    PathSegment::Arc(c2, r2, start2, end2),
-                ) => arc_arc_intersection(*c1, *r1, *start1, *end1, *c2, *r2, *start2, *end2),
-                (
-                    PathSegment::ConnectedArc(c1, r1, start1, end1, _, _),
-                    PathSegment::ConnectedArc(c2, r2, start2, end2, _, _),
-                ) => arc_arc_intersection(*c1, *r1, *start1, *end1, *c2, *r2, *start2, *end2),
                 _ => Vec::new(),
-            };
-            intersections.extend(pts);
+            },
+        },
+    }
+}
+
+/// Find all intersections between the boundaries of two resolved shapes
+///
+/// Each record links the point to the edge it lies on in both shapes (see
+/// `shape_edges`) and its parameter there. Points found more than once, such
+/// as a vertex lying on the other boundary, are merged into one record that
+/// refers to the edge the point is interior to when there is one. Boundaries
+/// that overlap contribute the ends of each shared stretch.
+pub fn find_shape_intersections(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    tolerance: Tolerance,
+) -> Vec<Intersection> {
+    let bbox1 =
+        get_shape_bounding_box_with_tolerance(shape1, tolerance).expanded(tolerance.distance);
+    if shape1.is_empty()
+        || shape2.is_empty()
+        || !bbox1.intersects(&get_shape_bounding_box_with_tolerance(shape2, tolerance))
+    {
+        return Vec::new();
+    }
+
+    let edges1 = shape_edges(shape1, tolerance);
+    let edges2 = shape_edges(shape2, tolerance);
+    let mut intersections: Vec<Intersection> = Vec::new();
+
+    for (i, j) in candidate_segment_pairs(&edges1, &edges2, tolerance) {
+        let (seg1, seg2) = (&edges1[i], &edges2[j]);
+        for point in find_segment_intersections(seg1, seg2, tolerance) {
+            let record = intersection_record(point, (i, seg1), (j, seg2), tolerance);
+            merge_intersection(&mut intersections, record, &edges1, &edges2, tolerance);
         }
     }
 
     intersections
 }
 
-/// Check if a point is inside a shape using ray casting algorithm
-pub fn point_inside_shape(point: Point, shape: &ResolvedShape) -> bool {
-    let ray_end = Point {
-        x: point.x + 10000.0,
-        y: point.y,
+/// Find the points where the boundary of a shape meets itself
+///
+/// Both segment indices of a record refer to `shape_edges(shape)`, with
+/// `segment1 < segment2`. The vertex shared by consecutive edges of a contour
+/// is not an intersection; a contour touching itself anywhere else, or two
+/// contours meeting, is. Candidate pairs come from the sweep-line broad phase.
+pub fn find_self_intersections(shape: &ResolvedShape, tolerance: Tolerance) -> Vec<Intersection> {
+    let edges = shape_edges(shape, tolerance);
+    let mut intersections: Vec<Intersection> = Vec::new();
+    for record in self_intersection_records(shape, &edges, tolerance) {
+        merge_intersection(&mut intersections, record, &edges, &edges, tolerance);
+    }
+    intersections
+}
+
+/// Unmerged self-intersection records of `shape`, whose edges are `edges`
+fn self_intersection_records(
+    shape: &ResolvedShape,
+    edges: &[PathSegment],
+    tolerance: Tolerance,
+) -> Vec<Intersection> {
+    // Index range of every contour's edges
+    let mut ranges = Vec::new();
+    let mut first = 0;
+    for contour in &shape.contours {
+        let count = contour_edges(contour, tolerance).len();
+        ranges.push(first..first + count);
+        first += count;
+    }
+    let consecutive = |i: usize, j: usize| {
+        ranges.iter().any(|range| {
+            range.contains(&i)
+                && range.contains(&j)
+                && (j == i + 1 || (i == range.start && j + 1 == range.end))
+        })
     };
-    let mut intersection_count = 0;
 
-    for segment in &shape.segments {
-        match segment {
-            PathSegment::Line(start, end) => {
-                if !line_line_intersection(point, ray_end, *start, *end).is_empty() {
-                    intersection_count += 1;
-                }
-            }
-            PathSegment::Arc(center, radius, start_angle, end_angle) => {
-                let arc_intersections = line_arc_intersection(
-                    point,
-                    ray_end,
-                    *center,
-                    *radius,
-                    *start_angle,
-                    *end_angle,
-                );
-                intersection_count += arc_intersections.len();
-
-                // For full circles, also check if point is inside
-                if (*end_angle - *start_angle).abs() >= 360.0 - 1e-6 {
-                    if distance(point, *center) < *radius - 1e-10 {
-                        return true;
-                    }
-                }
-            }
-            PathSegment::ConnectedArc(center, radius, start_angle, end_angle, _, _) => {
-                let arc_intersections = line_arc_intersection(
-                    point,
-                    ray_end,
-                    *center,
-                    *radius,
-                    *start_angle,
-                    *end_angle,
-                );
-                intersection_count += arc_intersections.len();
+    let mut records = Vec::new();
+    for (i, j) in candidate_segment_pairs(edges, edges, tolerance) {
+        if i >= j {
+            continue;
+        }
+        let (seg1, seg2) = (&edges[i], &edges[j]);
+        for point in find_segment_intersections(seg1, seg2, tolerance) {
+            let record = intersection_record(point, (i, seg1), (j, seg2), tolerance);
+            let at_shared_vertex = !is_interior_parameter(seg1, record.param1, tolerance)
+                && !is_interior_parameter(seg2, record.param2, tolerance);
+            if !(consecutive(i, j) && at_shared_vertex) {
+                records.push(record);
             }
-            PathSegment::ClosePath => {}
-            PathSegment::DrawPoint(_) => {
-                // DrawPoint doesn't affect inside/outside testing
+        }
+    }
+    records
+}
+
+/// Build the record of `point` lying on `seg1` and `seg2`
+fn intersection_record(
+    point: Point,
+    (segment1, seg1): (usize, &PathSegment),
+    (segment2, seg2): (usize, &PathSegment),
+    tolerance: Tolerance,
+) -> Intersection {
+    let param1 = segment_parameter(seg1, point);
+    let param2 = segment_parameter(seg2, point);
+    let interior = is_interior_parameter(seg1, param1, tolerance)
+        && is_interior_parameter(seg2, param2, tolerance);
+    let transversal = match (segment_tangent(seg1, point), segment_tangent(seg2, point)) {
+        (Some(t1), Some(t2)) => {
+            let cross = t1.x * t2.y - t1.y * t2.x;
+            let lengths = t1.x.hypot(t1.y) * t2.x.hypot(t2.y);
+            cross.abs() > lengths * tolerance.angle.to_radians().sin()
+        }
+        _ => false,
+    };
+    Intersection {
+        point,
+        segment1,
+        segment2,
+        param1,
+        param2,
+        kind: if interior && transversal {
+            IntersectionKind::Crossing
+        } else {
+            IntersectionKind::Touching
+        },
+    }
+}
+
+/// Add `record` to `intersections`, merging it with a record at the same point
+///
+/// For each shape the merged record keeps an edge the point is interior to,
+/// since only those edges need to be split there.
+fn merge_intersection(
+    intersections: &mut Vec<Intersection>,
+    record: Intersection,
+    edges1: &[PathSegment],
+    edges2: &[PathSegment],
+    tolerance: Tolerance,
+) {
+    let Some(existing) = intersections
+        .iter_mut()
+        .find(|existing| distance(existing.point, record.point) < tolerance.distance)
+    else {
+        intersections.push(record);
+        return;
+    };
+
+    if !is_interior_parameter(&edges1[existing.segment1], existing.param1, tolerance)
+        && is_interior_parameter(&edges1[record.segment1], record.param1, tolerance)
+    {
+        existing.segment1 = record.segment1;
+        existing.param1 = record.param1;
+    }
+    if !is_interior_parameter(&edges2[existing.segment2], existing.param2, tolerance)
+        && is_interior_parameter(&edges2[record.segment2], record.param2, tolerance)
+    {
+        existing.segment2 = record.segment2;
+        existing.param2 = record.param2;
+    }
+    if record.kind == IntersectionKind::Crossing {
+        existing.kind = IntersectionKind::Crossing;
+    }
+}
+
+/// Distance along a segment from its start to `param`, and the segment's length
+fn distance_along(segment: &PathSegment, param: f64) -> (f64, f64) {
+    match segment {
+        PathSegment::Line(start, end) => {
+            let length = distance(*start, *end);
+            (param * length, length)
+        }
+        PathSegment::Arc(_, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(_, radius, start_angle, end_angle, direction, _, _) => (
+            (param - start_angle.degrees()).abs().to_radians() * radius,
+            arc_sweep(*start_angle, *end_angle, *direction)
+                .abs()
+                .to_radians()
+                * radius,
+        ),
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            (
+                elliptical_arc_length(ellipse, start_angle, param),
+                elliptical_arc_length(ellipse, start_angle, end_angle),
+            )
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            match cubic_control_points(segment) {
+                Some(curve) => (
+                    cubic_length(&curve, 0.0, param),
+                    cubic_length(&curve, 0.0, 1.0),
+                ),
+                None => (0.0, 0.0),
             }
         }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => (0.0, 0.0),
     }
+}
 
-    intersection_count % 2 == 1
+/// Length of a segment
+fn piece_length(segment: &PathSegment) -> f64 {
+    distance_along(segment, 0.0).1
 }
 
-/// Compute union of two shapes
-pub fn compute_union(
+/// Whether `param` lies on a segment more than the tolerance away from its ends
+fn is_interior_parameter(segment: &PathSegment, param: f64, tolerance: Tolerance) -> bool {
+    let (along, length) = distance_along(segment, param);
+    along > tolerance.distance && along < length - tolerance.distance
+}
+
+/// Find the parts of `seg1` that run along `seg2`
+///
+/// Only collinear lines, arcs of the same circle or ellipse, and copies of the
+/// same Bézier curve can overlap. Lines and curves keep the direction of
+/// `seg1`, arcs are returned counter-clockwise.
+pub fn find_segment_overlaps(
+    seg1: &PathSegment,
+    seg2: &PathSegment,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    match (seg1, seg2) {
+        (PathSegment::Line(s1, e1), PathSegment::Line(s2, e2)) => {
+            line_line_overlap(*s1, *e1, *s2, *e2, tolerance)
+                .into_iter()
+                .collect()
+        }
+        _ => match (arc_geometry(seg1), arc_geometry(seg2)) {
+            (Some((c1, r1, from1, to1)), Some((c2, r2, from2, to2))) => {
+                arc_arc_overlap(c1, r1, from1, to1, c2, r2, from2, to2, tolerance)
+            }
+            _ => match (conic_geometry(seg1), conic_geometry(seg2)) {
+                (Some((e1, from1, to1)), Some((e2, from2, to2))) => {
+                    ellipse_ellipse_overlap(&e1, from1, to1, &e2, from2, to2, tolerance)
+                }
+                _ => match (cubic_control_points(seg1), cubic_control_points(seg2)) {
+                    (Some(curve1), Some(curve2)) => {
+                        bezier_bezier_overlap(seg1, &curve1, &curve2, tolerance)
+                    }
+                    _ => Vec::new(),
+                },
+            },
+        },
+    }
+}
+
+/// Find all parts of the first shape's boundary that run along the second's
+///
+/// Both boundaries are taken as listed by `shape_edges`, so a gap closed by
+/// `ClosePath` can overlap too.
+pub fn find_shape_overlaps(
     shape1: &ResolvedShape,
     shape2: &ResolvedShape,
-    intersections: &Vec<Point>,
-) -> ResolvedShape {
-    let mut result_segments = Vec::new();
-
-    // Split segments at intersection points and keep outer boundaries
-    for segment in &shape1.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            // Keep segment if it's outside shape2 or on the boundary
-            if !point_inside_shape(midpoint, shape2) {
-                result_segments.push(*segment);
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    let edges1 = shape_edges(shape1, tolerance);
+    let edges2 = shape_edges(shape2, tolerance);
+
+    candidate_segment_pairs(&edges1, &edges2, tolerance)
+        .into_iter()
+        .flat_map(|(i, j)| find_segment_overlaps(&edges1[i], &edges2[j], tolerance))
+        .collect()
+}
+
+/// Split a segment at every point of `points` that lies on it
+///
+/// Arc pieces are emitted as `ConnectedArc`s so that the split points are
+/// stored exactly and neighbouring pieces share their endpoints. Elliptical
+/// arcs are split at the parametric angles of the cuts, and Bézier curves
+/// into pieces of the same degree ending exactly at the cut points.
+pub fn split_segment(
+    segment: &PathSegment,
+    points: &[Point],
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    let cuts: Vec<(f64, Point)> = points
+        .iter()
+        .filter(|p| {
+            distance_to_segment_with_tolerance(**p, segment, tolerance) < tolerance.distance
+        })
+        .map(|p| (segment_parameter(segment, *p), *p))
+        .collect();
+    split_segment_at(segment, &cuts, tolerance)
+}
+
+/// Split a segment at the given `(parameter, point)` cuts
+///
+/// Parameters are as in `segment_parameter`; cuts at the segment's ends are
+/// ignored.
+pub fn split_segment_at(
+    segment: &PathSegment,
+    cuts: &[(f64, Point)],
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    // (distance along the segment, parameter, split point)
+    let mut cuts: Vec<(f64, f64, Point)> = cuts
+        .iter()
+        .filter(|(param, _)| is_interior_parameter(segment, *param, tolerance))
+        .map(|&(param, point)| (distance_along(segment, param).0, param, point))
+        .collect();
+    cuts.sort_by(|a, b| a.0.total_cmp(&b.0));
+    cuts.dedup_by(|a, b| (a.0 - b.0).abs() < tolerance.distance);
+    if cuts.is_empty() {
+        return vec![*segment];
+    }
+
+    match segment {
+        PathSegment::Line(start, end) => {
+            let mut pieces = Vec::new();
+            let mut piece_start = *start;
+            for (_, _, point) in cuts {
+                pieces.push(PathSegment::Line(piece_start, point));
+                piece_start = point;
             }
+            pieces.push(PathSegment::Line(piece_start, *end));
+            pieces
         }
-    }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let (Some(start_point), Some(end_point)) = (
+                get_segment_start_point(segment),
+                get_segment_end_point(segment),
+            ) else {
+                return vec![*segment];
+            };
 
-    for segment in &shape2.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            // Keep segment if it's outside shape1
-            if !point_inside_shape(midpoint, shape1) {
-                result_segments.push(*segment);
+            let mut pieces = Vec::new();
+            let mut piece_angle = start_angle;
+            let mut piece_start = start_point;
+            for (_, angle, point) in cuts {
+                pieces.push(PathSegment::ConnectedArc(
+                    *center,
+                    *radius,
+                    Angle::from_degrees(piece_angle),
+                    Angle::from_degrees(angle),
+                    *direction,
+                    piece_start,
+                    point,
+                ));
+                piece_angle = angle;
+                piece_start = point;
             }
+            pieces.push(PathSegment::ConnectedArc(
+                *center,
+                *radius,
+                Angle::from_degrees(piece_angle),
+                Angle::from_degrees(end_angle),
+                *direction,
+                piece_start,
+                end_point,
+            ));
+            pieces
         }
-    }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let mut pieces = Vec::new();
+            let mut piece_angle = start_angle;
+            for (_, angle, _) in cuts {
+                pieces.push(PathSegment::EllipticalArc(
+                    *ellipse,
+                    Angle::from_degrees(piece_angle),
+                    Angle::from_degrees(angle),
+                    *direction,
+                ));
+                piece_angle = angle;
+            }
+            pieces.push(PathSegment::EllipticalArc(
+                *ellipse,
+                Angle::from_degrees(piece_angle),
+                Angle::from_degrees(end_angle),
+                *direction,
+            ));
+            pieces
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            let (Some(start_point), Some(end_point)) = (
+                get_segment_start_point(segment),
+                get_segment_end_point(segment),
+            ) else {
+                return vec![*segment];
+            };
 
-    // Add connecting arcs at intersection points
-    for &intersection in intersections {
-        result_segments.push(PathSegment::ConnectedArc(
-            Point { x: 0.0, y: 0.0 },
-            0.0,
-            0.0,
-            0.0,
-            intersection,
-            intersection,
-        ));
+            let mut pieces = Vec::new();
+            let mut piece_t = 0.0;
+            let mut piece_start = start_point;
+            for (_, t, point) in cuts {
+                pieces.push(bezier_piece(segment, piece_t, t, piece_start, point));
+                piece_t = t;
+                piece_start = point;
+            }
+            pieces.push(bezier_piece(segment, piece_t, 1.0, piece_start, end_point));
+            pieces
+        }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => vec![*segment],
     }
+}
 
-    if !result_segments.is_empty() {
-        result_segments.push(PathSegment::ClosePath);
+/// Drawable edges of one contour in stored order
+///
+/// `ClosePath` and `DrawPoint` are dropped, and a gap between the contour's
+/// end and its start is materialized as a closing line.
+fn contour_edges(contour: &Contour, tolerance: Tolerance) -> Vec<PathSegment> {
+    let mut path = Vec::new();
+    let mut contour_start: Option<Point> = None;
+    let mut current_point: Option<Point> = None;
+    for segment in &contour.segments {
+        match segment {
+            PathSegment::ClosePath | PathSegment::DrawPoint(_) => {}
+            _ => {
+                if contour_start.is_none() {
+                    contour_start = get_segment_start_point(segment);
+                }
+                current_point = get_segment_end_point(segment);
+                path.push(*segment);
+            }
+        }
+    }
+    if let (Some(start), Some(current)) = (contour_start, current_point)
+        && distance(start, current) > tolerance.distance
+    {
+        path.push(PathSegment::Line(current, start));
     }
+    path
+}
+
+/// Drawable edges of a shape, contour by contour in stored order
+///
+/// These are the segments that `Intersection` records index into: closing
+/// gaps become explicit lines and markers are left out.
+pub fn shape_edges(shape: &ResolvedShape, tolerance: Tolerance) -> Vec<PathSegment> {
+    shape
+        .contours
+        .iter()
+        .flat_map(|contour| contour_edges(contour, tolerance))
+        .collect()
+}
+
+/// Collect the boundary of a shape as drawable segments, with outer contours
+/// oriented counter-clockwise and holes clockwise
+fn boundary_segments(shape: &ResolvedShape, tolerance: Tolerance) -> Vec<PathSegment> {
+    split_boundary(shape, std::iter::empty(), tolerance)
+}
 
-    ResolvedShape {
-        segments: result_segments,
+/// Split the edges of a shape at `(edge index, parameter, point)` cuts,
+/// keeping the stored direction of every contour
+fn split_contours(
+    shape: &ResolvedShape,
+    cuts: impl Iterator<Item = (usize, f64, Point)>,
+    tolerance: Tolerance,
+) -> Vec<Vec<PathSegment>> {
+    let contours: Vec<Vec<PathSegment>> = shape
+        .contours
+        .iter()
+        .map(|contour| contour_edges(contour, tolerance))
+        .collect();
+    let mut cuts_by_edge: Vec<Vec<(f64, Point)>> =
+        vec![Vec::new(); contours.iter().map(Vec::len).sum()];
+    for (edge, param, point) in cuts {
+        if let Some(edge_cuts) = cuts_by_edge.get_mut(edge) {
+            edge_cuts.push((param, point));
+        }
     }
+
+    let mut edge_cuts = cuts_by_edge.iter();
+    contours
+        .iter()
+        .map(|edges| {
+            edges
+                .iter()
+                .zip(&mut edge_cuts)
+                .flat_map(|(edge, cuts)| split_segment_at(edge, cuts, tolerance))
+                .collect()
+        })
+        .collect()
 }
 
-/// Compute subtraction of two shapes
-pub fn compute_subtract(
-    shape1: &ResolvedShape,
-    shape2: &ResolvedShape,
-    _intersections: &Vec<Point>,
-) -> ResolvedShape {
-    let mut result_segments = Vec::new();
-
-    // Keep segments from shape1 that are outside shape2
-    for segment in &shape1.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            if !point_inside_shape(midpoint, shape2) {
-                result_segments.push(*segment);
+/// Split the edges of a shape at `(edge index, parameter, point)` cuts and
+/// orient the pieces like `boundary_segments`
+fn split_boundary(
+    shape: &ResolvedShape,
+    cuts: impl Iterator<Item = (usize, f64, Point)>,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    shape
+        .contours
+        .iter()
+        .zip(split_contours(shape, cuts, tolerance))
+        .flat_map(|(contour, pieces)| orient_path(&pieces, contour.role == ContourRole::Outer))
+        .collect()
+}
+
+/// Cuts on the first shape's edges given by intersection records
+fn cuts_on_first(intersections: &[Intersection]) -> impl Iterator<Item = (usize, f64, Point)> + '_ {
+    intersections.iter().map(|intersection| {
+        (
+            intersection.segment1,
+            intersection.param1,
+            intersection.point,
+        )
+    })
+}
+
+/// Cuts on the second shape's edges given by intersection records
+fn cuts_on_second(
+    intersections: &[Intersection],
+) -> impl Iterator<Item = (usize, f64, Point)> + '_ {
+    intersections.iter().map(|intersection| {
+        (
+            intersection.segment2,
+            intersection.param2,
+            intersection.point,
+        )
+    })
+}
+
+/// Keep the boundary pieces whose midpoint is inside `other`
+/// (`keep_inside`) or outside it (`!keep_inside`)
+///
+/// Pieces lying on the other shape's boundary, found through the overlaps of
+/// the two boundaries, are kept according to `shared` by comparing their
+/// direction with the coincident edge of `other`.
+fn select_pieces(
+    pieces: &[PathSegment],
+    other: &ResolvedShape,
+    keep_inside: bool,
+    shared: SharedEdges,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    let other_boundary = boundary_segments(other, tolerance);
+    // Stretches of this boundary lying on the other one, each with the edge
+    // of the other boundary it lies on
+    let overlaps: Vec<(PathSegment, PathSegment)> =
+        candidate_segment_pairs(pieces, &other_boundary, tolerance)
+            .into_iter()
+            .flat_map(|(i, j)| {
+                let edge = other_boundary[j];
+                find_segment_overlaps(&pieces[i], &edge, tolerance)
+                    .into_iter()
+                    .map(move |overlap| (overlap, edge))
+            })
+            .collect();
+
+    pieces
+        .iter()
+        .copied()
+        .filter(|piece| {
+            let midpoint = get_segment_midpoint(piece);
+            let overlap_edge = overlaps
+                .iter()
+                .find(|(overlap, _)| {
+                    distance_to_segment_with_tolerance(midpoint, overlap, tolerance)
+                        <= tolerance.distance
+                })
+                .map(|(_, edge)| edge);
+            let location = match overlap_edge {
+                Some(_) => PointLocation::OnBoundary,
+                None => classify_boundary_point(midpoint, other, &other_boundary, tolerance),
+            };
+            match location {
+                PointLocation::Inside => keep_inside,
+                PointLocation::Outside => !keep_inside,
+                PointLocation::OnBoundary => {
+                    let edge = overlap_edge.or_else(|| nearest_segment(midpoint, &other_boundary));
+                    let same_direction = edge.and_then(|edge| runs_along(piece, edge, midpoint));
+                    match shared {
+                        SharedEdges::None => false,
+                        SharedEdges::SameDirection => same_direction == Some(true),
+                        SharedEdges::OppositeDirection => same_direction == Some(false),
+                    }
+                }
             }
+        })
+        .collect()
+}
+
+/// Segment of `boundary` closest to `point`
+fn nearest_segment(point: Point, boundary: &[PathSegment]) -> Option<&PathSegment> {
+    boundary
+        .iter()
+        .min_by(|a, b| distance_to_segment(point, a).total_cmp(&distance_to_segment(point, b)))
+}
+
+/// Whether `piece` travels in the same direction as `edge` at `point`
+fn runs_along(piece: &PathSegment, edge: &PathSegment, point: Point) -> Option<bool> {
+    let piece_direction = segment_tangent(piece, point)?;
+    let edge_direction = segment_tangent(edge, point)?;
+    Some(piece_direction.x * edge_direction.x + piece_direction.y * edge_direction.y > 0.0)
+}
+
+/// Chain directed pieces into closed contours, each terminated by `ClosePath`
+///
+/// A piece continues the current contour when its start point coincides with
+/// the contour's open end.
+pub fn trace_contours(pieces: &[PathSegment], tolerance: Tolerance) -> Vec<Vec<PathSegment>> {
+    let endpoints: Vec<(Point, Point)> = pieces
+        .iter()
+        .map(|piece| {
+            let start = get_segment_start_point(piece).unwrap_or(Point { x: 0.0, y: 0.0 });
+            let end = get_segment_end_point(piece).unwrap_or(start);
+            (start, end)
+        })
+        .collect();
+    let mut used = vec![false; pieces.len()];
+    let mut loops = Vec::new();
+
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
         }
-    }
+        used[first] = true;
+        let mut contour = vec![pieces[first]];
+        let (contour_start, mut contour_end) = endpoints[first];
 
-    // Add reversed segments from shape2 that are inside shape1 (creates a hole)
-    for segment in &shape2.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            if point_inside_shape(midpoint, shape1) {
-                // Reverse the segment
-                let reversed = match segment {
-                    PathSegment::Line(s, e) => PathSegment::Line(*e, *s),
-                    PathSegment::Arc(c, r, sa, ea) => PathSegment::Arc(*c, *r, *ea, *sa),
-                    _ => *segment,
-                };
-                result_segments.push(reversed);
+        while distance(contour_end, contour_start) > tolerance.distance {
+            let candidates: Vec<usize> = (0..pieces.len())
+                .filter(|&i| !used[i] && distance(endpoints[i].0, contour_end) < tolerance.distance)
+                .collect();
+            // Where several pieces leave the same point, as at a vertex two
+            // loops touch at, take the sharpest left turn so the loops stay
+            // separate
+            let incoming = contour
+                .last()
+                .and_then(|last| segment_tangent(last, contour_end));
+            let next = match (candidates.as_slice(), incoming) {
+                ([], _) => None,
+                ([only], _) | ([only, ..], None) => Some(*only),
+                (_, Some(incoming)) => candidates.iter().copied().min_by(|&a, &b| {
+                    let turn = |i: usize| {
+                        segment_tangent(&pieces[i], endpoints[i].0)
+                            .map_or(f64::INFINITY, |outgoing| clockwise_turn(incoming, outgoing))
+                    };
+                    turn(a).total_cmp(&turn(b))
+                }),
+            };
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    contour.push(pieces[i]);
+                    contour_end = endpoints[i].1;
+                }
+                // Open chain: ClosePath below bridges the gap with a line
+                None => break,
             }
         }
+        contour.push(PathSegment::ClosePath);
+        loops.push(contour);
     }
 
-    if !result_segments.is_empty() {
-        result_segments.push(PathSegment::ClosePath);
+    loops
+}
+
+/// Clockwise angle in radians from the reverse of `incoming` to `outgoing`,
+/// in `(0, 2π]`; smaller values are sharper left turns
+fn clockwise_turn(incoming: Point, outgoing: Point) -> f64 {
+    let back = Point {
+        x: -incoming.x,
+        y: -incoming.y,
+    };
+    let counter_clockwise = (back.x * outgoing.y - back.y * outgoing.x)
+        .atan2(back.x * outgoing.x + back.y * outgoing.y);
+    let turn = (-counter_clockwise).rem_euclid(std::f64::consts::TAU);
+    if turn == 0.0 {
+        std::f64::consts::TAU
+    } else {
+        turn
     }
+}
+
+/// Build a shape from closed loops, deciding each loop's role by nesting
+///
+/// A loop nested inside an odd number of other loops is a hole. Outer
+/// contours are oriented counter-clockwise and holes clockwise.
+pub fn build_shape_from_contours(loops: Vec<Vec<PathSegment>>) -> ResolvedShape {
+    let samples: Vec<Option<Point>> = loops
+        .iter()
+        .map(|path| {
+            path.iter()
+                .find(|segment| {
+                    !matches!(segment, PathSegment::ClosePath | PathSegment::DrawPoint(_))
+                })
+                .map(get_segment_midpoint)
+        })
+        .collect();
+
+    let contours = loops
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let depth = match samples[i] {
+                Some(sample) => loops
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| j != i && point_inside_contour(sample, other))
+                    .count(),
+                None => 0,
+            };
+            if depth % 2 == 0 {
+                Contour::outer(orient_path(path, true))
+            } else {
+                Contour::hole(orient_path(path, false))
+            }
+        })
+        .collect();
+
+    ResolvedShape { contours }
+}
 
-    ResolvedShape {
-        segments: result_segments,
+/// Check if a point is inside a single closed path using the even-odd rule
+pub fn point_inside_contour(point: Point, segments: &[PathSegment]) -> bool {
+    FillRule::EvenOdd.is_inside(winding_number(point, segments))
+}
+
+/// Winding number of a shape around a point
+///
+/// Each contour is counted as if oriented by its role (outer contours
+/// counter-clockwise, holes clockwise), so a point inside a hole winds zero
+/// times regardless of how the contours were built.
+pub fn shape_winding_number(point: Point, shape: &ResolvedShape) -> i32 {
+    shape
+        .contours
+        .iter()
+        .map(|contour| {
+            let winding = winding_number(point, &contour.segments);
+            if winding == 0 {
+                return 0;
+            }
+            let counter_clockwise = signed_area_of_path(&contour.segments) >= 0.0;
+            if counter_clockwise == (contour.role == ContourRole::Outer) {
+                winding
+            } else {
+                -winding
+            }
+        })
+        .sum()
+}
+
+/// Check if a point is inside a shape under the given fill rule
+pub fn point_inside_shape_with_fill_rule(
+    point: Point,
+    shape: &ResolvedShape,
+    fill_rule: FillRule,
+) -> bool {
+    fill_rule.is_inside(shape_winding_number(point, shape))
+}
+
+/// Check if a point is inside a shape using the non-zero winding rule
+pub fn point_inside_shape(point: Point, shape: &ResolvedShape) -> bool {
+    point_inside_shape_with_fill_rule(point, shape, FillRule::NonZero)
+}
+
+/// Classify a point as inside, outside or on the boundary of a shape
+///
+/// Points within `tolerance.distance` of any boundary segment, including the implicit
+/// closing line of a contour, are reported as `OnBoundary`.
+pub fn classify_point(point: Point, shape: &ResolvedShape, tolerance: Tolerance) -> PointLocation {
+    classify_boundary_point(
+        point,
+        shape,
+        &boundary_segments(shape, tolerance),
+        tolerance,
+    )
+}
+
+/// `classify_point` with the shape's boundary segments already collected
+fn classify_boundary_point(
+    point: Point,
+    shape: &ResolvedShape,
+    boundary: &[PathSegment],
+    tolerance: Tolerance,
+) -> PointLocation {
+    if boundary.iter().any(|segment| {
+        distance_to_segment_with_tolerance(point, segment, tolerance) <= tolerance.distance
+    }) {
+        PointLocation::OnBoundary
+    } else if point_inside_shape(point, shape) {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
     }
 }
 
-/// Compute XOR of two shapes
+/// Compute union of two shapes
+///
+/// Both boundaries are split at the intersection records, the pieces lying
+/// outside the other shape are kept and then traced into closed,
+/// counter-clockwise contours. An edge shared by both shapes is kept once
+/// when both are filled on the same side of it and dropped otherwise.
+pub fn compute_union(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    intersections: &[Intersection],
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    let mut pieces = select_pieces(
+        &split_boundary(shape1, cuts_on_first(intersections), tolerance),
+        shape2,
+        false,
+        SharedEdges::SameDirection,
+        tolerance,
+    );
+    pieces.extend(select_pieces(
+        &split_boundary(shape2, cuts_on_second(intersections), tolerance),
+        shape1,
+        false,
+        SharedEdges::None,
+        tolerance,
+    ));
+
+    build_shape_from_contours(trace_contours(&pieces, tolerance))
+}
+
+/// Compute intersection of two shapes
+///
+/// Both boundaries are split at the intersection records, the pieces lying
+/// inside the other shape are kept and then traced into closed,
+/// counter-clockwise contours. An edge shared by both shapes is kept once
+/// when both are filled on the same side of it and dropped otherwise.
+pub fn compute_intersection(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    intersections: &[Intersection],
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    let mut pieces = select_pieces(
+        &split_boundary(shape1, cuts_on_first(intersections), tolerance),
+        shape2,
+        true,
+        SharedEdges::SameDirection,
+        tolerance,
+    );
+    pieces.extend(select_pieces(
+        &split_boundary(shape2, cuts_on_second(intersections), tolerance),
+        shape1,
+        true,
+        SharedEdges::None,
+        tolerance,
+    ));
+
+    build_shape_from_contours(trace_contours(&pieces, tolerance))
+}
+
+/// Compute subtraction of two shapes
+///
+/// The pieces of the first boundary outside the second shape are kept
+/// together with the reversed pieces of the second boundary inside the first
+/// shape, so a cutter fully inside the first shape becomes a hole. A shared
+/// edge survives only when the shapes are filled on opposite sides of it.
+pub fn compute_subtract(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    intersections: &[Intersection],
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    build_shape_from_contours(trace_contours(
+        &subtract_pieces(shape1, shape2, intersections, tolerance),
+        tolerance,
+    ))
+}
+
+/// Compute XOR of two shapes as the contours of both one-sided differences
 pub fn compute_xor(
     shape1: &ResolvedShape,
     shape2: &ResolvedShape,
-    _intersections: &Vec<Point>,
+    intersections: &[Intersection],
+    tolerance: Tolerance,
 ) -> ResolvedShape {
-    let mut result_segments = Vec::new();
-
-    // Keep segments from shape1 that are outside shape2
-    for segment in &shape1.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            if !point_inside_shape(midpoint, shape2) {
-                result_segments.push(*segment);
-            }
-        }
+    let mut loops = trace_contours(
+        &subtract_pieces(shape1, shape2, intersections, tolerance),
+        tolerance,
+    );
+    let swapped: Vec<Intersection> = intersections.iter().map(Intersection::swapped).collect();
+    loops.extend(trace_contours(
+        &subtract_pieces(shape2, shape1, &swapped, tolerance),
+        tolerance,
+    ));
+    build_shape_from_contours(loops)
+}
+
+/// Boundary pieces of `shape1 - shape2`
+fn subtract_pieces(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    intersections: &[Intersection],
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    let mut pieces = select_pieces(
+        &split_boundary(shape1, cuts_on_first(intersections), tolerance),
+        shape2,
+        false,
+        SharedEdges::OppositeDirection,
+        tolerance,
+    );
+    pieces.extend(
+        select_pieces(
+            &split_boundary(shape2, cuts_on_second(intersections), tolerance),
+            shape1,
+            true,
+            SharedEdges::None,
+            tolerance,
+        )
+        .iter()
+        .map(reverse_segment),
+    );
+    pieces
+}
+
+/// Rebuild a shape as simple, non-crossing contours filling the same region
+/// under `fill_rule`
+///
+/// The boundary is split wherever it meets itself. A piece is kept when the
+/// region is filled on exactly one side of it, oriented with the filled side
+/// on its left, and the kept pieces are traced into outer contours and holes.
+pub fn simplify(shape: &ResolvedShape, fill_rule: FillRule, tolerance: Tolerance) -> ResolvedShape {
+    let edges = shape_edges(shape, tolerance);
+    // Cut at every record before merging, so edges meeting at a point shared
+    // by more than two of them are all split there
+    let cuts = self_intersection_records(shape, &edges, tolerance)
+        .into_iter()
+        .flat_map(|x| {
+            [
+                (x.segment1, x.param1, x.point),
+                (x.segment2, x.param2, x.point),
+            ]
+        });
+    let pieces: Vec<PathSegment> = split_contours(shape, cuts, tolerance)
+        .into_iter()
+        .flatten()
+        .filter(|piece| piece_length(piece) > tolerance.distance)
+        .collect();
+
+    trace_region(
+        &pieces,
+        |point| fill_rule.is_inside(shape_winding_number(point, shape)),
+        tolerance,
+    )
+}
+
+/// Union of any number of shapes, computed in a single pass
+///
+/// The boundaries of all shapes are split against each other at once and a
+/// piece is kept where the region covered by at least one shape starts or
+/// ends, so no intermediate result is built or resolved again.
+pub fn union_all(shapes: &[ResolvedShape], tolerance: Tolerance) -> ResolvedShape {
+    let shapes: Vec<&ResolvedShape> = shapes.iter().collect();
+    let inside = containment_tests(&shapes);
+    trace_region(
+        &arrangement_pieces(&shapes, tolerance),
+        |point| inside.iter().any(|contains| contains(point)),
+        tolerance,
+    )
+}
+
+/// Intersection of any number of shapes, computed in a single pass
+///
+/// Like `union_all`, but keeping the region covered by every shape. No
+/// shapes give an empty result.
+pub fn intersect_all(shapes: &[ResolvedShape], tolerance: Tolerance) -> ResolvedShape {
+    if shapes.is_empty() {
+        return ResolvedShape::default();
     }
+    let shapes: Vec<&ResolvedShape> = shapes.iter().collect();
+    let inside = containment_tests(&shapes);
+    trace_region(
+        &arrangement_pieces(&shapes, tolerance),
+        |point| inside.iter().all(|contains| contains(point)),
+        tolerance,
+    )
+}
 
-    // Keep segments from shape2 that are outside shape1
-    for segment in &shape2.segments {
-        if !matches!(segment, PathSegment::ClosePath) {
-            let midpoint = get_segment_midpoint(segment);
-            if !point_inside_shape(midpoint, shape1) {
-                result_segments.push(*segment);
-            }
+/// Subtract every cutter from `base` in a single pass
+pub fn subtract_many(
+    base: &ResolvedShape,
+    cutters: &[ResolvedShape],
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    let shapes: Vec<&ResolvedShape> = std::iter::once(base).chain(cutters).collect();
+    let inside = containment_tests(&shapes);
+    trace_region(
+        &arrangement_pieces(&shapes, tolerance),
+        |point| inside[0](point) && !inside[1..].iter().any(|contains| contains(point)),
+        tolerance,
+    )
+}
+
+/// Non-zero containment test of every shape, rejecting points outside its
+/// bounding box before counting windings
+fn containment_tests<'a>(shapes: &[&'a ResolvedShape]) -> Vec<impl Fn(Point) -> bool + 'a> {
+    shapes
+        .iter()
+        .map(|&shape| {
+            let bbox = get_shape_bounding_box(shape);
+            move |point| bbox.contains(point) && point_inside_shape(point, shape)
+        })
+        .collect()
+}
+
+/// Boundaries of all `shapes` split wherever two of the shapes meet
+fn arrangement_pieces(shapes: &[&ResolvedShape], tolerance: Tolerance) -> Vec<PathSegment> {
+    let shape_edge_lists: Vec<Vec<PathSegment>> = shapes
+        .iter()
+        .map(|shape| shape_edges(shape, tolerance))
+        .collect();
+    // Owning shape and index within that shape of every edge
+    let owners: Vec<(usize, usize)> = shape_edge_lists
+        .iter()
+        .enumerate()
+        .flat_map(|(k, edges)| (0..edges.len()).map(move |i| (k, i)))
+        .collect();
+    let edges: Vec<PathSegment> = shape_edge_lists.into_iter().flatten().collect();
+
+    let mut cuts: Vec<Vec<(usize, f64, Point)>> = vec![Vec::new(); shapes.len()];
+    for (i, j) in candidate_segment_pairs(&edges, &edges, tolerance) {
+        let ((shape1, edge1), (shape2, edge2)) = (owners[i], owners[j]);
+        if shape1 >= shape2 {
+            continue;
+        }
+        for point in find_segment_intersections(&edges[i], &edges[j], tolerance) {
+            cuts[shape1].push((edge1, segment_parameter(&edges[i], point), point));
+            cuts[shape2].push((edge2, segment_parameter(&edges[j], point), point));
         }
     }
 
-    if !result_segments.is_empty() {
-        result_segments.push(PathSegment::ClosePath);
+    shapes
+        .iter()
+        .zip(cuts)
+        .flat_map(|(shape, cuts)| split_contours(shape, cuts.into_iter(), tolerance))
+        .flatten()
+        .filter(|piece| piece_length(piece) > tolerance.distance)
+        .collect()
+}
+
+/// Trace the boundary of the region where `inside` holds out of `pieces`,
+/// which must only meet at their ends
+///
+/// Every piece is tested just to its left and right. It is kept when exactly
+/// one side is inside, oriented with that side on its left; pieces lying on
+/// top of each other are considered once.
+fn trace_region(
+    pieces: &[PathSegment],
+    inside: impl Fn(Point) -> bool,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    // A side sample never moves further than the piece's length from its
+    // midpoint, so only pieces within that reach can get in the way
+    let boxes = segment_boxes(pieces, tolerance);
+    let reach: Vec<BoundingBox> = boxes
+        .iter()
+        .zip(pieces)
+        .map(|(bbox, piece)| bbox.expanded(piece_length(piece)))
+        .collect();
+    let mut neighbours = vec![Vec::new(); pieces.len()];
+    for (i, j) in overlapping_pairs(&reach, &boxes) {
+        if i != j {
+            neighbours[i].push(j);
+        }
     }
 
-    ResolvedShape {
-        segments: result_segments,
+    let mut kept = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        let midpoint = get_segment_midpoint(piece);
+        let (coincident, others): (Vec<usize>, Vec<usize>) = neighbours[i]
+            .iter()
+            .partition(|&&j| same_extent(&pieces[j], piece, tolerance));
+        if coincident.iter().any(|&j| j < i) {
+            continue;
+        }
+        let Some(tangent) = segment_tangent(piece, midpoint) else {
+            continue;
+        };
+        let speed = tangent.x.hypot(tangent.y);
+        let normal = Point {
+            x: -tangent.y / speed,
+            y: tangent.x / speed,
+        };
+        // Step off the piece by less than the gap to any other piece, and
+        // less than the distance along the normal to where the piece itself
+        // comes back round, as the far side of a thin ellipse does
+        let clearance = others
+            .iter()
+            .map(|&j| distance_to_segment_with_tolerance(midpoint, &pieces[j], tolerance))
+            .fold(piece_length(piece), f64::min);
+        let step = self_clearance(piece, midpoint, normal, clearance, tolerance) / 4.0;
+        let left = Point {
+            x: midpoint.x + normal.x * step,
+            y: midpoint.y + normal.y * step,
+        };
+        let right = Point {
+            x: midpoint.x - normal.x * step,
+            y: midpoint.y - normal.y * step,
+        };
+        match (inside(left), inside(right)) {
+            (true, false) => kept.push(*piece),
+            (false, true) => kept.push(reverse_segment(piece)),
+            _ => {}
+        }
     }
+
+    build_shape_from_contours(trace_contours(&kept, tolerance))
+}
+
+/// Distance from `point` on `piece` along the unit `normal`, either way, to
+/// where the normal line meets the piece again, capped at `reach`
+fn self_clearance(
+    piece: &PathSegment,
+    point: Point,
+    normal: Point,
+    reach: f64,
+    tolerance: Tolerance,
+) -> f64 {
+    let probe = PathSegment::Line(
+        Point {
+            x: point.x - normal.x * reach,
+            y: point.y - normal.y * reach,
+        },
+        Point {
+            x: point.x + normal.x * reach,
+            y: point.y + normal.y * reach,
+        },
+    );
+    // The crossing at `point` itself comes back with some rounding
+    let own = tolerance.distance.max(reach * 1e-9);
+    find_segment_intersections(&probe, piece, tolerance)
+        .into_iter()
+        .map(|crossing| distance(crossing, point))
+        .filter(|&gap| gap > own)
+        .fold(reach, f64::min)
+}
+
+/// Whether two pieces cover the same stretch of boundary, in either direction
+fn same_extent(a: &PathSegment, b: &PathSegment, tolerance: Tolerance) -> bool {
+    let ends = |segment: &PathSegment| {
+        (
+            get_segment_start_point(segment),
+            get_segment_end_point(segment),
+        )
+    };
+    let close = |p: Option<Point>, q: Option<Point>| match (p, q) {
+        (Some(p), Some(q)) => distance(p, q) < tolerance.distance,
+        _ => false,
+    };
+    let (a_start, a_end) = ends(a);
+    let (b_start, b_end) = ends(b);
+    let matching_ends = (close(a_start, b_start) && close(a_end, b_end))
+        || (close(a_start, b_end) && close(a_end, b_start));
+    matching_ends
+        && distance_to_segment_with_tolerance(get_segment_midpoint(a), b, tolerance)
+            < tolerance.distance
 }
 
 /// Calculate the signed area of a resolved shape
 pub fn compute_signed_area(shape: &ResolvedShape) -> f64 {
-    signed_area_of_path(&shape.segments)
+    shape
+        .contours
+        .iter()
+        .map(|contour| signed_area_of_path(&contour.segments))
+        .sum()
 }
 
-/// Calculate the absolute area of a resolved shape
+/// Calculate the absolute area of a resolved shape, subtracting its holes
 pub fn compute_area(shape: &ResolvedShape) -> f64 {
-    area_of_path(&shape.segments)
+    shape
+        .contours
+        .iter()
+        .map(|contour| match contour.role {
+            ContourRole::Outer => area_of_path(&contour.segments),
+            ContourRole::Hole => -area_of_path(&contour.segments),
+        })
+        .sum()
 }
 
 /// Check if a resolved shape has counter-clockwise orientation
 pub fn is_shape_counter_clockwise(shape: &ResolvedShape) -> bool {
-    is_counter_clockwise(&shape.segments)
+    compute_signed_area(shape) > 0.0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometry::{arc_from_span, point_on_circle};
     use crate::resolver::resolve_shape;
+    use crate::test_fixtures::{polygon, rectangle};
+    use crate::types::ArcDirection;
     use crate::types::Shape;
 
     // ...existing code...
@@ -271,7 +1296,7 @@ mod tests {
             bottom_right: Point { x: 15.0, y: 12.0 },
         });
 
-        let intersections = find_shape_intersections(&circle, &rectangle);
+        let intersections = find_shape_intersections(&circle, &rectangle, Tolerance::default());
         assert_eq!(intersections.len(), 3);
     }
 
@@ -349,4 +1374,1289 @@ mod tests {
         // Rectangle should have some orientation
         let _ = is_shape_counter_clockwise(&rectangle);
     }
+
+    fn assert_contours_connected(shape: &ResolvedShape) {
+        for contour in &shape.contours {
+            let mut previous_end: Option<Point> = None;
+            let mut contour_start: Option<Point> = None;
+            for segment in &contour.segments {
+                if matches!(segment, PathSegment::ClosePath) {
+                    continue;
+                }
+                let start = get_segment_start_point(segment).unwrap();
+                if let Some(end) = previous_end {
+                    assert!(
+                        distance(start, end) < 1e-9,
+                        "Segments should be chained end to start"
+                    );
+                }
+                contour_start.get_or_insert(start);
+                previous_end = get_segment_end_point(segment);
+            }
+            assert!(
+                distance(previous_end.unwrap(), contour_start.unwrap()) < 1e-9,
+                "Contour should end where it started"
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_segment_line() {
+        let line = PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 });
+        let pieces = split_segment(
+            &line,
+            &[
+                Point { x: 7.0, y: 0.0 },
+                Point { x: 3.0, y: 0.0 },
+                Point { x: 5.0, y: 1.0 }, // not on the line
+                Point { x: 0.0, y: 0.0 }, // endpoint
+            ],
+            Tolerance::default(),
+        );
+        assert_eq!(pieces.len(), 3);
+        match pieces[1] {
+            PathSegment::Line(start, end) => {
+                assert!((start.x - 3.0).abs() < 1e-10);
+                assert!((end.x - 7.0).abs() < 1e-10);
+            }
+            _ => panic!("Expected Line segment"),
+        }
+    }
+
+    #[test]
+    fn test_split_segment_full_circle() {
+        let circle = arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 0.0, 360.0);
+        let pieces = split_segment(
+            &circle,
+            &[Point { x: 0.0, y: 5.0 }, Point { x: -5.0, y: 0.0 }],
+            Tolerance::default(),
+        );
+        assert_eq!(pieces.len(), 3);
+        let total_sweep: f64 = pieces
+            .iter()
+            .map(|piece| match piece {
+                PathSegment::ConnectedArc(_, _, start, end, direction, _, _) => {
+                    arc_sweep(*start, *end, *direction)
+                }
+                _ => panic!("Expected ConnectedArc pieces"),
+            })
+            .sum();
+        assert!((total_sweep - 360.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_split_segment_reversed_arc() {
+        // Quarter arc traversed clockwise from 90° to 0°
+        let arc = arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 90.0, 0.0);
+        let split_point = Point {
+            x: 5.0 * 45.0_f64.to_radians().cos(),
+            y: 5.0 * 45.0_f64.to_radians().sin(),
+        };
+        let pieces = split_segment(
+            &arc,
+            &[split_point, Point { x: -5.0, y: 0.0 }],
+            Tolerance::default(),
+        );
+        assert_eq!(pieces.len(), 2);
+        match pieces[0] {
+            PathSegment::ConnectedArc(_, _, start, end, direction, _, end_pt) => {
+                assert!((start.degrees() - 90.0).abs() < 1e-9);
+                assert!((end.degrees() - 45.0).abs() < 1e-9);
+                assert_eq!(direction, ArcDirection::Clockwise);
+                assert!(distance(end_pt, split_point) < 1e-12);
+            }
+            _ => panic!("Expected ConnectedArc segment"),
+        }
+    }
+
+    #[test]
+    fn test_union_circle_rectangle_area() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5.0,
+        });
+        let rectangle = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 0.0, y: -3.0 },
+            bottom_right: Point { x: 10.0, y: 3.0 },
+        });
+
+        let intersections = find_shape_intersections(&circle, &rectangle, Tolerance::default());
+        let union = compute_union(&circle, &rectangle, &intersections, Tolerance::default());
+        assert_contours_connected(&union);
+
+        // Overlap is the part of the disk inside the strip |y| <= 3, x >= 0
+        let overlap = 12.0 + 25.0 * 0.6_f64.asin();
+        let expected = std::f64::consts::PI * 25.0 + 60.0 - overlap;
+        let area = compute_area(&union);
+        assert!(
+            (area - expected).abs() < 1e-6,
+            "Union area should be {:.6}, got {:.6}",
+            expected,
+            area
+        );
+    }
+
+    #[test]
+    fn test_union_overlapping_circles_area() {
+        let radius: f64 = 5.0;
+        let d: f64 = 6.0;
+        let circle1 = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius,
+        });
+        let circle2 = resolve_shape(&Shape::Circle {
+            center: Point { x: d, y: 0.0 },
+            radius,
+        });
+
+        let intersections = find_shape_intersections(&circle1, &circle2, Tolerance::default());
+        let union = compute_union(&circle1, &circle2, &intersections, Tolerance::default());
+        assert_contours_connected(&union);
+
+        let lens = 2.0 * radius * radius * (d / (2.0 * radius)).acos()
+            - d / 2.0 * (4.0 * radius * radius - d * d).sqrt();
+        let expected = 2.0 * std::f64::consts::PI * radius * radius - lens;
+        assert!((compute_area(&union) - expected).abs() < 1e-6);
+        assert!(is_shape_counter_clockwise(&union));
+    }
+
+    #[test]
+    fn test_union_clockwise_rectangles() {
+        // Rectangles with y growing downwards are traversed clockwise
+        let rect1 = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 0.0, y: 10.0 },
+            bottom_right: Point { x: 10.0, y: 0.0 },
+        });
+        let rect2 = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 5.0, y: 15.0 },
+            bottom_right: Point { x: 15.0, y: 5.0 },
+        });
+
+        let intersections = find_shape_intersections(&rect1, &rect2, Tolerance::default());
+        let union = compute_union(&rect1, &rect2, &intersections, Tolerance::default());
+        assert_contours_connected(&union);
+        assert!((compute_area(&union) - 175.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_union_disjoint_shapes() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 2.0,
+        });
+        let rectangle = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 10.0, y: 0.0 },
+            bottom_right: Point { x: 14.0, y: 5.0 },
+        });
+
+        let intersections = find_shape_intersections(&circle, &rectangle, Tolerance::default());
+        let union = compute_union(&circle, &rectangle, &intersections, Tolerance::default());
+
+        assert_eq!(
+            union.contours.len(),
+            2,
+            "Disjoint shapes should give two contours"
+        );
+        assert!(
+            union
+                .contours
+                .iter()
+                .all(|contour| contour.role == ContourRole::Outer)
+        );
+        let expected = std::f64::consts::PI * 4.0 + 20.0;
+        assert!((compute_area(&union) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_union_contained_shape() {
+        let big = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 10.0,
+        });
+        let small = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: -2.0, y: -2.0 },
+            bottom_right: Point { x: 2.0, y: 2.0 },
+        });
+
+        let intersections = find_shape_intersections(&big, &small, Tolerance::default());
+        let union = compute_union(&big, &small, &intersections, Tolerance::default());
+        let expected = std::f64::consts::PI * 100.0;
+        assert!((compute_area(&union) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intersection_overlapping_circles_area() {
+        let radius: f64 = 5.0;
+        let d: f64 = 6.0;
+        let circle1 = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius,
+        });
+        let circle2 = resolve_shape(&Shape::Circle {
+            center: Point { x: d, y: 0.0 },
+            radius,
+        });
+
+        let intersections = find_shape_intersections(&circle1, &circle2, Tolerance::default());
+        let lens = compute_intersection(&circle1, &circle2, &intersections, Tolerance::default());
+        assert_contours_connected(&lens);
+
+        let expected = 2.0 * radius * radius * (d / (2.0 * radius)).acos()
+            - d / 2.0 * (4.0 * radius * radius - d * d).sqrt();
+        assert!((compute_area(&lens) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_intersection_circle_rectangle_area() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5.0,
+        });
+        let rectangle = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 0.0, y: -3.0 },
+            bottom_right: Point { x: 10.0, y: 3.0 },
+        });
+
+        let intersections = find_shape_intersections(&circle, &rectangle, Tolerance::default());
+        let overlap =
+            compute_intersection(&circle, &rectangle, &intersections, Tolerance::default());
+        assert_contours_connected(&overlap);
+
+        let expected = 12.0 + 25.0 * 0.6_f64.asin();
+        assert!((compute_area(&overlap) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_intersection_with_vertex_on_circle() {
+        // (3, 0) lies on the circle, and the edge into it crosses the circle
+        // once more before getting there
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 3.0, y: 3.0 },
+            radius: 3.0,
+        });
+        let quad = polygon(&[(0.0, 1.0), (3.0, 0.0), (5.0, 1.0), (3.0, 4.0)]);
+        let tolerance = Tolerance::default();
+
+        let area = |a: &ResolvedShape, b: &ResolvedShape| {
+            let intersections = find_shape_intersections(a, b, tolerance);
+            compute_area(&compute_intersection(a, b, &intersections, tolerance))
+        };
+        let overlap = area(&quad, &circle);
+        assert!((overlap - area(&circle, &quad)).abs() < 1e-9);
+        assert!(overlap < compute_area(&quad));
+
+        let intersections = find_shape_intersections(&quad, &circle, tolerance);
+        let rest = compute_subtract(&quad, &circle, &intersections, tolerance);
+        assert!((overlap + compute_area(&rest) - compute_area(&quad)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intersection_disjoint_and_contained() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 10.0,
+        });
+        let inner = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: -2.0, y: -2.0 },
+            bottom_right: Point { x: 2.0, y: 2.0 },
+        });
+        let far = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 20.0, y: 0.0 },
+            bottom_right: Point { x: 25.0, y: 5.0 },
+        });
+
+        let contained = compute_intersection(&circle, &inner, &[], Tolerance::default());
+        assert!((compute_area(&contained) - 16.0).abs() < 1e-9);
+
+        let disjoint = compute_intersection(&circle, &far, &[], Tolerance::default());
+        assert!(disjoint.is_empty());
+    }
+
+    #[test]
+    fn test_subtract_inner_circle_makes_hole() {
+        let big = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 10.0,
+        });
+        let small = resolve_shape(&Shape::Circle {
+            center: Point { x: 2.0, y: 0.0 },
+            radius: 3.0,
+        });
+
+        let intersections = find_shape_intersections(&big, &small, Tolerance::default());
+        let ring = compute_subtract(&big, &small, &intersections, Tolerance::default());
+
+        assert_eq!(ring.contours.len(), 2);
+        let outer = ring.outer_contours().count();
+        assert_eq!(outer, 1, "Ring should have one outer boundary");
+        let hole = ring
+            .contours
+            .iter()
+            .find(|contour| contour.role == ContourRole::Hole)
+            .expect("Ring should have a hole");
+        assert!(
+            signed_area_of_path(&hole.segments) < 0.0,
+            "Holes run clockwise"
+        );
+
+        let expected = std::f64::consts::PI * (100.0 - 9.0);
+        assert!((compute_area(&ring) - expected).abs() < 1e-9);
+        assert!((compute_signed_area(&ring) - expected).abs() < 1e-9);
+
+        assert!(!point_inside_shape(Point { x: 2.0, y: 0.0 }, &ring));
+        assert!(point_inside_shape(Point { x: -8.0, y: 0.0 }, &ring));
+
+        let bbox = crate::geometry::get_shape_bounding_box(&ring);
+        assert!((bbox.min.x + 10.0).abs() < 1e-9 && (bbox.max.y - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_subtract_overlapping_rectangles() {
+        let rect1 = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 0.0, y: 0.0 },
+            bottom_right: Point { x: 10.0, y: 10.0 },
+        });
+        let rect2 = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 5.0, y: 5.0 },
+            bottom_right: Point { x: 15.0, y: 15.0 },
+        });
+
+        let intersections = find_shape_intersections(&rect1, &rect2, Tolerance::default());
+        let difference = compute_subtract(&rect1, &rect2, &intersections, Tolerance::default());
+        assert_contours_connected(&difference);
+        assert_eq!(difference.contours.len(), 1);
+        assert!((compute_area(&difference) - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_xor_overlapping_circles() {
+        let radius: f64 = 5.0;
+        let d: f64 = 6.0;
+        let circle1 = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius,
+        });
+        let circle2 = resolve_shape(&Shape::Circle {
+            center: Point { x: d, y: 0.0 },
+            radius,
+        });
+
+        let intersections = find_shape_intersections(&circle1, &circle2, Tolerance::default());
+        let xor = compute_xor(&circle1, &circle2, &intersections, Tolerance::default());
+        assert_contours_connected(&xor);
+        assert_eq!(xor.contours.len(), 2, "XOR of two circles is two crescents");
+
+        let lens = 2.0 * radius * radius * (d / (2.0 * radius)).acos()
+            - d / 2.0 * (4.0 * radius * radius - d * d).sqrt();
+        let expected = 2.0 * (std::f64::consts::PI * radius * radius - lens);
+        assert!((compute_area(&xor) - expected).abs() < 1e-6);
+        assert!(!point_inside_shape(Point { x: 3.0, y: 0.0 }, &xor));
+    }
+
+    #[test]
+    fn test_union_closing_a_notch_makes_hole() {
+        // Square with a notch cut from its right side, then a bar across the
+        // notch's mouth: the notch becomes an enclosed hole
+        let notched = resolve_shape(&Shape::Subtract(
+            Box::new(Shape::Rectangle {
+                top_left: Point { x: 0.0, y: 0.0 },
+                bottom_right: Point { x: 10.0, y: 10.0 },
+            }),
+            Box::new(Shape::Rectangle {
+                top_left: Point { x: 2.0, y: 2.0 },
+                bottom_right: Point { x: 12.0, y: 8.0 },
+            }),
+        ));
+        let bar = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 7.0, y: -1.0 },
+            bottom_right: Point { x: 11.0, y: 11.0 },
+        });
+
+        let intersections = find_shape_intersections(&notched, &bar, Tolerance::default());
+        let union = compute_union(&notched, &bar, &intersections, Tolerance::default());
+        assert_contours_connected(&union);
+
+        let roles: Vec<ContourRole> = union.contours.iter().map(|c| c.role).collect();
+        assert_eq!(roles.len(), 2);
+        assert!(roles.contains(&ContourRole::Outer) && roles.contains(&ContourRole::Hole));
+        assert!((compute_area(&union) - 88.0).abs() < 1e-9);
+        assert!(!point_inside_shape(Point { x: 4.0, y: 5.0 }, &union));
+    }
+
+    #[test]
+    fn test_from_segments_splits_at_close_path() {
+        let shape = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 1.0, y: 0.0 }, Point { x: 0.0, y: 1.0 }),
+            PathSegment::ClosePath,
+            arc_from_span(Point { x: 5.0, y: 5.0 }, 1.0, 0.0, 360.0),
+        ]);
+        assert_eq!(shape.contours.len(), 2);
+        assert_eq!(shape.contours[0].segments.len(), 3);
+        assert_eq!(shape.segments().count(), 4);
+    }
+
+    #[test]
+    fn test_point_inside_shape_fill_rules() {
+        // Two overlapping squares stored as separate outer contours
+        let shape = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 }),
+            PathSegment::ClosePath,
+            PathSegment::Line(Point { x: 5.0, y: 5.0 }, Point { x: 15.0, y: 5.0 }),
+            PathSegment::Line(Point { x: 15.0, y: 5.0 }, Point { x: 15.0, y: 15.0 }),
+            PathSegment::Line(Point { x: 15.0, y: 15.0 }, Point { x: 5.0, y: 15.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let overlap = Point { x: 7.0, y: 7.0 };
+        let single = Point { x: 2.0, y: 2.0 };
+
+        assert_eq!(shape_winding_number(overlap, &shape), 2);
+        assert!(point_inside_shape_with_fill_rule(
+            overlap,
+            &shape,
+            FillRule::NonZero
+        ));
+        assert!(!point_inside_shape_with_fill_rule(
+            overlap,
+            &shape,
+            FillRule::EvenOdd
+        ));
+        assert!(point_inside_shape_with_fill_rule(
+            single,
+            &shape,
+            FillRule::EvenOdd
+        ));
+    }
+
+    #[test]
+    fn test_point_inside_shape_circular_hole() {
+        let ring = resolve_shape(&Shape::Subtract(
+            Box::new(Shape::Circle {
+                center: Point { x: 0.0, y: 0.0 },
+                radius: 10.0,
+            }),
+            Box::new(Shape::Circle {
+                center: Point { x: 0.0, y: 0.0 },
+                radius: 4.0,
+            }),
+        ));
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            assert!(!point_inside_shape_with_fill_rule(
+                Point { x: 1.0, y: 1.0 },
+                &ring,
+                fill_rule
+            ));
+            assert!(point_inside_shape_with_fill_rule(
+                Point { x: 7.0, y: 0.0 },
+                &ring,
+                fill_rule
+            ));
+        }
+    }
+
+    #[test]
+    fn test_point_inside_shape_clockwise_outer() {
+        // Outer contour drawn clockwise still counts as filled
+        let rectangle = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 10.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 0.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 0.0, y: 10.0 }),
+        ]);
+        assert_eq!(
+            shape_winding_number(Point { x: 5.0, y: 5.0 }, &rectangle),
+            1
+        );
+        assert!(point_inside_shape(Point { x: 5.0, y: 5.0 }, &rectangle));
+    }
+
+    #[test]
+    fn test_classify_point() {
+        let rectangle = resolve_shape(&Shape::Rectangle {
+            top_left: Point { x: 0.0, y: 0.0 },
+            bottom_right: Point { x: 10.0, y: 10.0 },
+        });
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5.0,
+        });
+
+        assert_eq!(
+            classify_point(Point { x: 5.0, y: 5.0 }, &rectangle, Tolerance::default()),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            classify_point(Point { x: 15.0, y: 5.0 }, &rectangle, Tolerance::default()),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            classify_point(Point { x: 10.0, y: 5.0 }, &rectangle, Tolerance::default()),
+            PointLocation::OnBoundary
+        );
+        assert_eq!(
+            classify_point(Point { x: 3.0, y: 4.0 }, &circle, Tolerance::default()),
+            PointLocation::OnBoundary
+        );
+        assert_eq!(
+            classify_point(Point { x: 3.0, y: 4.1 }, &circle, Tolerance::default()),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            classify_point(Point { x: 3.0, y: 4.1 }, &circle, Tolerance::new(0.2, 1e-6)),
+            PointLocation::OnBoundary
+        );
+    }
+
+    #[test]
+    fn test_union_adjacent_rectangles_merges_shared_edge() {
+        let left = rectangle(0.0, 0.0, 10.0, 10.0);
+        let right = rectangle(10.0, 0.0, 20.0, 10.0);
+        let intersections = find_shape_intersections(&left, &right, Tolerance::default());
+        let union = compute_union(&left, &right, &intersections, Tolerance::default());
+
+        assert_eq!(union.contours.len(), 1);
+        assert_contours_connected(&union);
+        assert!((compute_area(&union) - 200.0).abs() < 1e-9);
+        assert_eq!(
+            classify_point(Point { x: 10.0, y: 5.0 }, &union, Tolerance::default()),
+            PointLocation::Inside
+        );
+
+        let intersection =
+            compute_intersection(&left, &right, &intersections, Tolerance::default());
+        assert!(intersection.is_empty());
+    }
+
+    #[test]
+    fn test_subtract_rectangle_sharing_edge() {
+        let base = rectangle(0.0, 0.0, 10.0, 10.0);
+        let cutter = rectangle(5.0, 0.0, 10.0, 10.0);
+        let intersections = find_shape_intersections(&base, &cutter, Tolerance::default());
+        let result = compute_subtract(&base, &cutter, &intersections, Tolerance::default());
+
+        assert_eq!(result.contours.len(), 1);
+        assert_contours_connected(&result);
+        assert!((compute_area(&result) - 50.0).abs() < 1e-9);
+
+        // Cutting with an adjacent rectangle leaves the base untouched
+        let neighbour = rectangle(10.0, 0.0, 20.0, 10.0);
+        let intersections = find_shape_intersections(&base, &neighbour, Tolerance::default());
+        let result = compute_subtract(&base, &neighbour, &intersections, Tolerance::default());
+        assert!((compute_area(&result) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_boolean_ops_identical_shapes() {
+        for shape in [
+            rectangle(0.0, 0.0, 10.0, 10.0),
+            resolve_shape(&Shape::Circle {
+                center: Point { x: 0.0, y: 0.0 },
+                radius: 5.0,
+            }),
+        ] {
+            let area = compute_area(&shape);
+            let intersections = find_shape_intersections(&shape, &shape, Tolerance::default());
+
+            let union = compute_union(&shape, &shape, &intersections, Tolerance::default());
+            assert_eq!(union.contours.len(), 1);
+            assert!((compute_area(&union) - area).abs() < 1e-9);
+
+            let intersection =
+                compute_intersection(&shape, &shape, &intersections, Tolerance::default());
+            assert_eq!(intersection.contours.len(), 1);
+            assert!((compute_area(&intersection) - area).abs() < 1e-9);
+
+            assert!(
+                compute_subtract(&shape, &shape, &intersections, Tolerance::default()).is_empty()
+            );
+            assert!(compute_xor(&shape, &shape, &intersections, Tolerance::default()).is_empty());
+        }
+    }
+
+    fn half_disk(start_angle: f64) -> ResolvedShape {
+        // Half of a radius 5 disk at the origin, bounded by an arc and a diameter
+        let end_angle = start_angle + 180.0;
+        let start = point_on_circle(Point { x: 0.0, y: 0.0 }, 5.0, start_angle);
+        let end = point_on_circle(Point { x: 0.0, y: 0.0 }, 5.0, end_angle);
+        ResolvedShape::from_segments(vec![
+            arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, start_angle, end_angle),
+            PathSegment::Line(end, start),
+            PathSegment::ClosePath,
+        ])
+    }
+
+    #[test]
+    fn test_find_shape_overlaps() {
+        let upper = half_disk(0.0);
+        let right = half_disk(-90.0);
+        let overlaps = find_shape_overlaps(&upper, &right, Tolerance::default());
+        // A quarter arc, and no shared straight edge
+        assert_eq!(overlaps.len(), 1);
+        assert!(matches!(overlaps[0], PathSegment::Arc(..)));
+
+        let left = rectangle(0.0, 0.0, 10.0, 10.0);
+        let right = rectangle(10.0, 5.0, 20.0, 15.0);
+        let overlaps = find_shape_overlaps(&left, &right, Tolerance::default());
+        assert_eq!(overlaps.len(), 1);
+        match overlaps[0] {
+            PathSegment::Line(from, to) => {
+                assert!((distance(from, to) - 5.0).abs() < 1e-12);
+            }
+            _ => panic!("Expected Line segment"),
+        }
+
+        // The left side of this square is only the gap its ClosePath closes
+        let open_left = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let beside = rectangle(-5.0, 2.0, 0.0, 8.0);
+        let overlaps = find_shape_overlaps(&open_left, &beside, Tolerance::default());
+        assert_eq!(overlaps.len(), 1);
+        match overlaps[0] {
+            PathSegment::Line(from, to) => {
+                assert!(from.x.abs() < 1e-12 && to.x.abs() < 1e-12);
+                assert!((distance(from, to) - 6.0).abs() < 1e-12);
+            }
+            _ => panic!("Expected Line segment"),
+        }
+    }
+
+    #[test]
+    fn test_boolean_ops_cocircular_half_disks() {
+        let upper = half_disk(0.0);
+        let right = half_disk(-90.0);
+        let tolerance = Tolerance::default();
+        let intersections = find_shape_intersections(&upper, &right, tolerance);
+        let disk = std::f64::consts::PI * 25.0;
+
+        let union = compute_union(&upper, &right, &intersections, tolerance);
+        assert_eq!(union.contours.len(), 1);
+        assert_contours_connected(&union);
+        assert!((compute_area(&union) - disk * 0.75).abs() < 1e-9);
+
+        let intersection = compute_intersection(&upper, &right, &intersections, tolerance);
+        assert_eq!(intersection.contours.len(), 1);
+        assert!((compute_area(&intersection) - disk * 0.25).abs() < 1e-9);
+
+        let difference = compute_subtract(&upper, &right, &intersections, tolerance);
+        assert_eq!(difference.contours.len(), 1);
+        assert!((compute_area(&difference) - disk * 0.25).abs() < 1e-9);
+
+        let xor = compute_xor(&upper, &right, &intersections, tolerance);
+        assert_eq!(xor.contours.len(), 2);
+        assert!((compute_area(&xor) - disk * 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_union_collinear_split_edges() {
+        // Both shared edges are split at vertices the other shape does not
+        // have, so only the collinear overlap marks where sharing starts
+        let left = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 3.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 3.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let right = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 20.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 20.0, y: 0.0 }, Point { x: 20.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 20.0, y: 10.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 10.0, y: 7.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 7.0 }, Point { x: 10.0, y: 0.0 }),
+        ]);
+        let tolerance = Tolerance::default();
+        let intersections = find_shape_intersections(&left, &right, tolerance);
+        let union = compute_union(&left, &right, &intersections, tolerance);
+
+        assert_eq!(union.contours.len(), 1);
+        assert_contours_connected(&union);
+        assert!((compute_area(&union) - 200.0).abs() < 1e-9);
+    }
+
+    /// Check that every record lies on the edges it refers to at its parameters
+    fn assert_records_consistent(
+        intersections: &[Intersection],
+        shape1: &ResolvedShape,
+        shape2: &ResolvedShape,
+    ) {
+        let tolerance = Tolerance::default();
+        let edges1 = shape_edges(shape1, tolerance);
+        let edges2 = shape_edges(shape2, tolerance);
+        for record in intersections {
+            for (edge, param) in [
+                (&edges1[record.segment1], record.param1),
+                (&edges2[record.segment2], record.param2),
+            ] {
+                assert!(distance_to_segment(record.point, edge) < 1e-9);
+                assert!((segment_parameter(edge, record.point) - param).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersection_arc_parameters_are_absolute_angles() {
+        // The upper part of a circle of radius 2, from 30° to 150°, crossed by
+        // the sides of a strip at x = ±0.5
+        let center = Point { x: 0.0, y: 0.0 };
+        let strip = rectangle(-0.5, 0.0, 0.5, 3.0);
+        let crossing = (0.25f64).acos().to_degrees();
+        for direction in [ArcDirection::CounterClockwise, ArcDirection::Clockwise] {
+            let (start, end) = match direction {
+                ArcDirection::CounterClockwise => (30.0, 150.0),
+                ArcDirection::Clockwise => (150.0, 30.0),
+            };
+            let cap = ResolvedShape::from_segments(vec![
+                PathSegment::Arc(
+                    center,
+                    2.0,
+                    Angle::from_degrees(start),
+                    Angle::from_degrees(end),
+                    direction,
+                ),
+                PathSegment::Line(
+                    point_on_circle(center, 2.0, end),
+                    point_on_circle(center, 2.0, start),
+                ),
+                PathSegment::ClosePath,
+            ]);
+            let intersections = find_shape_intersections(&cap, &strip, Tolerance::default());
+            let mut params: Vec<f64> = intersections
+                .iter()
+                .filter(|x| x.segment1 == 0)
+                .map(|x| x.param1)
+                .collect();
+            params.sort_by(f64::total_cmp);
+            assert_eq!(params.len(), 2);
+            assert!((params[0] - crossing).abs() < 1e-9);
+            assert!((params[1] - (180.0 - crossing)).abs() < 1e-9);
+            assert_records_consistent(&intersections, &cap, &strip);
+        }
+    }
+
+    #[test]
+    fn test_find_shape_intersections_records() {
+        let a = rectangle(0.0, 0.0, 10.0, 10.0);
+        let b = rectangle(5.0, 5.0, 15.0, 15.0);
+        let intersections = find_shape_intersections(&a, &b, Tolerance::default());
+
+        assert_eq!(intersections.len(), 2);
+        assert!(
+            intersections
+                .iter()
+                .all(|x| x.kind == IntersectionKind::Crossing)
+        );
+        assert_records_consistent(&intersections, &a, &b);
+
+        let reversed = find_shape_intersections(&b, &a, Tolerance::default());
+        for record in &intersections {
+            assert!(reversed.contains(&record.swapped()));
+        }
+    }
+
+    #[test]
+    fn test_find_shape_intersections_arc_parameters() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5.0,
+        });
+        let band = rectangle(0.0, -3.0, 20.0, 3.0);
+        let intersections = find_shape_intersections(&circle, &band, Tolerance::default());
+
+        assert_eq!(intersections.len(), 2);
+        assert_records_consistent(&intersections, &circle, &band);
+        for record in &intersections {
+            let angle = record.point.y.atan2(record.point.x).to_degrees();
+            assert!((record.param1.rem_euclid(360.0) - angle.rem_euclid(360.0)).abs() < 1e-9);
+            assert_eq!(record.kind, IntersectionKind::Crossing);
+        }
+    }
+
+    #[test]
+    fn test_find_shape_intersections_merges_vertex_on_edge() {
+        // The triangle's apex touches the middle of the square's right edge
+        let square = rectangle(0.0, 0.0, 10.0, 10.0);
+        let triangle = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 10.0, y: 5.0 }, Point { x: 15.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 15.0, y: 0.0 }, Point { x: 15.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 15.0, y: 10.0 }, Point { x: 10.0, y: 5.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let intersections = find_shape_intersections(&square, &triangle, Tolerance::default());
+
+        assert_eq!(intersections.len(), 1);
+        let record = intersections[0];
+        assert!(distance(record.point, Point { x: 10.0, y: 5.0 }) < 1e-9);
+        assert_eq!(record.kind, IntersectionKind::Touching);
+        assert!((record.param1 - 0.5).abs() < 1e-9);
+        assert_records_consistent(&intersections, &square, &triangle);
+
+        let union = compute_union(&square, &triangle, &intersections, Tolerance::default());
+        assert!((compute_area(&union) - 125.0).abs() < 1e-9);
+    }
+
+    /// Closed polygon through `count` points at radius `radius(angle)` around the origin
+    fn radial_polygon(count: usize, radius: impl Fn(f64) -> f64) -> ResolvedShape {
+        let points: Vec<Point> = (0..count)
+            .map(|k| {
+                let angle = k as f64 * 360.0 / count as f64;
+                point_on_circle(Point { x: 0.0, y: 0.0 }, radius(angle), angle)
+            })
+            .collect();
+        let mut segments: Vec<PathSegment> = (0..count)
+            .map(|k| PathSegment::Line(points[k], points[(k + 1) % count]))
+            .collect();
+        segments.push(PathSegment::ClosePath);
+        ResolvedShape::from_segments(segments)
+    }
+
+    #[test]
+    fn test_find_shape_intersections_large_inputs() {
+        // A wavy outline crossing a circle 100 times, 5000 edges each; the
+        // pairwise search would run 25 million exact segment tests
+        let count = 5000;
+        let wavy = radial_polygon(count, |angle| {
+            10.0 + (50.0 * angle + 1.0).to_radians().sin()
+        });
+        let circle = radial_polygon(count, |_| 10.0);
+
+        let started = std::time::Instant::now();
+        let intersections = find_shape_intersections(&wavy, &circle, Tolerance::default());
+        let elapsed = started.elapsed();
+
+        assert_eq!(intersections.len(), 100);
+        assert!(
+            intersections
+                .iter()
+                .all(|x| x.kind == IntersectionKind::Crossing)
+        );
+        assert_records_consistent(&intersections, &wavy, &circle);
+        assert!(
+            elapsed < std::time::Duration::from_secs(2),
+            "took {elapsed:?}"
+        );
+    }
+
+    /// Check that no contour of `shape` crosses or touches itself
+    fn assert_contours_simple(shape: &ResolvedShape) {
+        for contour in &shape.contours {
+            let single = ResolvedShape {
+                contours: vec![contour.clone()],
+            };
+            assert!(find_self_intersections(&single, Tolerance::default()).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_find_self_intersections() {
+        let tolerance = Tolerance::default();
+        assert!(find_self_intersections(&rectangle(0.0, 0.0, 2.0, 1.0), tolerance).is_empty());
+        assert!(find_self_intersections(&half_disk(30.0), tolerance).is_empty());
+
+        let bowtie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        let intersections = find_self_intersections(&bowtie, tolerance);
+        assert_eq!(intersections.len(), 1);
+        let record = intersections[0];
+        assert!(distance(record.point, Point { x: 1.0, y: 1.0 }) < 1e-9);
+        assert_eq!(record.kind, IntersectionKind::Crossing);
+        assert!(record.segment1 < record.segment2);
+        assert_records_consistent(&intersections, &bowtie, &bowtie);
+    }
+
+    #[test]
+    fn test_simplify_bowtie() {
+        let bowtie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        assert!(compute_signed_area(&bowtie).abs() < 1e-9);
+
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let simple = simplify(&bowtie, fill_rule, Tolerance::default());
+            assert_eq!(simple.contours.len(), 2);
+            assert_eq!(simple.outer_contours().count(), 2);
+            assert_contours_simple(&simple);
+            assert!((compute_signed_area(&simple) - 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_simplify_pentagram_fill_rules() {
+        let points: Vec<(f64, f64)> = (0..5)
+            .map(|k| {
+                let p = point_on_circle(Point { x: 0.0, y: 0.0 }, 1.0, 90.0 + 144.0 * k as f64);
+                (p.x, p.y)
+            })
+            .collect();
+        let star = polygon(&points);
+        assert_eq!(
+            find_self_intersections(&star, Tolerance::default()).len(),
+            5
+        );
+
+        // Radius of the inner pentagon the edges cross at
+        let inner = 72f64.to_radians().cos() / 36f64.to_radians().cos();
+        let star_area = 5.0 * inner * 36f64.to_radians().sin();
+        let pentagon_area = 2.5 * inner * inner * 72f64.to_radians().sin();
+
+        let non_zero = simplify(&star, FillRule::NonZero, Tolerance::default());
+        assert_eq!(non_zero.contours.len(), 1);
+        assert_contours_simple(&non_zero);
+        assert!((compute_area(&non_zero) - star_area).abs() < 1e-9);
+
+        let even_odd = simplify(&star, FillRule::EvenOdd, Tolerance::default());
+        assert_eq!(even_odd.contours.len(), 5);
+        assert_contours_simple(&even_odd);
+        assert!((compute_area(&even_odd) - (star_area - pentagon_area)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simplify_doubled_contour() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let doubled = polygon(&[square, square].concat());
+
+        let non_zero = simplify(&doubled, FillRule::NonZero, Tolerance::default());
+        assert_eq!(non_zero.contours.len(), 1);
+        assert!((compute_signed_area(&non_zero) - 1.0).abs() < 1e-9);
+
+        let even_odd = simplify(&doubled, FillRule::EvenOdd, Tolerance::default());
+        assert!(even_odd.is_empty());
+    }
+
+    /// Fold two-shape unions over `shapes`, as a nested `Shape::Union` would
+    fn union_pairwise(shapes: &[ResolvedShape]) -> ResolvedShape {
+        let tolerance = Tolerance::default();
+        shapes[1..].iter().fold(shapes[0].clone(), |acc, shape| {
+            let intersections = find_shape_intersections(&acc, shape, tolerance);
+            compute_union(&acc, shape, &intersections, tolerance)
+        })
+    }
+
+    #[test]
+    fn test_union_all_rectangles() {
+        let shapes = [
+            rectangle(0.0, 0.0, 2.0, 2.0),
+            rectangle(1.0, 1.0, 3.0, 3.0),
+            rectangle(2.0, 0.0, 4.0, 2.0),
+        ];
+        let union = union_all(&shapes, Tolerance::default());
+        assert_eq!(union.contours.len(), 1);
+        assert_contours_connected(&union);
+        assert!((compute_signed_area(&union) - 10.0).abs() < 1e-9);
+
+        assert!(union_all(&[], Tolerance::default()).is_empty());
+    }
+
+    #[test]
+    fn test_union_all_matches_pairwise_union() {
+        let circles: Vec<ResolvedShape> = (0..12)
+            .map(|k| {
+                resolve_shape(&Shape::Circle {
+                    center: Point {
+                        x: k as f64 * 1.5,
+                        y: (k % 3) as f64 * 0.5,
+                    },
+                    radius: 1.0,
+                })
+            })
+            .collect();
+        let union = union_all(&circles, Tolerance::default());
+        let expected = union_pairwise(&circles);
+
+        assert_eq!(union.contours.len(), expected.contours.len());
+        assert!((compute_area(&union) - compute_area(&expected)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_pass_ops_on_thin_ellipses() {
+        let tolerance = Tolerance::default();
+        // Side samples on a thin ellipse must not step across to its far side
+        let thin = ResolvedShape::from_segments(vec![PathSegment::EllipticalArc(
+            crate::types::Ellipse {
+                center: Point { x: 0.0, y: 0.0 },
+                radius_x: 4.0,
+                radius_y: 0.5,
+                rotation: Angle::ZERO,
+            },
+            Angle::from_degrees(90.0),
+            Angle::from_degrees(450.0),
+            ArcDirection::CounterClockwise,
+        )]);
+        let pi = std::f64::consts::PI;
+        let simplified = simplify(&thin, FillRule::NonZero, tolerance);
+        assert!((compute_area(&simplified) - 2.0 * pi).abs() < 1e-9);
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 10.0, y: 0.0 },
+            radius: 1.0,
+        });
+        let union = union_all(&[thin, circle], tolerance);
+        assert!((compute_area(&union) - 3.0 * pi).abs() < 1e-9);
+
+        let rectangle = rectangle(3.0, 1.0, 4.0, 4.0);
+        let ellipse = resolve_shape(&Shape::Ellipse {
+            center: Point { x: 0.0, y: 4.0 },
+            radius_x: 4.0602,
+            radius_y: 0.9443,
+            rotation: Angle::from_degrees(11.74),
+        });
+        let expected = compute_area(&union_pairwise(&[rectangle.clone(), ellipse.clone()]));
+        for shapes in [
+            [rectangle.clone(), ellipse.clone()],
+            [ellipse.clone(), rectangle.clone()],
+        ] {
+            let union = union_all(&shapes, tolerance);
+            assert!((compute_area(&union) - expected).abs() < 1e-9);
+        }
+        let intersections = find_shape_intersections(&ellipse, &rectangle, tolerance);
+        let pairwise = compute_subtract(&ellipse, &rectangle, &intersections, tolerance);
+        let difference = subtract_many(&ellipse, &[rectangle], tolerance);
+        assert!((compute_area(&difference) - compute_area(&pairwise)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_union_all_rounded_rectangles_sharing_an_edge() {
+        // Both left edges lie on x = 3, where each one is touched by the
+        // other's rounded corner
+        let rounded = |x0, y0, x1, y1| {
+            crate::fillet::fillet(
+                &rectangle(x0, y0, x1, y1),
+                0.5,
+                &crate::types::CornerSelector::All,
+            )
+        };
+        let shapes = [
+            rounded(3.0, 1.0, 5.849, 6.153),
+            rounded(3.0, 5.0, 5.278, 8.253),
+        ];
+        let pairwise = union_pairwise(&shapes);
+        assert_eq!(pairwise.contours.len(), 1);
+        for shapes in [shapes.clone(), [shapes[1].clone(), shapes[0].clone()]] {
+            let union = union_all(&shapes, Tolerance::default());
+            assert_eq!(union.contours.len(), 1);
+            assert_contours_connected(&union);
+            assert!((compute_area(&union) - compute_area(&pairwise)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_union_all_many_parts() {
+        // 500 overlapping squares whose union is a single rectangle; many of
+        // their edges overlap collinearly
+        let squares: Vec<ResolvedShape> = (0..500)
+            .map(|k| {
+                let x = (k % 25) as f64;
+                let y = (k / 25) as f64;
+                rectangle(x, y, x + 1.5, y + 1.5)
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let union = union_all(&squares, Tolerance::default());
+        let elapsed = started.elapsed();
+
+        assert_eq!(union.contours.len(), 1);
+        assert!((compute_signed_area(&union) - 25.5 * 20.5).abs() < 1e-9);
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn test_intersect_all() {
+        let circles: Vec<ResolvedShape> = [(0.0, 0.0), (1.0, 0.0), (0.5, 0.8)]
+            .iter()
+            .map(|&(x, y)| {
+                resolve_shape(&Shape::Circle {
+                    center: Point { x, y },
+                    radius: 1.0,
+                })
+            })
+            .collect();
+        let tolerance = Tolerance::default();
+        let common = intersect_all(&circles, tolerance);
+        let pairwise = circles[1..].iter().fold(circles[0].clone(), |acc, shape| {
+            compute_intersection(
+                &acc,
+                shape,
+                &find_shape_intersections(&acc, shape, tolerance),
+                tolerance,
+            )
+        });
+
+        assert_eq!(common.contours.len(), 1);
+        assert!(compute_area(&common) > 0.0);
+        assert!((compute_area(&common) - compute_area(&pairwise)).abs() < 1e-9);
+
+        let far = rectangle(10.0, 10.0, 11.0, 11.0);
+        assert!(intersect_all(&[circles[0].clone(), far], tolerance).is_empty());
+        assert!(intersect_all(&[], tolerance).is_empty());
+    }
+
+    #[test]
+    fn test_subtract_many() {
+        let base = rectangle(0.0, 0.0, 10.0, 10.0);
+        let cutters = [
+            rectangle(2.0, 2.0, 3.0, 3.0),
+            rectangle(6.0, 2.0, 7.0, 3.0),
+            rectangle(2.0, 6.0, 3.0, 7.0),
+            // Bites into the right edge
+            rectangle(9.0, 6.0, 11.0, 7.0),
+        ];
+        let result = subtract_many(&base, &cutters, Tolerance::default());
+
+        assert_eq!(result.outer_contours().count(), 1);
+        assert_eq!(result.contours.len(), 4);
+        assert_contours_connected(&result);
+        assert!((compute_area(&result) - 96.0).abs() < 1e-9);
+        assert!(!point_inside_shape(Point { x: 2.5, y: 2.5 }, &result));
+        assert!(point_inside_shape(Point { x: 5.0, y: 5.0 }, &result));
+
+        let unchanged = subtract_many(&base, &[], Tolerance::default());
+        assert!((compute_area(&unchanged) - 100.0).abs() < 1e-9);
+    }
+
+    fn ellipse(center: Point, radius_x: f64, radius_y: f64, rotation: f64) -> ResolvedShape {
+        resolve_shape(&Shape::Ellipse {
+            center,
+            radius_x,
+            radius_y,
+            rotation: Angle::from_degrees(rotation),
+        })
+    }
+
+    #[test]
+    fn test_point_inside_shape_ellipse() {
+        let shape = ellipse(Point { x: 1.0, y: 1.0 }, 5.0, 1.0, 45.0);
+        let along = |d: f64| Point {
+            x: 1.0 + d / 2f64.sqrt(),
+            y: 1.0 + d / 2f64.sqrt(),
+        };
+        assert!(point_inside_shape(along(0.0), &shape));
+        assert!(point_inside_shape(along(4.9), &shape));
+        assert!(point_inside_shape(along(-4.9), &shape));
+        assert!(!point_inside_shape(along(5.1), &shape));
+        // Across the minor axis
+        assert!(point_inside_shape(Point { x: 0.4, y: 1.6 }, &shape));
+        assert!(!point_inside_shape(Point { x: 0.0, y: 2.0 }, &shape));
+    }
+
+    #[test]
+    fn test_ellipse_rectangle_boolean_areas() {
+        let (a, b, c) = (4.0, 2.0, 2.0);
+        let shape = ellipse(Point { x: 0.0, y: 0.0 }, a, b, 0.0);
+        let rect = rectangle(-10.0, -10.0, c, 10.0);
+        let intersections = find_shape_intersections(&shape, &rect, Tolerance::default());
+        assert_eq!(intersections.len(), 2);
+        assert_records_consistent(&intersections, &shape, &rect);
+
+        // Area of the ellipse left of x = c: a·b·(u√(1-u²) + asin(u) + π/2), u = c/a
+        let u: f64 = c / a;
+        let left = a * b * (u * (1.0 - u * u).sqrt() + u.asin() + std::f64::consts::FRAC_PI_2);
+        let total = std::f64::consts::PI * a * b;
+
+        let inside = compute_intersection(&shape, &rect, &intersections, Tolerance::default());
+        assert!((compute_area(&inside) - left).abs() < 1e-9);
+        let outside = compute_subtract(&shape, &rect, &intersections, Tolerance::default());
+        assert!((compute_area(&outside) - (total - left)).abs() < 1e-9);
+        assert_contours_connected(&outside);
+    }
+
+    #[test]
+    fn test_crossed_ellipses_union() {
+        // Two ellipses crossed at right angles; inclusion-exclusion ties the
+        // union and the intersection together
+        let center = Point { x: 0.0, y: 0.0 };
+        let wide = ellipse(center, 3.0, 1.0, 0.0);
+        let tall = ellipse(center, 3.0, 1.0, 90.0);
+        let tolerance = Tolerance::default();
+        let intersections = find_shape_intersections(&wide, &tall, tolerance);
+        assert_eq!(intersections.len(), 4);
+        assert!(
+            intersections
+                .iter()
+                .all(|record| record.kind == IntersectionKind::Crossing)
+        );
+
+        let union = compute_union(&wide, &tall, &intersections, tolerance);
+        let common = compute_intersection(&wide, &tall, &intersections, tolerance);
+        assert_eq!(union.contours.len(), 1);
+        assert_eq!(common.contours.len(), 1);
+        let each = std::f64::consts::PI * 3.0;
+        assert!((compute_area(&union) + compute_area(&common) - 2.0 * each).abs() < 1e-9);
+
+        // Each of the eight pieces of the common part between an axis and a
+        // diagonal is a sector of the narrower ellipse, of area a·b·t/2 with
+        // the diagonal at parametric angle t = atan(1/3)
+        let expected = 8.0 * 3.0 * (1.0f64 / 3.0).atan() / 2.0;
+        assert!((compute_area(&common) - expected).abs() < 1e-9);
+    }
+
+    /// Region between the x axis and the parabola y = u·(2 - u), u = x - x0
+    fn parabola_region(x0: f64, cubic: bool) -> ResolvedShape {
+        let (start, end) = (
+            Point { x: x0, y: 0.0 },
+            Point {
+                x: x0 + 2.0,
+                y: 0.0,
+            },
+        );
+        let curve = if cubic {
+            PathSegment::CubicBezier(
+                start,
+                Point {
+                    x: x0 + 2.0 / 3.0,
+                    y: 4.0 / 3.0,
+                },
+                Point {
+                    x: x0 + 4.0 / 3.0,
+                    y: 4.0 / 3.0,
+                },
+                end,
+            )
+        } else {
+            PathSegment::QuadBezier(
+                start,
+                Point {
+                    x: x0 + 1.0,
+                    y: 2.0,
+                },
+                end,
+            )
+        };
+        let segments = [curve, PathSegment::Line(end, start), PathSegment::ClosePath];
+        ResolvedShape {
+            contours: vec![Contour::outer(orient_path(&segments, true))],
+        }
+    }
+
+    #[test]
+    fn test_bezier_boolean_areas() {
+        let tolerance = Tolerance::default();
+        let region = parabola_region(0.0, false);
+        assert!((compute_area(&region) - 4.0 / 3.0).abs() < 1e-12);
+
+        // Left half: ∫₀¹ x·(2 - x) dx = 2/3
+        let half = rectangle(-1.0, -1.0, 1.0, 5.0);
+        let intersections = find_shape_intersections(&region, &half, tolerance);
+        assert_records_consistent(&intersections, &region, &half);
+        let left = compute_intersection(&region, &half, &intersections, tolerance);
+        assert!((compute_area(&left) - 2.0 / 3.0).abs() < 1e-9);
+        let right = compute_subtract(&region, &half, &intersections, tolerance);
+        assert!((compute_area(&right) - 2.0 / 3.0).abs() < 1e-9);
+        assert_contours_connected(&right);
+
+        // Two regions a unit apart share the part under both curves, which
+        // meet at x = 1.5: 2·∫ x·(2 - x) dx over [1.5, 2] = 5/12
+        let shifted = parabola_region(1.0, true);
+        let intersections = find_shape_intersections(&region, &shifted, tolerance);
+        let common = compute_intersection(&region, &shifted, &intersections, tolerance);
+        assert!((compute_area(&common) - 5.0 / 12.0).abs() < 1e-9);
+        let union = compute_union(&region, &shifted, &intersections, tolerance);
+        assert_eq!(union.contours.len(), 1);
+        assert!((compute_area(&union) - (8.0 / 3.0 - 5.0 / 12.0)).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,133 @@
+use crate::geometry::get_segment_bounding_box;
+use crate::types::{BoundingBox, PathSegment, Point, Tolerance};
+
+/// Find every pair `(i, j)` such that `boxes1[i]` and `boxes2[j]` intersect
+///
+/// Sweeps a vertical line across both lists in order of `min.x`, keeping the
+/// boxes it currently crosses active, so only boxes that overlap in x are
+/// compared. Pairs are returned sorted by `i`, then `j`.
+pub fn overlapping_pairs(boxes1: &[BoundingBox], boxes2: &[BoundingBox]) -> Vec<(usize, usize)> {
+    // (box, side, index in its list)
+    let mut events: Vec<(&BoundingBox, usize, usize)> = boxes1
+        .iter()
+        .enumerate()
+        .map(|(i, bbox)| (bbox, 0, i))
+        .chain(boxes2.iter().enumerate().map(|(j, bbox)| (bbox, 1, j)))
+        .collect();
+    events.sort_by(|a, b| a.0.min.x.total_cmp(&b.0.min.x));
+
+    let mut active: [Vec<(&BoundingBox, usize)>; 2] = [Vec::new(), Vec::new()];
+    let mut pairs = Vec::new();
+    for (bbox, side, index) in events {
+        let other = 1 - side;
+        active[other].retain(|(open, _)| open.max.x >= bbox.min.x);
+        for (open, open_index) in &active[other] {
+            if open.min.y <= bbox.max.y && bbox.min.y <= open.max.y {
+                pairs.push(if side == 0 {
+                    (index, *open_index)
+                } else {
+                    (*open_index, index)
+                });
+            }
+        }
+        active[side].push((bbox, index));
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Bounding boxes of `segments` grown by the distance tolerance
+///
+/// `ClosePath` has no extent and gets an empty box that intersects nothing.
+pub fn segment_boxes(segments: &[PathSegment], tolerance: Tolerance) -> Vec<BoundingBox> {
+    segments
+        .iter()
+        .map(|segment| match get_segment_bounding_box(segment) {
+            Some(bbox) => bbox.expanded(tolerance.distance),
+            None => BoundingBox {
+                min: Point {
+                    x: f64::INFINITY,
+                    y: f64::INFINITY,
+                },
+                max: Point {
+                    x: f64::NEG_INFINITY,
+                    y: f64::NEG_INFINITY,
+                },
+            },
+        })
+        .collect()
+}
+
+/// Pairs of segments from the two lists whose bounding boxes meet, the only
+/// candidates for an intersection or overlap
+pub fn candidate_segment_pairs(
+    segments1: &[PathSegment],
+    segments2: &[PathSegment],
+    tolerance: Tolerance,
+) -> Vec<(usize, usize)> {
+    overlapping_pairs(
+        &segment_boxes(segments1, tolerance),
+        &segment_boxes(segments2, tolerance),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bbox(x0: f64, y0: f64, x1: f64, y1: f64) -> BoundingBox {
+        BoundingBox::from_points(&[Point { x: x0, y: y0 }, Point { x: x1, y: y1 }])
+    }
+
+    #[test]
+    fn test_overlapping_pairs_matches_brute_force() {
+        // A staggered grid of small boxes against a set of long thin ones
+        let boxes1: Vec<BoundingBox> = (0..60)
+            .map(|k| {
+                let x = (k % 10) as f64 * 1.5;
+                let y = (k / 10) as f64 * 1.3;
+                bbox(x, y, x + 1.0, y + 1.0)
+            })
+            .collect();
+        let boxes2: Vec<BoundingBox> = (0..20)
+            .map(|k| {
+                let t = k as f64 * 0.7;
+                if k % 2 == 0 {
+                    bbox(t, -1.0, t + 0.2, 10.0)
+                } else {
+                    bbox(-1.0, t, 20.0, t + 0.1)
+                }
+            })
+            .collect();
+
+        let mut expected = Vec::new();
+        for (i, a) in boxes1.iter().enumerate() {
+            for (j, b) in boxes2.iter().enumerate() {
+                if a.intersects(b) {
+                    expected.push((i, j));
+                }
+            }
+        }
+
+        assert!(!expected.is_empty());
+        assert_eq!(overlapping_pairs(&boxes1, &boxes2), expected);
+    }
+
+    #[test]
+    fn test_overlapping_pairs_touching_boxes() {
+        let left = [bbox(0.0, 0.0, 1.0, 1.0)];
+        let right = [bbox(1.0, 1.0, 2.0, 2.0), bbox(1.0 + 1e-12, 0.0, 2.0, 1.0)];
+        assert_eq!(overlapping_pairs(&left, &right), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_candidate_segment_pairs_skip_close_path() {
+        let segments = [
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }),
+            PathSegment::ClosePath,
+        ];
+        let pairs = candidate_segment_pairs(&segments, &segments, Tolerance::default());
+        assert_eq!(pairs, vec![(0, 0)]);
+    }
+}
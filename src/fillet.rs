@@ -0,0 +1,646 @@
+use crate::geometry::{
+    distance_to_segment_with_tolerance, get_segment_end_point, get_segment_start_point,
+    point_on_circle, segment_length, segment_parameter, segment_tangent, signed_area_of_path,
+};
+use crate::types::{
+    Angle, ArcDirection, Contour, ContourRole, CornerSelector, PathSegment, Point, ResolvedShape,
+    Tolerance,
+};
+
+/// Round off the selected corners of a shape with arcs of `radius`
+///
+/// See `fillet_with_tolerance`.
+pub fn fillet(shape: &ResolvedShape, radius: f64, corners: &CornerSelector) -> ResolvedShape {
+    fillet_with_tolerance(shape, radius, corners, Tolerance::default())
+}
+
+/// Round off the selected corners of a shape with arcs of `radius`
+///
+/// Each corner between two lines or circular arcs is replaced by an arc
+/// tangent to both, on the side the boundary turns towards, and the two
+/// segments are trimmed back to where the arc touches them. Corners next to
+/// elliptical arcs or Bézier curves, corners whose arc would not fit on the
+/// segments, and both corners at the ends of a segment too short for their
+/// two arcs are left sharp.
+pub fn fillet_with_tolerance(
+    shape: &ResolvedShape,
+    radius: f64,
+    corners: &CornerSelector,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    if !radius.is_finite() || radius <= 0.0 {
+        return shape.clone();
+    }
+    cut_corners(shape, corners, tolerance, |before, after, turn| {
+        fillet_corner(before, after, turn, radius, tolerance)
+    })
+}
+
+/// Cut the selected corners of a shape off with straight lines
+///
+/// See `chamfer_with_tolerance`.
+pub fn chamfer(shape: &ResolvedShape, distance: f64, corners: &CornerSelector) -> ResolvedShape {
+    chamfer_with_tolerance(shape, distance, corners, Tolerance::default())
+}
+
+/// Cut the selected corners of a shape off with straight lines
+///
+/// Each corner between two lines or circular arcs is replaced by a line
+/// between the points `distance` along each segment from the corner,
+/// measured along arcs rather than across them. Corners are left sharp under
+/// the same conditions as with `fillet_with_tolerance`.
+pub fn chamfer_with_tolerance(
+    shape: &ResolvedShape,
+    distance: f64,
+    corners: &CornerSelector,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    if !distance.is_finite() || distance <= 0.0 {
+        return shape.clone();
+    }
+    cut_corners(shape, corners, tolerance, |before, after, _| {
+        let before_point = point_along(before, segment_length(before) - distance)?;
+        let after_point = point_along(after, distance)?;
+        Some(Cut {
+            before: before_point,
+            after: after_point,
+            joint: PathSegment::Line(before_point, after_point),
+        })
+    })
+}
+
+/// What replaces the tip of a corner
+struct Cut {
+    before: Point, // new end of the segment into the corner
+    after: Point,  // new start of the segment out of it
+    joint: PathSegment,
+}
+
+fn cut_corners(
+    shape: &ResolvedShape,
+    selector: &CornerSelector,
+    tolerance: Tolerance,
+    cut: impl Fn(&PathSegment, &PathSegment, f64) -> Option<Cut>,
+) -> ResolvedShape {
+    let contours = shape
+        .contours
+        .iter()
+        .map(|contour| Contour {
+            segments: cut_contour(contour, selector, tolerance, &cut),
+            role: contour.role,
+        })
+        .collect();
+    ResolvedShape { contours }
+}
+
+fn cut_contour(
+    contour: &Contour,
+    selector: &CornerSelector,
+    tolerance: Tolerance,
+    cut: &impl Fn(&PathSegment, &PathSegment, f64) -> Option<Cut>,
+) -> Vec<PathSegment> {
+    let close_path = matches!(contour.segments.last(), Some(PathSegment::ClosePath));
+    let segments: Vec<PathSegment> = contour
+        .segments
+        .iter()
+        .filter(|segment| !matches!(segment, PathSegment::ClosePath))
+        .copied()
+        .collect();
+    let count = segments.len();
+    if count < 2 {
+        return contour.segments.clone();
+    }
+    let closed = close_path
+        || matches!(
+            (get_segment_start_point(&segments[0]), get_segment_end_point(&segments[count - 1])),
+            (Some(first), Some(last)) if first.distance_to(last) <= tolerance.distance
+        );
+    // The filled region is on the left of travel for a counter-clockwise
+    // outer contour and for a clockwise hole
+    let filled_left =
+        (signed_area_of_path(&contour.segments) >= 0.0) == (contour.role == ContourRole::Outer);
+
+    // cuts[i] is the corner between segment i and the one after it
+    let mut cuts: Vec<Option<Cut>> = (0..count)
+        .map(|i| {
+            if !closed && i + 1 == count {
+                return None;
+            }
+            let before = &segments[i];
+            let after = &segments[(i + 1) % count];
+            let turn = corner_turn(before, after, tolerance)?;
+            let corner = get_segment_end_point(before)?;
+            if !selector.selects(corner, (turn > 0.0) == filled_left) {
+                return None;
+            }
+            cut(before, after, turn)
+        })
+        .collect();
+
+    // Trimming a segment from both ends must leave something of it
+    for i in 0..count {
+        let previous = (i + count - 1) % count;
+        let overlaps = match (&cuts[previous], &cuts[i]) {
+            (Some(entry), Some(exit)) => {
+                along(&segments[i], entry.after)
+                    > along(&segments[i], exit.before) + tolerance.distance
+            }
+            _ => false,
+        };
+        if overlaps {
+            cuts[previous] = None;
+            cuts[i] = None;
+        }
+    }
+
+    let mut path = Vec::with_capacity(2 * count + 1);
+    for (i, segment) in segments.iter().enumerate() {
+        let previous = (i + count - 1) % count;
+        let start = cuts[previous].as_ref().map(|cut| cut.after);
+        let end = cuts[i].as_ref().map(|cut| cut.before);
+        let from = start.map_or(0.0, |point| along(segment, point));
+        let to = end.map_or(segment_length(segment), |point| along(segment, point));
+        // A segment used up entirely by its two cuts disappears
+        if to - from > tolerance.distance {
+            path.push(trimmed(segment, start, end));
+        }
+        if let Some(cut) = &cuts[i] {
+            path.push(cut.joint);
+        }
+    }
+    if close_path {
+        path.push(PathSegment::ClosePath);
+    }
+    path
+}
+
+/// Signed angle in degrees the boundary turns through from `before` into
+/// `after`, positive to the left, or `None` where they don't form a corner
+/// that can be cut
+fn corner_turn(before: &PathSegment, after: &PathSegment, tolerance: Tolerance) -> Option<f64> {
+    let supported = |segment: &PathSegment| {
+        matches!(
+            segment,
+            PathSegment::Line(..) | PathSegment::Arc(..) | PathSegment::ConnectedArc(..)
+        )
+    };
+    if !supported(before) || !supported(after) {
+        return None;
+    }
+    let corner = get_segment_end_point(before)?;
+    if corner.distance_to(get_segment_start_point(after)?) > tolerance.distance {
+        return None;
+    }
+    let incoming = segment_tangent(before, corner)?;
+    let outgoing = segment_tangent(after, corner)?;
+    let cross = incoming.x * outgoing.y - incoming.y * outgoing.x;
+    let dot = incoming.x * outgoing.x + incoming.y * outgoing.y;
+    let turn = cross.atan2(dot).to_degrees();
+    // Smooth joins and cusps where the boundary doubles back have no corner
+    (turn.abs() > tolerance.angle && 180.0 - turn.abs() > tolerance.angle).then_some(turn)
+}
+
+/// A line or circle every point of which is the same distance from one side
+/// of a segment
+enum Guide {
+    Line(Point, Point), // a point on the line, unit direction
+    Circle(Point, f64), // center, radius
+}
+
+/// The curve through the points `shift` to the left of a segment, or to the
+/// right when `shift` is negative
+fn guide(segment: &PathSegment, shift: f64) -> Option<Guide> {
+    match segment {
+        PathSegment::Line(start, end) => {
+            let length = start.distance_to(*end);
+            if length == 0.0 {
+                return None;
+            }
+            let direction = Point {
+                x: (end.x - start.x) / length,
+                y: (end.y - start.y) / length,
+            };
+            Some(Guide::Line(
+                Point {
+                    x: start.x - shift * direction.y,
+                    y: start.y + shift * direction.x,
+                },
+                direction,
+            ))
+        }
+        PathSegment::Arc(center, radius, _, _, direction)
+        | PathSegment::ConnectedArc(center, radius, _, _, direction, _, _) => {
+            // The left of counter-clockwise travel faces the center
+            let radius = radius - direction.sign() * shift;
+            (radius > 0.0).then_some(Guide::Circle(*center, radius))
+        }
+        _ => None,
+    }
+}
+
+fn line_circle_crossings(point: Point, direction: Point, center: Point, radius: f64) -> Vec<Point> {
+    let t = (center.x - point.x) * direction.x + (center.y - point.y) * direction.y;
+    let foot = Point {
+        x: point.x + t * direction.x,
+        y: point.y + t * direction.y,
+    };
+    let squared = radius * radius - foot.distance_to(center).powi(2);
+    if squared < 0.0 {
+        return Vec::new();
+    }
+    let half = squared.sqrt();
+    [-half, half]
+        .iter()
+        .map(|s| Point {
+            x: foot.x + s * direction.x,
+            y: foot.y + s * direction.y,
+        })
+        .collect()
+}
+
+fn crossings(first: &Guide, second: &Guide) -> Vec<Point> {
+    match (first, second) {
+        (Guide::Line(p1, d1), Guide::Line(p2, d2)) => {
+            let denominator = d1.x * d2.y - d1.y * d2.x;
+            if denominator.abs() < 1e-12 {
+                return Vec::new();
+            }
+            let t = ((p2.x - p1.x) * d2.y - (p2.y - p1.y) * d2.x) / denominator;
+            vec![Point {
+                x: p1.x + t * d1.x,
+                y: p1.y + t * d1.y,
+            }]
+        }
+        (Guide::Line(point, direction), Guide::Circle(center, radius))
+        | (Guide::Circle(center, radius), Guide::Line(point, direction)) => {
+            line_circle_crossings(*point, *direction, *center, *radius)
+        }
+        (Guide::Circle(c1, r1), Guide::Circle(c2, r2)) => {
+            let d = c1.distance_to(*c2);
+            if d == 0.0 {
+                return Vec::new();
+            }
+            let a = (r1 * r1 - r2 * r2 + d * d) / (2.0 * d);
+            let squared = r1 * r1 - a * a;
+            if squared < 0.0 {
+                return Vec::new();
+            }
+            let h = squared.sqrt();
+            let ux = (c2.x - c1.x) / d;
+            let uy = (c2.y - c1.y) / d;
+            [-h, h]
+                .iter()
+                .map(|s| Point {
+                    x: c1.x + a * ux - s * uy,
+                    y: c1.y + a * uy + s * ux,
+                })
+                .collect()
+        }
+    }
+}
+
+/// The point of a line or circle arc's full line or circle closest to `point`
+fn foot(segment: &PathSegment, point: Point) -> Option<Point> {
+    match segment {
+        PathSegment::Line(start, end) => {
+            let dx = end.x - start.x;
+            let dy = end.y - start.y;
+            let t = ((point.x - start.x) * dx + (point.y - start.y) * dy) / (dx * dx + dy * dy);
+            Some(Point {
+                x: start.x + t * dx,
+                y: start.y + t * dy,
+            })
+        }
+        PathSegment::Arc(center, radius, ..) | PathSegment::ConnectedArc(center, radius, ..) => {
+            let angle = (point.y - center.y).atan2(point.x - center.x).to_degrees();
+            Some(point_on_circle(*center, *radius, angle))
+        }
+        _ => None,
+    }
+}
+
+/// Arc of `radius` tangent to both segments, found as the crossing nearest
+/// the corner of the two curves `radius` away from them on the side the
+/// boundary turns towards
+fn fillet_corner(
+    before: &PathSegment,
+    after: &PathSegment,
+    turn: f64,
+    radius: f64,
+    tolerance: Tolerance,
+) -> Option<Cut> {
+    let corner = get_segment_end_point(before)?;
+    let shift = turn.signum() * radius;
+    let center = crossings(&guide(before, shift)?, &guide(after, shift)?)
+        .into_iter()
+        .min_by(|a, b| a.distance_to(corner).total_cmp(&b.distance_to(corner)))?;
+    let before_point = foot(before, center)?;
+    let after_point = foot(after, center)?;
+    if distance_to_segment_with_tolerance(before_point, before, tolerance) > tolerance.distance
+        || distance_to_segment_with_tolerance(after_point, after, tolerance) > tolerance.distance
+    {
+        return None;
+    }
+    let angle_of =
+        |point: Point| Angle::from_radians((point.y - center.y).atan2(point.x - center.x));
+    let direction = if turn > 0.0 {
+        ArcDirection::CounterClockwise
+    } else {
+        ArcDirection::Clockwise
+    };
+    Some(Cut {
+        before: before_point,
+        after: after_point,
+        joint: PathSegment::ConnectedArc(
+            center,
+            radius,
+            angle_of(before_point),
+            angle_of(after_point),
+            direction,
+            before_point,
+            after_point,
+        ),
+    })
+}
+
+/// Distance along a line or circle arc from its start to a point on it
+fn along(segment: &PathSegment, point: Point) -> f64 {
+    match segment {
+        PathSegment::Arc(_, radius, start_angle, _, direction)
+        | PathSegment::ConnectedArc(_, radius, start_angle, _, direction, _, _) => {
+            let turned =
+                direction.sign() * (segment_parameter(segment, point) - start_angle.degrees());
+            radius * turned.to_radians()
+        }
+        _ => segment_parameter(segment, point) * segment_length(segment),
+    }
+}
+
+/// The point `distance` along a line or circle arc from its start, if the
+/// segment is that long
+fn point_along(segment: &PathSegment, distance: f64) -> Option<Point> {
+    if distance < 0.0 || distance > segment_length(segment) {
+        return None;
+    }
+    match segment {
+        PathSegment::Line(start, end) => {
+            let t = distance / start.distance_to(*end);
+            Some(Point {
+                x: start.x + t * (end.x - start.x),
+                y: start.y + t * (end.y - start.y),
+            })
+        }
+        PathSegment::Arc(center, radius, start_angle, _, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, _, direction, _, _) => {
+            let angle = start_angle.degrees() + direction.sign() * (distance / radius).to_degrees();
+            Some(point_on_circle(*center, *radius, angle))
+        }
+        _ => None,
+    }
+}
+
+/// A line or circle arc cut down to run from `start` to `end`, each of which
+/// keeps the segment's own end when `None`
+fn trimmed(segment: &PathSegment, start: Option<Point>, end: Option<Point>) -> PathSegment {
+    if start.is_none() && end.is_none() {
+        return *segment;
+    }
+    match segment {
+        PathSegment::Line(from, to) => {
+            PathSegment::Line(start.unwrap_or(*from), end.unwrap_or(*to))
+        }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let angle_of =
+                |point: Point| Angle::from_radians((point.y - center.y).atan2(point.x - center.x));
+            let from = get_segment_start_point(segment).unwrap_or(*center);
+            let to = get_segment_end_point(segment).unwrap_or(*center);
+            PathSegment::ConnectedArc(
+                *center,
+                *radius,
+                start.map_or(*start_angle, angle_of),
+                end.map_or(*end_angle, angle_of),
+                *direction,
+                start.unwrap_or(from),
+                end.unwrap_or(to),
+            )
+        }
+        _ => *segment,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean_ops::compute_area;
+    use crate::geometry::distance;
+    use crate::test_fixtures::{l_shape, polygon};
+    use std::f64::consts::PI;
+
+    /// Centers of the arcs of `radius` on the first contour
+    fn joints(shape: &ResolvedShape, radius: f64) -> Vec<Point> {
+        shape.contours[0]
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                PathSegment::ConnectedArc(center, r, ..) if *r == radius => Some(*center),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Consecutive segments meet
+    fn assert_connected(shape: &ResolvedShape) {
+        for contour in &shape.contours {
+            let segments: Vec<&PathSegment> = contour
+                .segments
+                .iter()
+                .filter(|segment| !matches!(segment, PathSegment::ClosePath))
+                .collect();
+            for (i, segment) in segments.iter().enumerate() {
+                let next = segments[(i + 1) % segments.len()];
+                let end = get_segment_end_point(segment).unwrap();
+                let start = get_segment_start_point(next).unwrap();
+                assert!(distance(end, start) < 1e-9);
+            }
+        }
+    }
+
+    /// Every arc leaves its neighbours in the direction they travel
+    fn assert_tangent(shape: &ResolvedShape) {
+        let segments: Vec<&PathSegment> = shape.contours[0]
+            .segments
+            .iter()
+            .filter(|segment| !matches!(segment, PathSegment::ClosePath))
+            .collect();
+        for (i, segment) in segments.iter().enumerate() {
+            if !matches!(segment, PathSegment::ConnectedArc(..)) {
+                continue;
+            }
+            let count = segments.len();
+            for (a, b) in [
+                (segments[(i + count - 1) % count], *segment),
+                (*segment, segments[(i + 1) % count]),
+            ] {
+                let point = get_segment_end_point(a).unwrap();
+                let t1 = segment_tangent(a, point).unwrap();
+                let t2 = segment_tangent(b, point).unwrap();
+                let cross = t1.x * t2.y - t1.y * t2.x;
+                let dot = t1.x * t2.x + t1.y * t2.y;
+                assert!(cross.atan2(dot).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fillet_and_chamfer_rectangle() {
+        let rectangle = polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)]);
+
+        let rounded = fillet(&rectangle, 0.5, &CornerSelector::All);
+        assert_connected(&rounded);
+        assert_tangent(&rounded);
+        assert_eq!(joints(&rounded, 0.5).len(), 4);
+        assert!((compute_area(&rounded) - (8.0 - (4.0 - PI) * 0.25)).abs() < 1e-9);
+
+        let cut = chamfer(&rectangle, 0.5, &CornerSelector::All);
+        assert_connected(&cut);
+        assert!((compute_area(&cut) - (8.0 - 4.0 * 0.125)).abs() < 1e-9);
+
+        // Only the corner picked out is touched
+        let one = fillet(
+            &rectangle,
+            0.5,
+            &CornerSelector::Near(vec![Point { x: 4.0, y: 2.0 }], 0.1),
+        );
+        assert_eq!(joints(&one, 0.5), vec![Point { x: 3.5, y: 1.5 }]);
+
+        // Radius 1 uses up the short sides exactly; anything larger doesn't fit
+        let stadium = fillet(&rectangle, 1.0, &CornerSelector::All);
+        assert_connected(&stadium);
+        assert!((compute_area(&stadium) - (8.0 - (4.0 - PI))).abs() < 1e-9);
+        assert_eq!(
+            stadium.contours[0].segments.len(),
+            7,
+            "two long sides, four arcs and the ClosePath"
+        );
+        let too_big = fillet(&rectangle, 1.5, &CornerSelector::All);
+        assert!(joints(&too_big, 1.5).is_empty());
+        assert!((compute_area(&too_big) - 8.0).abs() < 1e-9);
+
+        // Sizes that are not positive and finite leave the shape alone
+        for size in [0.0, -0.5, f64::NAN, f64::INFINITY] {
+            for shape in [
+                fillet(&rectangle, size, &CornerSelector::All),
+                chamfer(&rectangle, size, &CornerSelector::All),
+            ] {
+                assert_eq!(shape.contours[0].segments.len(), 5);
+                assert!((compute_area(&shape) - 8.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fillet_concave_corners() {
+        let l_shape = l_shape();
+        let rounded = fillet(&l_shape, 0.25, &CornerSelector::Concave);
+        assert_connected(&rounded);
+        assert_tangent(&rounded);
+        assert_eq!(joints(&rounded, 0.25), vec![Point { x: 1.25, y: 1.25 }]);
+        assert!((compute_area(&rounded) - (3.0 + (1.0 - PI / 4.0) * 0.0625)).abs() < 1e-9);
+
+        let convex = fillet(&l_shape, 0.25, &CornerSelector::Convex);
+        assert_eq!(joints(&convex, 0.25).len(), 5);
+
+        // A square hole has concave corners only
+        let mut frame = polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+        let hole = polygon(&[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)]);
+        frame
+            .contours
+            .push(Contour::hole(hole.contours[0].segments.clone()));
+        let rounded = fillet(&frame, 0.5, &CornerSelector::Concave);
+        assert!(joints(&rounded, 0.5).is_empty());
+        assert_eq!(
+            rounded.contours[1]
+                .segments
+                .iter()
+                .filter(|segment| matches!(segment, PathSegment::ConnectedArc(..)))
+                .count(),
+            4
+        );
+        assert!((compute_area(&rounded) - (12.0 + (4.0 - PI) * 0.25)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fillet_line_arc_and_arc_arc_corners() {
+        // Half disc: two corners between the diameter and the arc
+        let half_disc = ResolvedShape::from_segments(vec![
+            PathSegment::Arc(
+                Point { x: 0.0, y: 0.0 },
+                2.0,
+                Angle::ZERO,
+                Angle::from_degrees(180.0),
+                ArcDirection::CounterClockwise,
+            ),
+            PathSegment::Line(Point { x: -2.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let rounded = fillet(&half_disc, 0.5, &CornerSelector::All);
+        assert_connected(&rounded);
+        assert_tangent(&rounded);
+        let centers = joints(&rounded, 0.5);
+        assert_eq!(centers.len(), 2);
+        for center in centers {
+            assert!((center.x.abs() - 2.0f64.sqrt()).abs() < 1e-9);
+            assert!((center.y - 0.5).abs() < 1e-9);
+        }
+        let area = compute_area(&rounded);
+        assert!(area < 2.0 * PI && area > 2.0 * PI - 0.2);
+
+        // Lens between two discs: two corners between arcs
+        let (low, high) = (
+            Point {
+                x: 0.0,
+                y: -3f64.sqrt(),
+            },
+            Point {
+                x: 0.0,
+                y: 3f64.sqrt(),
+            },
+        );
+        let lens = ResolvedShape::from_segments(vec![
+            PathSegment::ConnectedArc(
+                Point { x: 1.0, y: 0.0 },
+                2.0,
+                Angle::from_degrees(240.0),
+                Angle::from_degrees(120.0),
+                ArcDirection::Clockwise,
+                low,
+                high,
+            ),
+            PathSegment::ConnectedArc(
+                Point { x: -1.0, y: 0.0 },
+                2.0,
+                Angle::from_degrees(60.0),
+                Angle::from_degrees(-60.0),
+                ArcDirection::Clockwise,
+                high,
+                low,
+            ),
+            PathSegment::ClosePath,
+        ]);
+        // Clockwise lens as an outer contour: the filled side is on the right
+        let rounded = fillet(&lens, 0.25, &CornerSelector::Convex);
+        assert_connected(&rounded);
+        assert_tangent(&rounded);
+        let centers = joints(&rounded, 0.25);
+        assert_eq!(centers.len(), 2);
+        for center in centers {
+            assert!(center.x.abs() < 1e-9);
+            assert!((center.y.abs() - (1.75f64 * 1.75 - 1.0).sqrt()).abs() < 1e-9);
+        }
+
+        let cut = chamfer(&lens, 0.5, &CornerSelector::All);
+        assert_connected(&cut);
+        assert!(compute_area(&cut) < compute_area(&lens));
+    }
+}
@@ -1,4 +1,14 @@
-use crate::types::{BoundingBox, PathSegment, Point, ResolvedShape};
+use crate::bezier::{
+    closest_cubic_parameter, cubic_area_term, cubic_axis_extremes, cubic_control_points,
+    cubic_length, cubic_point, cubic_tangent, cubic_winding,
+};
+use crate::types::{
+    Angle, ArcDirection, BoundingBox, Contour, Ellipse, PathSegment, Point, ResolvedShape,
+    Tolerance,
+};
+
+/// Number of points each elliptical arc or Bézier curve adds to a convex hull
+const HULL_CURVE_POINTS: usize = 64;
 
 /// Calculate distance between two points
 pub fn distance(p1: Point, p2: Point) -> f64 {
@@ -19,7 +29,9 @@ pub fn point_inside_rectangle(point: Point, top_left: Point, bottom_right: Point
 }
 
 /// Helper function to check if an angle is within an arc range
-pub fn is_angle_in_arc(angle: f64, start_angle: f64, end_angle: f64) -> bool {
+///
+/// The range is widened by `tolerance.angle` degrees on both ends.
+pub fn is_angle_in_arc(angle: f64, start_angle: f64, end_angle: f64, tolerance: Tolerance) -> bool {
     // Normalize angles to [0, 360)
     let mut a = angle % 360.0;
     if a < 0.0 {
@@ -37,15 +49,532 @@ pub fn is_angle_in_arc(angle: f64, start_angle: f64, end_angle: f64) -> bool {
     }
 
     // Handle full circle case
-    if (end - start).abs() < 1e-10 || ((end - start).abs() - 360.0).abs() < 1e-10 {
+    let epsilon = tolerance.angle;
+    if (end_angle - start_angle).abs() >= 360.0 - epsilon
+        || (end - start).abs() < epsilon
+        || ((end - start).abs() - 360.0).abs() < epsilon
+    {
         return true;
     }
 
     // Handle arc crossing 0 degrees
     if start <= end {
-        a >= start - 1e-6 && a <= end + 1e-6
+        a >= start - epsilon && a <= end + epsilon
+    } else {
+        a >= start - epsilon || a <= end + epsilon
+    }
+}
+
+/// Get the point at `angle` degrees on a circle
+pub fn point_on_circle(center: Point, radius: f64, angle: f64) -> Point {
+    let rad = angle.to_radians();
+    Point {
+        x: center.x + radius * rad.cos(),
+        y: center.y + radius * rad.sin(),
+    }
+}
+
+/// Convert an arc's signed sweep (from `start_angle` to `end_angle`) into the
+/// counter-clockwise `(from, to)` range expected by `is_angle_in_arc`
+pub fn arc_angle_range(start_angle: f64, end_angle: f64) -> (f64, f64) {
+    if end_angle >= start_angle {
+        (start_angle, end_angle)
     } else {
-        a >= start - 1e-6 || a <= end + 1e-6
+        (end_angle, start_angle)
+    }
+}
+
+/// Signed sweep of an arc in degrees, positive counter-clockwise
+///
+/// The sweep follows `direction` from `start_angle` to `end_angle` and spans
+/// at most a full turn; angles a non-zero multiple of 360° apart give the
+/// whole circle.
+pub fn arc_sweep(start_angle: Angle, end_angle: Angle, direction: ArcDirection) -> f64 {
+    let difference = direction.sign() * (end_angle - start_angle).degrees();
+    let magnitude = match difference.rem_euclid(360.0) {
+        0.0 if difference != 0.0 => 360.0,
+        magnitude => magnitude,
+    };
+    direction.sign() * magnitude
+}
+
+/// Start and end angle of an arc in degrees, with the end unwrapped so that
+/// `end - start` is the signed sweep
+pub fn arc_span(start_angle: Angle, end_angle: Angle, direction: ArcDirection) -> (f64, f64) {
+    let start = start_angle.degrees();
+    (start, start + arc_sweep(start_angle, end_angle, direction))
+}
+
+/// Arc from `start_angle` to `end_angle` degrees, counter-clockwise when the
+/// end angle is the larger one and clockwise otherwise
+pub fn arc_from_span(center: Point, radius: f64, start_angle: f64, end_angle: f64) -> PathSegment {
+    PathSegment::Arc(
+        center,
+        radius,
+        Angle::from_degrees(start_angle),
+        Angle::from_degrees(end_angle),
+        ArcDirection::of_sweep(end_angle - start_angle),
+    )
+}
+
+/// Get the first point of a single segment
+pub fn get_segment_start_point(segment: &PathSegment) -> Option<Point> {
+    match segment {
+        PathSegment::Line(start, _) => Some(*start),
+        PathSegment::Arc(center, radius, start_angle, _, _) => {
+            Some(point_on_circle(*center, *radius, start_angle.degrees()))
+        }
+        PathSegment::ConnectedArc(_, _, _, _, _, start_point, _) => Some(*start_point),
+        PathSegment::EllipticalArc(ellipse, start_angle, _, _) => {
+            Some(ellipse.point_at(*start_angle))
+        }
+        PathSegment::QuadBezier(start, _, _) | PathSegment::CubicBezier(start, _, _, _) => {
+            Some(*start)
+        }
+        PathSegment::ClosePath => None,
+        PathSegment::DrawPoint(point) => Some(*point),
+    }
+}
+
+/// Get the last point of a single segment
+pub fn get_segment_end_point(segment: &PathSegment) -> Option<Point> {
+    match segment {
+        PathSegment::Line(_, end) => Some(*end),
+        PathSegment::Arc(center, radius, _, end_angle, _) => {
+            Some(point_on_circle(*center, *radius, end_angle.degrees()))
+        }
+        PathSegment::ConnectedArc(_, _, _, _, _, _, end_point) => Some(*end_point),
+        PathSegment::EllipticalArc(ellipse, _, end_angle, _) => Some(ellipse.point_at(*end_angle)),
+        PathSegment::QuadBezier(_, _, end) | PathSegment::CubicBezier(_, _, _, end) => Some(*end),
+        PathSegment::ClosePath => None,
+        PathSegment::DrawPoint(point) => Some(*point),
+    }
+}
+
+/// Traverse a segment in the opposite direction
+///
+/// Arcs swap their angles and flip their direction, which keeps the same arc
+/// and only changes the direction of travel.
+pub fn reverse_segment(segment: &PathSegment) -> PathSegment {
+    match segment {
+        PathSegment::Line(start, end) => PathSegment::Line(*end, *start),
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction) => PathSegment::Arc(
+            *center,
+            *radius,
+            *end_angle,
+            *start_angle,
+            direction.reversed(),
+        ),
+        PathSegment::ConnectedArc(
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            direction,
+            start_pt,
+            end_pt,
+        ) => PathSegment::ConnectedArc(
+            *center,
+            *radius,
+            *end_angle,
+            *start_angle,
+            direction.reversed(),
+            *end_pt,
+            *start_pt,
+        ),
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            PathSegment::EllipticalArc(*ellipse, *end_angle, *start_angle, direction.reversed())
+        }
+        PathSegment::QuadBezier(start, control, end) => {
+            PathSegment::QuadBezier(*end, *control, *start)
+        }
+        PathSegment::CubicBezier(start, control1, control2, end) => {
+            PathSegment::CubicBezier(*end, *control2, *control1, *start)
+        }
+        PathSegment::ClosePath => PathSegment::ClosePath,
+        PathSegment::DrawPoint(point) => PathSegment::DrawPoint(*point),
+    }
+}
+
+/// Shortest distance from a point to a segment
+///
+/// See `distance_to_segment_with_tolerance`.
+pub fn distance_to_segment(point: Point, segment: &PathSegment) -> f64 {
+    distance_to_segment_with_tolerance(point, segment, Tolerance::default())
+}
+
+/// Shortest distance from a point to a segment
+///
+/// `ClosePath` has no geometry of its own and is infinitely far away. A
+/// point off a circular arc is measured to the circle when its direction
+/// from the center lies in the arc's range within `tolerance`, and to the
+/// nearer end otherwise.
+pub fn distance_to_segment_with_tolerance(
+    point: Point,
+    segment: &PathSegment,
+    tolerance: Tolerance,
+) -> f64 {
+    match segment {
+        PathSegment::Line(start, end) => {
+            let dx = end.x - start.x;
+            let dy = end.y - start.y;
+            let length_squared = dx * dx + dy * dy;
+            if length_squared == 0.0 {
+                return distance(point, *start);
+            }
+            let t = (((point.x - start.x) * dx + (point.y - start.y) * dy) / length_squared)
+                .clamp(0.0, 1.0);
+            distance(
+                point,
+                Point {
+                    x: start.x + t * dx,
+                    y: start.y + t * dy,
+                },
+            )
+        }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let angle = (point.y - center.y).atan2(point.x - center.x).to_degrees();
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let (from, to) = arc_angle_range(start_angle, end_angle);
+            if is_angle_in_arc(angle, from, to, tolerance) {
+                (distance(point, *center) - radius).abs()
+            } else {
+                let start = get_segment_start_point(segment).unwrap_or(*center);
+                let end = get_segment_end_point(segment).unwrap_or(*center);
+                distance(point, start).min(distance(point, end))
+            }
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let t = closest_ellipse_parameter(ellipse, start_angle, end_angle, point);
+            distance(point, ellipse.point_at(Angle::from_degrees(t)))
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            let Some(curve) = cubic_control_points(segment) else {
+                return f64::INFINITY;
+            };
+            distance(
+                point,
+                cubic_point(&curve, closest_cubic_parameter(&curve, point)),
+            )
+        }
+        PathSegment::ClosePath => f64::INFINITY,
+        PathSegment::DrawPoint(p) => distance(point, *p),
+    }
+}
+
+/// Direction of travel along a segment at a point on it, not normalized
+pub fn segment_tangent(segment: &PathSegment, point: Point) -> Option<Point> {
+    match segment {
+        PathSegment::Line(start, end) => Some(Point {
+            x: end.x - start.x,
+            y: end.y - start.y,
+        }),
+        PathSegment::Arc(center, _, _, _, direction)
+        | PathSegment::ConnectedArc(center, _, _, _, direction, _, _) => {
+            // Counter-clockwise travel turns the radius vector by +90°
+            let direction = direction.sign();
+            Some(Point {
+                x: -direction * (point.y - center.y),
+                y: direction * (point.x - center.x),
+            })
+        }
+        PathSegment::EllipticalArc(ellipse, _, _, direction) => {
+            let derivative = ellipse.derivative_at(ellipse.parameter_of(point));
+            Some(Point {
+                x: direction.sign() * derivative.x,
+                y: direction.sign() * derivative.y,
+            })
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            let curve = cubic_control_points(segment)?;
+            Some(cubic_tangent(
+                &curve,
+                closest_cubic_parameter(&curve, point),
+            ))
+        }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => None,
+    }
+}
+
+/// Position of a point on a segment
+///
+/// Lines use `t` in `[0, 1]` from start to end. Arcs use the absolute angle
+/// in degrees, unwrapped along the sweep so that the parameter runs from
+/// `start_angle` to `end_angle` whichever way the arc turns. Elliptical arcs
+/// do the same with the parametric angle. Bézier curves use their own `t` in
+/// `[0, 1]`.
+pub fn segment_parameter(segment: &PathSegment, point: Point) -> f64 {
+    match segment {
+        PathSegment::Line(start, end) => {
+            let dx = end.x - start.x;
+            let dy = end.y - start.y;
+            let length_squared = dx * dx + dy * dy;
+            if length_squared == 0.0 {
+                return 0.0;
+            }
+            (((point.x - start.x) * dx + (point.y - start.y) * dy) / length_squared).clamp(0.0, 1.0)
+        }
+        PathSegment::Arc(center, _, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, _, start_angle, end_angle, direction, _, _) => {
+            let angle = (point.y - center.y).atan2(point.x - center.x).to_degrees();
+            parameter_in_sweep(angle, *start_angle, *end_angle, *direction)
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let angle = ellipse.parameter_of(point).degrees();
+            parameter_in_sweep(angle, *start_angle, *end_angle, *direction)
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => cubic_control_points(segment)
+            .map_or(0.0, |curve| closest_cubic_parameter(&curve, point)),
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => 0.0,
+    }
+}
+
+/// Unwrapped angle (degrees) along the sweep of an arc at which it passes
+/// `angle`, clamped to the sweep
+fn parameter_in_sweep(
+    angle: f64,
+    start_angle: Angle,
+    end_angle: Angle,
+    direction: ArcDirection,
+) -> f64 {
+    let sweep = arc_sweep(start_angle, end_angle, direction).abs();
+    let start_angle = start_angle.degrees();
+    let direction = direction.sign();
+    let offset = (direction * (angle - start_angle)).rem_euclid(360.0);
+    // Points just before the start wrap around to almost 360°
+    let offset = if offset > sweep && offset - sweep > 360.0 - offset {
+        0.0
+    } else {
+        offset.min(sweep)
+    };
+    start_angle + direction * offset
+}
+
+/// Parametric angle (degrees) of the point of an elliptical arc closest to
+/// `point`, with the arc given by its unwrapped span in either order
+///
+/// A coarse sample of the arc gives the starting guess, which Newton's method
+/// refines on the condition that the offset to `point` is normal to the arc.
+/// On a full turn the refinement wraps across the seam instead of stopping at
+/// the span's ends.
+pub fn closest_ellipse_parameter(
+    ellipse: &Ellipse,
+    start_angle: f64,
+    end_angle: f64,
+    point: Point,
+) -> f64 {
+    const SAMPLES: usize = 64;
+    let (low, high) = arc_angle_range(start_angle, end_angle);
+    let at = |t: f64| ellipse.point_at(Angle::from_degrees(t));
+    let mut best = (0..=SAMPLES)
+        .map(|i| low + (high - low) * i as f64 / SAMPLES as f64)
+        .min_by(|a, b| distance(at(*a), point).total_cmp(&distance(at(*b), point)))
+        .unwrap_or(low);
+
+    for _ in 0..16 {
+        let p = at(best);
+        let d1 = ellipse.derivative_at(Angle::from_degrees(best));
+        // The second derivative points from the curve back to the center
+        let d2 = Point {
+            x: ellipse.center.x - p.x,
+            y: ellipse.center.y - p.y,
+        };
+        let offset = Point {
+            x: p.x - point.x,
+            y: p.y - point.y,
+        };
+        let g = offset.x * d1.x + offset.y * d1.y;
+        let dg = d1.x * d1.x + d1.y * d1.y + offset.x * d2.x + offset.y * d2.y;
+        if dg <= 0.0 {
+            break;
+        }
+        let step = (g / dg).to_degrees();
+        best = if high - low >= 360.0 {
+            low + (best - step - low).rem_euclid(360.0)
+        } else {
+            (best - step).clamp(low, high)
+        };
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    best
+}
+
+/// Integral of `f` from `low` to `high`, by 5-point Gauss-Legendre
+/// quadrature on `pieces` equal sub-intervals
+pub fn integrate(f: impl Fn(f64) -> f64, low: f64, high: f64, pieces: usize) -> f64 {
+    // Nodes and weights on [-1, 1]
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    ];
+    let pieces = pieces.max(1);
+    let width = (high - low) / pieces as f64;
+    let mut total = 0.0;
+    for i in 0..pieces {
+        let middle = low + width * (i as f64 + 0.5);
+        for (node, weight) in NODES {
+            total += weight * f(middle + node * width / 2.0);
+        }
+    }
+    total * width / 2.0
+}
+
+/// Candidate roots of `f` on `[low, high]`, found from `samples` equal steps
+///
+/// Sign changes between samples are bisected. Where `|f|` has a local minimum
+/// at a sample without changing sign, the minimum is refined by
+/// golden-section search and returned too, so that touching roots are not
+/// lost; callers decide whether it is close enough to zero.
+pub fn sampled_roots(f: impl Fn(f64) -> f64, low: f64, high: f64, samples: usize) -> Vec<f64> {
+    let samples = samples.max(2);
+    let at = |i: usize| low + (high - low) * i as f64 / samples as f64;
+    let values: Vec<f64> = (0..=samples).map(|i| f(at(i))).collect();
+
+    let mut roots = Vec::new();
+    for i in 0..samples {
+        let (mut a, mut b) = (at(i), at(i + 1));
+        let (fa, fb) = (values[i], values[i + 1]);
+        if fa == 0.0 {
+            roots.push(a);
+        } else if fa * fb < 0.0 {
+            let mut fa = fa;
+            for _ in 0..64 {
+                let middle = (a + b) / 2.0;
+                let fm = f(middle);
+                if fa * fm <= 0.0 {
+                    b = middle;
+                } else {
+                    a = middle;
+                    fa = fm;
+                }
+            }
+            roots.push((a + b) / 2.0);
+        } else if i > 0 && fa.abs() < values[i - 1].abs() && fa.abs() <= fb.abs() {
+            const RATIO: f64 = 0.618_033_988_749_895;
+            let (mut a, mut b) = (at(i - 1), at(i + 1));
+            for _ in 0..64 {
+                let c = b - RATIO * (b - a);
+                let d = a + RATIO * (b - a);
+                if f(c).abs() < f(d).abs() {
+                    b = d;
+                } else {
+                    a = c;
+                }
+            }
+            roots.push((a + b) / 2.0);
+        }
+    }
+    if values[samples] == 0.0 {
+        roots.push(high);
+    }
+    roots
+}
+
+/// Real roots on `[low, high]` of the polynomial with `coefficients`,
+/// constant term first
+///
+/// The roots of the derivative split the interval into pieces on which the
+/// polynomial is monotonic, so each piece holds at most one root and roots
+/// however close together are bisected apart. A turning point where the
+/// value is within `touching` of zero is a touching root, which stands in for
+/// the crossings rounding may put on either side of it.
+pub fn polynomial_roots(coefficients: &[f64], low: f64, high: f64, touching: f64) -> Vec<f64> {
+    if coefficients.len() < 2 {
+        return Vec::new();
+    }
+    let value = |x: f64| coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c);
+    let derivative: Vec<f64> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(power, c)| power as f64 * c)
+        .collect();
+    let mut ends = vec![low];
+    ends.extend(
+        polynomial_roots(&derivative, low, high, 0.0)
+            .into_iter()
+            .filter(|x| *x > low && *x < high),
+    );
+    ends.push(high);
+    let last = ends.len() - 1;
+    let touches: Vec<bool> = (0..=last)
+        .map(|i| i > 0 && i < last && value(ends[i]).abs() <= touching)
+        .collect();
+
+    let mut roots = Vec::new();
+    for i in 0..last {
+        let (mut a, mut b) = (ends[i], ends[i + 1]);
+        let (mut fa, fb) = (value(a), value(b));
+        if touches[i] {
+            roots.push(a);
+        } else if touches[i + 1] {
+            continue;
+        } else if fa == 0.0 {
+            roots.push(a);
+        } else if fa * fb < 0.0 {
+            for _ in 0..64 {
+                let middle = (a + b) / 2.0;
+                let fm = value(middle);
+                if fa * fm <= 0.0 {
+                    b = middle;
+                } else {
+                    a = middle;
+                    fa = fm;
+                }
+            }
+            roots.push((a + b) / 2.0);
+        }
+    }
+    if value(high) == 0.0 {
+        roots.push(high);
+    }
+    roots
+}
+
+/// Length of an elliptical arc between two parametric angles (degrees)
+///
+/// Elliptic integrals have no closed form, so the speed `|P'(t)|` is
+/// integrated numerically over pieces of at most 15°.
+pub fn elliptical_arc_length(ellipse: &Ellipse, start_angle: f64, end_angle: f64) -> f64 {
+    let (low, high) = arc_angle_range(start_angle, end_angle);
+    let pieces = ((high - low) / 15.0).ceil() as usize;
+    let speed = |t: f64| {
+        let d = ellipse.derivative_at(Angle::from_degrees(t));
+        d.x.hypot(d.y)
+    };
+    integrate(speed, low, high, pieces).to_radians()
+}
+
+/// Length of a single segment
+///
+/// Lines and circular arcs are exact. Elliptical arcs and Bézier curves,
+/// whose lengths have no closed form, are integrated numerically. `ClosePath`
+/// and `DrawPoint` have no length of their own.
+pub fn segment_length(segment: &PathSegment) -> f64 {
+    match segment {
+        PathSegment::Line(start, end) => distance(*start, *end),
+        PathSegment::Arc(_, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(_, radius, start_angle, end_angle, direction, _, _) => {
+            radius
+                * arc_sweep(*start_angle, *end_angle, *direction)
+                    .abs()
+                    .to_radians()
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            elliptical_arc_length(ellipse, start_angle, end_angle)
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            cubic_control_points(segment).map_or(0.0, |curve| cubic_length(&curve, 0.0, 1.0))
+        }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => 0.0,
     }
 }
 
@@ -54,15 +583,21 @@ pub fn get_starting_point(segments: &Vec<PathSegment>) -> Option<Point> {
     for segment in segments {
         match segment {
             PathSegment::Line(start, _) => return Some(*start),
-            PathSegment::Arc(center, radius, start_angle, _) => {
-                let rad = start_angle.to_radians();
+            PathSegment::Arc(center, radius, start_angle, _, _) => {
+                let rad = start_angle.radians();
                 let start_point = Point {
                     x: center.x + radius * rad.cos(),
                     y: center.y + radius * rad.sin(),
                 };
                 return Some(start_point);
             }
-            PathSegment::ConnectedArc(_, _, _, _, start_point, _) => return Some(*start_point),
+            PathSegment::ConnectedArc(_, _, _, _, _, start_point, _) => return Some(*start_point),
+            PathSegment::EllipticalArc(ellipse, start_angle, _, _) => {
+                return Some(ellipse.point_at(*start_angle));
+            }
+            PathSegment::QuadBezier(start, _, _) | PathSegment::CubicBezier(start, _, _, _) => {
+                return Some(*start);
+            }
             PathSegment::ClosePath => continue,
             PathSegment::DrawPoint(point) => return Some(*point),
         }
@@ -77,159 +612,402 @@ pub fn get_segment_midpoint(segment: &PathSegment) -> Point {
             x: (start.x + end.x) / 2.0,
             y: (start.y + end.y) / 2.0,
         },
-        PathSegment::Arc(center, radius, start_angle, end_angle) => {
-            let mid_angle = (start_angle + end_angle) / 2.0;
-            let rad = mid_angle.to_radians();
-            Point {
-                x: center.x + radius * rad.cos(),
-                y: center.y + radius * rad.sin(),
-            }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            point_on_circle(*center, *radius, (start_angle + end_angle) / 2.0)
         }
-        PathSegment::ConnectedArc(center, radius, start_angle, end_angle, _, _) => {
-            let mid_angle = (start_angle + end_angle) / 2.0;
-            let rad = mid_angle.to_radians();
-            Point {
-                x: center.x + radius * rad.cos(),
-                y: center.y + radius * rad.sin(),
-            }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            ellipse.point_at(Angle::from_degrees((start_angle + end_angle) / 2.0))
         }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => cubic_control_points(segment)
+            .map_or(Point { x: 0.0, y: 0.0 }, |curve| cubic_point(&curve, 0.5)),
         PathSegment::ClosePath => Point { x: 0.0, y: 0.0 },
         PathSegment::DrawPoint(point) => *point,
     }
 }
 
+/// Green's theorem contribution `½∫(x dy - y dx)` of an arc sweeping from
+/// `start_angle` to `end_angle` (degrees)
+///
+/// With x(θ) = cx + r·cos(θ), y(θ) = cy + r·sin(θ):
+/// x dy - y dx = (cx·r·cos(θ) + cy·r·sin(θ) + r²) dθ
+fn arc_area_term(center: Point, radius: f64, start_angle: f64, end_angle: f64) -> f64 {
+    let start_rad = start_angle.to_radians();
+    let end_rad = end_angle.to_radians();
+    let sweep = (end_angle - start_angle).to_radians();
+
+    (center.x * radius * (end_rad.sin() - start_rad.sin())
+        - center.y * radius * (end_rad.cos() - start_rad.cos())
+        + radius * radius * sweep)
+        / 2.0
+}
+
+/// Green's theorem contribution `½∫(x dy - y dx)` of an elliptical arc
+/// between two parametric angles (degrees)
+///
+/// Writing a point as `center + u(t)`, the cross product `u × u'` is the
+/// constant `radius_x·radius_y` whatever the rotation, so
+/// x dy - y dx = cx·du_y - cy·du_x + radius_x·radius_y dt
+fn elliptical_arc_area_term(ellipse: &Ellipse, start_angle: f64, end_angle: f64) -> f64 {
+    let start = ellipse.point_at(Angle::from_degrees(start_angle));
+    let end = ellipse.point_at(Angle::from_degrees(end_angle));
+    let center = ellipse.center;
+    let sweep = (end_angle - start_angle).to_radians();
+
+    (center.x * (end.y - start.y) - center.y * (end.x - start.x)
+        + ellipse.radius_x * ellipse.radius_y * sweep)
+        / 2.0
+}
+
+/// Shoelace contribution of the straight line from `p1` to `p2`
+fn line_area_term(p1: Point, p2: Point) -> f64 {
+    (p1.x * p2.y - p2.x * p1.y) / 2.0
+}
+
 /// Calculate signed area of a closed path
 /// Positive area = counter-clockwise orientation
 /// Negative area = clockwise orientation
+///
+/// `ClosePath` closes the current sub-path back to its own starting point, so
+/// a list holding several closed contours sums their signed areas.
 pub fn signed_area_of_path(segments: &Vec<PathSegment>) -> f64 {
     let mut area = 0.0;
-    let first_point = match get_starting_point(segments) {
-        Some(p) => p,
-        None => return 0.0,
-    };
-    let mut current_point = first_point;
+    let mut subpath_start: Option<Point> = None;
+    let mut current_point = Point { x: 0.0, y: 0.0 };
 
     for segment in segments {
-        match segment {
-            PathSegment::Line(start, end) => {
-                // Add connecting line from current_point to start if needed
-                if (current_point.x - start.x).abs() > 1e-10
-                    || (current_point.y - start.y).abs() > 1e-10
-                {
-                    area += (current_point.x * start.y - start.x * current_point.y) / 2.0;
-                }
-                // Shoelace formula for line segment
-                area += (start.x * end.y - end.x * start.y) / 2.0;
-                current_point = *end;
+        if let PathSegment::ClosePath = segment {
+            // Close path connects last point to the start of the sub-path;
+            // a zero-length gap contributes nothing
+            if let Some(start) = subpath_start.take() {
+                area += line_area_term(current_point, start);
             }
-            PathSegment::Arc(center, radius, start_angle, end_angle) => {
-                // Calculate arc contribution using Green's theorem
-                let start_rad = start_angle.to_radians();
-                let end_rad = end_angle.to_radians();
+            continue;
+        }
+        if let PathSegment::DrawPoint(_) = segment {
+            // DrawPoint doesn't contribute to area calculation
+            continue;
+        }
 
-                let start_point = Point {
-                    x: center.x + radius * start_rad.cos(),
-                    y: center.y + radius * start_rad.sin(),
-                };
-                let end_point = Point {
-                    x: center.x + radius * end_rad.cos(),
-                    y: center.y + radius * end_rad.sin(),
-                };
+        let (start_point, end_point) = match (
+            get_segment_start_point(segment),
+            get_segment_end_point(segment),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
 
-                // Add connecting line from current_point to arc start
-                if (current_point.x - start_point.x).abs() > 1e-10
-                    || (current_point.y - start_point.y).abs() > 1e-10
-                {
-                    area +=
-                        (current_point.x * start_point.y - start_point.x * current_point.y) / 2.0;
-                }
+        if subpath_start.is_none() {
+            subpath_start = Some(start_point);
+            current_point = start_point;
+        }
 
-                // For arc from start_point to end_point:
-                // Integrate x dy along the arc using parametric form
-                // x(θ) = cx + r*cos(θ), y(θ) = cy + r*sin(θ)
-                // dy = r*cos(θ) dθ
-                // ∫ x dy = ∫ (cx + r*cos(θ)) * r*cos(θ) dθ
-                //        = cx*r*∫cos(θ)dθ + r²∫cos²(θ)dθ
-                //        = cx*r*[sin(θ)] + r²*[θ/2 + sin(2θ)/4]
-
-                let mut angle_diff = end_angle - start_angle;
-                // Normalize to [-360, 360]
-                while angle_diff > 360.0 {
-                    angle_diff -= 360.0;
-                }
-                while angle_diff < -360.0 {
-                    angle_diff += 360.0;
-                }
+        // Connecting line from current_point to the segment start, zero when
+        // the segments are chained
+        area += line_area_term(current_point, start_point);
 
-                let angle_rad = angle_diff.to_radians();
+        area += match segment {
+            PathSegment::Line(start, end) => line_area_term(*start, *end),
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+            | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                arc_area_term(*center, *radius, start_angle, end_angle)
+            }
+            PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                elliptical_arc_area_term(ellipse, start_angle, end_angle)
+            }
+            PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                cubic_control_points(segment).map_or(0.0, |curve| cubic_area_term(&curve))
+            }
+            PathSegment::ClosePath | PathSegment::DrawPoint(_) => 0.0,
+        };
+        current_point = end_point;
+    }
 
-                // Term 1: cx * r * (sin(end) - sin(start))
-                let term1 = center.x * radius * (end_rad.sin() - start_rad.sin());
+    area
+}
 
-                // Term 2: r² * (angle/2 + (sin(2*end) - sin(2*start))/4)
-                let term2 = radius
-                    * radius
-                    * (angle_rad / 2.0 + (2.0 * end_rad).sin() / 4.0
-                        - (2.0 * start_rad).sin() / 4.0);
+/// Winding contribution of the directed line from `a` to `b` around `point`
+///
+/// Crossings with the horizontal line through `point` are counted on the
+/// half-open range `[min_y, max_y)` so a shared vertex is counted once.
+fn line_winding(point: Point, a: Point, b: Point) -> i32 {
+    let cross = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+    if a.y <= point.y && point.y < b.y && cross > 0.0 {
+        1
+    } else if b.y <= point.y && point.y < a.y && cross < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
 
-                area += term1 + term2;
-                current_point = end_point;
-            }
-            PathSegment::ConnectedArc(
-                center,
-                radius,
-                start_angle,
-                end_angle,
-                start_point,
-                end_point,
-            ) => {
-                // Add connecting line from current_point to arc start
-                if (current_point.x - start_point.x).abs() > 1e-10
-                    || (current_point.y - start_point.y).abs() > 1e-10
-                {
-                    area +=
-                        (current_point.x * start_point.y - start_point.x * current_point.y) / 2.0;
-                }
+/// Winding contribution of an arc around `point`
+///
+/// The arc is split at its topmost and bottommost points into y-monotone
+/// pieces, and each piece is tested like a line using the arc's own
+/// x-coordinate at the height of `point`.
+fn arc_winding(
+    point: Point,
+    center: Point,
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    start_point: Point,
+    end_point: Point,
+) -> i32 {
+    // Extremes in y sit at 90° + 180°·k, k even at the top and odd at the bottom
+    let mut breaks = vec![(start_angle, start_point)];
+    let extreme = |k: f64| {
+        let top = k.rem_euclid(2.0) == 0.0;
+        let y = if top {
+            center.y + radius
+        } else {
+            center.y - radius
+        };
+        (90.0 + 180.0 * k, Point { x: center.x, y })
+    };
+    if end_angle > start_angle {
+        let mut k = ((start_angle - 90.0) / 180.0).floor() + 1.0;
+        while 90.0 + 180.0 * k < end_angle {
+            breaks.push(extreme(k));
+            k += 1.0;
+        }
+    } else {
+        let mut k = ((start_angle - 90.0) / 180.0).ceil() - 1.0;
+        while 90.0 + 180.0 * k > end_angle {
+            breaks.push(extreme(k));
+            k -= 1.0;
+        }
+    }
+    breaks.push((end_angle, end_point));
 
-                // Use same formula as Arc
-                let start_rad = start_angle.to_radians();
-                let end_rad = end_angle.to_radians();
+    let mut winding = 0;
+    for pair in breaks.windows(2) {
+        let (angle0, a) = pair[0];
+        let (angle1, b) = pair[1];
+        let upward = a.y <= point.y && point.y < b.y;
+        let downward = b.y <= point.y && point.y < a.y;
+        if !upward && !downward {
+            continue;
+        }
+        // The piece lies on the right half of the circle when its middle does
+        let side = ((angle0 + angle1) / 2.0).to_radians().cos().signum();
+        let dy = point.y - center.y;
+        let x = center.x + side * (radius * radius - dy * dy).max(0.0).sqrt();
+        if x > point.x {
+            winding += if upward { 1 } else { -1 };
+        }
+    }
+    winding
+}
 
-                let mut angle_diff = end_angle - start_angle;
-                while angle_diff > 360.0 {
-                    angle_diff -= 360.0;
-                }
-                while angle_diff < -360.0 {
-                    angle_diff += 360.0;
-                }
+/// Parametric angle (degrees) of an extreme of an ellipse along the y axis,
+/// the others lying 180° apart, alternating between top and bottom
+///
+/// Relative to the center, y(t) = radius_x·sin(rotation)·cos(t) +
+/// radius_y·cos(rotation)·sin(t), a sinusoid in `t` peaking at the returned
+/// angle.
+fn ellipse_y_extreme(ellipse: &Ellipse) -> f64 {
+    let (sin_r, cos_r) = ellipse.rotation.radians().sin_cos();
+    (ellipse.radius_y * cos_r)
+        .atan2(ellipse.radius_x * sin_r)
+        .to_degrees()
+}
 
-                let angle_rad = angle_diff.to_radians();
+/// Parametric angle (degrees) of the rightmost point of an ellipse, with the
+/// leftmost 180° away
+fn ellipse_x_extreme(ellipse: &Ellipse) -> f64 {
+    let (sin_r, cos_r) = ellipse.rotation.radians().sin_cos();
+    (-ellipse.radius_y * sin_r)
+        .atan2(ellipse.radius_x * cos_r)
+        .to_degrees()
+}
 
-                let term1 = center.x * radius * (end_rad.sin() - start_rad.sin());
-                let term2 = radius
-                    * radius
-                    * (angle_rad / 2.0 + (2.0 * end_rad).sin() / 4.0
-                        - (2.0 * start_rad).sin() / 4.0);
+/// Winding contribution of an elliptical arc around `point`
+///
+/// Works like `arc_winding`: the arc is split where it is highest and lowest,
+/// and each y-monotone piece is solved for its x-coordinate at the height of
+/// `point`.
+fn elliptical_arc_winding(
+    point: Point,
+    ellipse: &Ellipse,
+    start_angle: f64,
+    end_angle: f64,
+    start_point: Point,
+    end_point: Point,
+) -> i32 {
+    let peak = ellipse_y_extreme(ellipse);
+    let at = |t: f64| ellipse.point_at(Angle::from_degrees(t));
+    let mut breaks = vec![(start_angle, start_point)];
+    if end_angle > start_angle {
+        let mut k = ((start_angle - peak) / 180.0).floor() + 1.0;
+        while peak + 180.0 * k < end_angle {
+            breaks.push((peak + 180.0 * k, at(peak + 180.0 * k)));
+            k += 1.0;
+        }
+    } else {
+        let mut k = ((start_angle - peak) / 180.0).ceil() - 1.0;
+        while peak + 180.0 * k > end_angle {
+            breaks.push((peak + 180.0 * k, at(peak + 180.0 * k)));
+            k -= 1.0;
+        }
+    }
+    breaks.push((end_angle, end_point));
+
+    let top = at(peak);
+    let height = top.y - ellipse.center.y;
+    let mut winding = 0;
+    for pair in breaks.windows(2) {
+        let (angle0, a) = pair[0];
+        let (angle1, b) = pair[1];
+        let upward = a.y <= point.y && point.y < b.y;
+        let downward = b.y <= point.y && point.y < a.y;
+        if !upward && !downward {
+            continue;
+        }
+        // y - cy = height·cos(t - peak); the piece fixes the sign of the sine
+        let middle = (angle0 + angle1) / 2.0;
+        let side = (middle - peak).to_radians().sin().signum();
+        let offset = ((point.y - ellipse.center.y) / height)
+            .clamp(-1.0, 1.0)
+            .acos()
+            .to_degrees();
+        let t = peak + side * offset;
+        let t = t + 360.0 * ((middle - t) / 360.0).round();
+        if at(t).x > point.x {
+            winding += if upward { 1 } else { -1 };
+        }
+    }
+    winding
+}
+
+/// Winding number of a closed path around a point
+///
+/// Counts signed crossings of the path with the horizontal line through the
+/// point, so the result does not depend on the size of the shape. The path is
+/// closed back to its start if it does not end there.
+pub fn winding_number(point: Point, segments: &[PathSegment]) -> i32 {
+    let mut winding = 0;
+    let mut subpath_start: Option<Point> = None;
+    let mut current_point = Point { x: 0.0, y: 0.0 };
 
-                area += term1 + term2;
-                current_point = *end_point;
+    for segment in segments {
+        if let PathSegment::ClosePath = segment {
+            if let Some(start) = subpath_start.take() {
+                winding += line_winding(point, current_point, start);
             }
-            PathSegment::ClosePath => {
-                // Close path connects last point to first
-                if (current_point.x - first_point.x).abs() > 1e-10
-                    || (current_point.y - first_point.y).abs() > 1e-10
-                {
-                    area +=
-                        (current_point.x * first_point.y - first_point.x * current_point.y) / 2.0;
-                }
+            continue;
+        }
+        let (start_point, end_point) = match (
+            get_segment_start_point(segment),
+            get_segment_end_point(segment),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
+        if let PathSegment::DrawPoint(_) = segment {
+            continue;
+        }
+
+        if subpath_start.is_none() {
+            subpath_start = Some(start_point);
+            current_point = start_point;
+        }
+        winding += line_winding(point, current_point, start_point);
+
+        winding += match segment {
+            PathSegment::Line(start, end) => line_winding(point, *start, *end),
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+            | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                arc_winding(
+                    point,
+                    *center,
+                    *radius,
+                    start_angle,
+                    end_angle,
+                    start_point,
+                    end_point,
+                )
             }
-            PathSegment::DrawPoint(_) => {
-                // DrawPoint doesn't contribute to area calculation
+            PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                elliptical_arc_winding(
+                    point,
+                    ellipse,
+                    start_angle,
+                    end_angle,
+                    start_point,
+                    end_point,
+                )
             }
+            PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                cubic_control_points(segment).map_or(0, |curve| cubic_winding(&curve, point))
+            }
+            PathSegment::ClosePath | PathSegment::DrawPoint(_) => 0,
+        };
+        current_point = end_point;
+    }
+    if let Some(start) = subpath_start {
+        winding += line_winding(point, current_point, start);
+    }
+
+    winding
+}
+
+/// Total length of a path
+///
+/// Gaps between segments that don't meet and the line `ClosePath` draws back
+/// to the start of its sub-path count as straight lines, as they do for
+/// `signed_area_of_path`.
+pub fn path_length(segments: &[PathSegment]) -> f64 {
+    let mut length = 0.0;
+    let mut subpath_start: Option<Point> = None;
+    let mut current_point = Point { x: 0.0, y: 0.0 };
+
+    for segment in segments {
+        if let PathSegment::ClosePath = segment {
+            if let Some(start) = subpath_start.take() {
+                length += distance(current_point, start);
+            }
+            continue;
+        }
+        if let PathSegment::DrawPoint(_) = segment {
+            continue;
+        }
+        let (Some(start_point), Some(end_point)) = (
+            get_segment_start_point(segment),
+            get_segment_end_point(segment),
+        ) else {
+            continue;
+        };
+        if subpath_start.is_none() {
+            subpath_start = Some(start_point);
+            current_point = start_point;
         }
+        length += distance(current_point, start_point) + segment_length(segment);
+        current_point = end_point;
     }
 
-    area
+    length
+}
+
+/// Length of each contour of a shape, in order
+pub fn contour_lengths(shape: &ResolvedShape) -> Vec<f64> {
+    shape
+        .contours
+        .iter()
+        .map(|contour| path_length(&contour.segments))
+        .collect()
+}
+
+/// Total length of the boundary of a shape, holes included
+pub fn perimeter(shape: &ResolvedShape) -> f64 {
+    contour_lengths(shape).iter().sum()
 }
 
 /// Calculate the absolute area of a closed path
@@ -242,69 +1020,555 @@ pub fn is_counter_clockwise(segments: &Vec<PathSegment>) -> bool {
     signed_area_of_path(segments) > 0.0
 }
 
+/// Return the path traversed in the requested orientation, reversing it if
+/// its signed area has the opposite sign
+pub fn orient_path(segments: &[PathSegment], counter_clockwise: bool) -> Vec<PathSegment> {
+    let path = segments.to_vec();
+    if (signed_area_of_path(&path) >= 0.0) == counter_clockwise {
+        return path;
+    }
+    reverse_path(&path)
+}
+
+/// The same path traversed backwards, keeping a trailing `ClosePath` last
+pub fn reverse_path(segments: &[PathSegment]) -> Vec<PathSegment> {
+    let closed = matches!(segments.last(), Some(PathSegment::ClosePath));
+    let mut reversed: Vec<PathSegment> = segments
+        .iter()
+        .rev()
+        .filter(|segment| !matches!(segment, PathSegment::ClosePath))
+        .map(reverse_segment)
+        .collect();
+    if closed {
+        reversed.push(PathSegment::ClosePath);
+    }
+    reversed
+}
+
+/// Points whose bounding box is the bounding box of `segment`: the endpoints
+/// plus, for curves, every axis extreme the arc passes through
+fn segment_extreme_points(segment: &PathSegment, points: &mut Vec<Point>, tolerance: Tolerance) {
+    match segment {
+        PathSegment::Line(start, end) => {
+            points.push(*start);
+            points.push(*end);
+        }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            if let PathSegment::ConnectedArc(_, _, _, _, _, start_pt, end_pt) = segment {
+                points.push(*start_pt);
+                points.push(*end_pt);
+            } else {
+                points.push(point_on_circle(*center, *radius, start_angle));
+                points.push(point_on_circle(*center, *radius, end_angle));
+            }
+
+            let (from, to) = arc_angle_range(start_angle, end_angle);
+            let mut current_angle = (from / 90.0).ceil() * 90.0;
+            while current_angle < to {
+                if is_angle_in_arc(current_angle, from, to, tolerance) {
+                    points.push(point_on_circle(*center, *radius, current_angle));
+                }
+                current_angle += 90.0;
+            }
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+            let at = |t: f64| ellipse.point_at(Angle::from_degrees(t));
+            points.push(at(start_angle));
+            points.push(at(end_angle));
+
+            let (from, to) = arc_angle_range(start_angle, end_angle);
+            for extreme in [ellipse_x_extreme(ellipse), ellipse_y_extreme(ellipse)] {
+                let mut current_angle = extreme + ((from - extreme) / 180.0).ceil() * 180.0;
+                while current_angle < to {
+                    points.push(at(current_angle));
+                    current_angle += 180.0;
+                }
+            }
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            if let Some(curve) = cubic_control_points(segment) {
+                points.push(curve[0]);
+                points.push(curve[3]);
+                let (x_extremes, y_extremes) = cubic_axis_extremes(&curve);
+                points.extend(
+                    x_extremes
+                        .into_iter()
+                        .chain(y_extremes)
+                        .map(|t| cubic_point(&curve, t)),
+                );
+            }
+        }
+        PathSegment::DrawPoint(p) => {
+            points.push(*p);
+        }
+        PathSegment::ClosePath => {}
+    }
+}
+
+/// Tight bounding box of a single segment, `None` for `ClosePath`
+pub fn get_segment_bounding_box(segment: &PathSegment) -> Option<BoundingBox> {
+    get_segment_bounding_box_with_tolerance(segment, Tolerance::default())
+}
+
+/// Tight bounding box of a single segment, `None` for `ClosePath`, with
+/// `tolerance` deciding which axis extremes a circular arc passes through
+pub fn get_segment_bounding_box_with_tolerance(
+    segment: &PathSegment,
+    tolerance: Tolerance,
+) -> Option<BoundingBox> {
+    let mut points = Vec::new();
+    segment_extreme_points(segment, &mut points, tolerance);
+    (!points.is_empty()).then(|| BoundingBox::from_points(&points))
+}
+
+/// Bounding box of a shape's outer contours (holes lie inside them)
 pub fn get_shape_bounding_box(shape: &ResolvedShape) -> BoundingBox {
+    get_shape_bounding_box_with_tolerance(shape, Tolerance::default())
+}
+
+/// Bounding box of a shape's outer contours, with `tolerance` as in
+/// `get_segment_bounding_box_with_tolerance`
+pub fn get_shape_bounding_box_with_tolerance(
+    shape: &ResolvedShape,
+    tolerance: Tolerance,
+) -> BoundingBox {
     let mut points = Vec::new();
-    for segment in &shape.segments {
+    for segment in shape
+        .outer_contours()
+        .flat_map(|contour| contour.segments.iter())
+    {
+        segment_extreme_points(segment, &mut points, tolerance);
+    }
+    BoundingBox::from_points(&points)
+}
+
+/// Where the boundary of a convex hull can touch a shape: a point, or the
+/// arc of a circle whose outward directions lie in `from..to` degrees
+#[derive(Copy, Clone)]
+enum HullSite {
+    Point(Point),
+    Circle {
+        center: Point,
+        radius: f64,
+        from: f64,
+        to: f64,
+    },
+}
+
+impl HullSite {
+    /// How far `direction` degrees is past the start of the site's arc, in
+    /// `(-slack, 360 - slack]`, with `slack` the angle tolerance on its circle
+    fn facing(&self, direction: f64, tolerance: Tolerance) -> Option<(f64, f64, f64)> {
+        match *self {
+            HullSite::Point(_) => None,
+            HullSite::Circle {
+                radius, from, to, ..
+            } => {
+                let slack = tolerance.angle_at_radius(radius);
+                let offset = (direction - from + slack).rem_euclid(360.0) - slack;
+                Some((offset, to - from, slack))
+            }
+        }
+    }
+
+    /// How far the site reaches in `direction` degrees, with the rate and
+    /// curvature of that reach as the direction turns, or `None` when the
+    /// site's arc does not face that way
+    fn reach(&self, direction: f64, tolerance: Tolerance) -> Option<(f64, f64, f64)> {
+        if let Some((offset, sweep, slack)) = self.facing(direction, tolerance)
+            && offset > sweep + slack
+        {
+            return None;
+        }
+        let (sin, cos) = direction.to_radians().sin_cos();
+        let (center, radius) = self.circle();
+        let along = center.x * cos + center.y * sin;
+        let across = center.y * cos - center.x * sin;
+        Some((along + radius, across, -along))
+    }
+
+    /// The point of the site furthest in `direction` degrees
+    fn support(&self, direction: f64) -> Point {
+        match *self {
+            HullSite::Point(point) => point,
+            HullSite::Circle { center, radius, .. } => point_on_circle(center, radius, direction),
+        }
+    }
+
+    /// First direction after `direction`, and before `limit`, at which the
+    /// reach of `self` rises above that of `leader`
+    fn overtakes(
+        &self,
+        leader: &HullSite,
+        direction: f64,
+        limit: f64,
+        tolerance: Tolerance,
+    ) -> Option<f64> {
+        let (center, radius) = self.circle();
+        let (leader_center, leader_radius) = leader.circle();
+        // reach(self) - reach(leader) = d·cos(θ - α) + Δr, rising through
+        // zero where θ - α = -acos(-Δr / d)
+        let (dx, dy) = (center.x - leader_center.x, center.y - leader_center.y);
+        let d = dx.hypot(dy);
+        if d == 0.0 {
+            return None;
+        }
+        // A point on the circle gives a ratio of one up to rounding
+        let ratio = (leader_radius - radius) / d;
+        if ratio.abs() > 1.0 + tolerance.distance / d {
+            return None;
+        }
+        let ratio = ratio.clamp(-1.0, 1.0);
+        let crossing = dy.atan2(dx).to_degrees() - ratio.acos().to_degrees();
+        let step = (crossing - direction).rem_euclid(360.0);
+        let mut candidate = direction
+            + if step <= tolerance.angle {
+                step + 360.0
+            } else {
+                step
+            };
+        // Rounding can put the takeover just before the arc starts facing
+        // that way, where it is meant to take over right at the start
+        if let Some((offset, _, _)) = self.facing(candidate, tolerance)
+            && offset < 0.0
+        {
+            candidate -= offset;
+        }
+        (candidate < limit && self.reach(candidate, tolerance).is_some()).then_some(candidate)
+    }
+
+    /// Whether the site still faces the way the direction turns past
+    /// `direction` degrees
+    fn faces_past(&self, direction: f64, tolerance: Tolerance) -> bool {
+        match self.facing(direction, tolerance) {
+            None => true,
+            Some((offset, sweep, slack)) => sweep >= 360.0 || offset + slack < sweep,
+        }
+    }
+
+    fn circle(&self) -> (Point, f64) {
+        match *self {
+            HullSite::Point(point) => (point, 0.0),
+            HullSite::Circle { center, radius, .. } => (center, radius),
+        }
+    }
+}
+
+/// Site reaching furthest in `direction` degrees, ties going to the one
+/// that stays ahead as the direction turns counter-clockwise
+fn hull_leader(sites: &[HullSite], direction: f64, tolerance: Tolerance) -> usize {
+    let mut best: Option<(usize, (f64, f64, f64))> = None;
+    for (i, site) in sites.iter().enumerate() {
+        let Some(reach) = site
+            .reach(direction, tolerance)
+            .filter(|_| site.faces_past(direction, tolerance))
+        else {
+            continue;
+        };
+        let ahead = match best {
+            None => true,
+            Some((_, leading)) => {
+                if (reach.0 - leading.0).abs() > tolerance.distance {
+                    reach.0 > leading.0
+                } else if (reach.1 - leading.1).abs() > tolerance.distance {
+                    reach.1 > leading.1
+                } else {
+                    reach.2 > leading.2 + tolerance.distance
+                }
+            }
+        };
+        if ahead {
+            best = Some((i, reach));
+        }
+    }
+    best.map_or(0, |(i, _)| i)
+}
+
+/// Exact convex hull of a shape's outer contours
+///
+/// See `convex_hull_with_tolerance`.
+pub fn convex_hull(shape: &ResolvedShape) -> ResolvedShape {
+    convex_hull_with_tolerance(shape, Tolerance::default())
+}
+
+/// Exact convex hull of a shape's outer contours
+///
+/// Lines and circular arcs are handled exactly; elliptical arcs and Bézier
+/// curves contribute points along them. The hull is traced by turning an
+/// outward direction once round and following whichever line end or arc
+/// reaches furthest that way: a line end holds the boundary at a corner, an
+/// arc facing that way carries it along, and where the lead passes from
+/// one to the next a straight bridge tangent to both joins them. A shape
+/// with no area gives an empty hull.
+pub fn convex_hull_with_tolerance(shape: &ResolvedShape, tolerance: Tolerance) -> ResolvedShape {
+    let mut sites: Vec<HullSite> = Vec::new();
+    let add_point = |sites: &mut Vec<HullSite>, point: Point| {
+        let seen = sites.iter().any(
+            |site| matches!(site, HullSite::Point(p) if p.distance_to(point) <= tolerance.distance),
+        );
+        if !seen {
+            sites.push(HullSite::Point(point));
+        }
+    };
+    for segment in shape
+        .outer_contours()
+        .flat_map(|contour| contour.segments.iter())
+    {
         match segment {
             PathSegment::Line(start, end) => {
-                points.push(*start);
-                points.push(*end);
+                add_point(&mut sites, *start);
+                add_point(&mut sites, *end);
             }
-            PathSegment::Arc(center, radius, start_angle, end_angle) => {
-                let start_rad = start_angle.to_radians();
-                let end_rad = end_angle.to_radians();
-                points.push(Point {
-                    x: center.x + radius * start_rad.cos(),
-                    y: center.y + radius * start_rad.sin(),
-                });
-                points.push(Point {
-                    x: center.x + radius * end_rad.cos(),
-                    y: center.y + radius * end_rad.sin(),
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+            | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, ..) => {
+                let (start, end) = arc_span(*start_angle, *end_angle, *direction);
+                let (from, to) = arc_angle_range(start, end);
+                add_point(&mut sites, point_on_circle(*center, *radius, from));
+                add_point(&mut sites, point_on_circle(*center, *radius, to));
+                sites.push(HullSite::Circle {
+                    center: *center,
+                    radius: *radius,
+                    from,
+                    to,
                 });
-
-                let mut current_angle = (start_angle / 90.0).ceil() * 90.0;
-                while current_angle < *end_angle {
-                    if is_angle_in_arc(current_angle, *start_angle, *end_angle) {
-                        let rad = current_angle.to_radians();
-                        points.push(Point {
-                            x: center.x + radius * rad.cos(),
-                            y: center.y + radius * rad.sin(),
-                        });
-                    }
-                    current_angle += 90.0;
+            }
+            PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+                for i in 0..=HULL_CURVE_POINTS {
+                    let t = from + (to - from) * i as f64 / HULL_CURVE_POINTS as f64;
+                    add_point(&mut sites, ellipse.point_at(Angle::from_degrees(t)));
                 }
             }
-            PathSegment::ConnectedArc(center, radius, start_angle, end_angle, start_pt, end_pt) => {
-                points.push(*start_pt);
-                points.push(*end_pt);
-
-                let mut current_angle = (start_angle / 90.0).ceil() * 90.0;
-                while current_angle < *end_angle {
-                    if is_angle_in_arc(current_angle, *start_angle, *end_angle) {
-                        let rad = current_angle.to_radians();
-                        points.push(Point {
-                            x: center.x + radius * rad.cos(),
-                            y: center.y + radius * rad.sin(),
-                        });
+            PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                if let Some(curve) = cubic_control_points(segment) {
+                    for i in 0..=HULL_CURVE_POINTS {
+                        let t = i as f64 / HULL_CURVE_POINTS as f64;
+                        add_point(&mut sites, cubic_point(&curve, t));
                     }
-                    current_angle += 90.0;
                 }
             }
-            PathSegment::DrawPoint(p) => {
-                points.push(*p);
+            PathSegment::ClosePath | PathSegment::DrawPoint(_) => {}
+        }
+    }
+    if sites.is_empty() {
+        return ResolvedShape::default();
+    }
+
+    // Each lead is held from its direction to the next one's
+    let mut leads = vec![(hull_leader(&sites, 0.0, tolerance), 0.0)];
+    while leads.len() <= 4 * sites.len() {
+        let (leader, direction) = leads[leads.len() - 1];
+        let limit = match sites[leader] {
+            HullSite::Point(_) => 360.0,
+            HullSite::Circle { from, to, .. } => {
+                (direction + to - from - (direction - from).rem_euclid(360.0)).min(360.0)
             }
-            PathSegment::ClosePath => {}
+        };
+        let next = sites
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != leader)
+            .filter_map(|(_, site)| site.overtakes(&sites[leader], direction, limit, tolerance))
+            .fold(limit, f64::min);
+        if next >= 360.0 {
+            break;
         }
+        leads.push((hull_leader(&sites, next, tolerance), next));
+    }
+
+    let mut segments = Vec::new();
+    for (k, &(leader, from)) in leads.iter().enumerate() {
+        let (next_leader, to) = leads.get(k + 1).copied().unwrap_or((leads[0].0, 360.0));
+        let start = sites[leader].support(from);
+        let end = sites[leader].support(to);
+        if let HullSite::Circle { center, radius, .. } = sites[leader]
+            && radius * (to - from).to_radians() > tolerance.distance
+        {
+            segments.push(PathSegment::ConnectedArc(
+                center,
+                radius,
+                Angle::from_degrees(from),
+                Angle::from_degrees(to),
+                ArcDirection::CounterClockwise,
+                start,
+                end,
+            ));
+        }
+        let bridge_end = sites[next_leader].support(to);
+        if end.distance_to(bridge_end) > tolerance.distance {
+            segments.push(PathSegment::Line(end, bridge_end));
+        }
+    }
+    segments.push(PathSegment::ClosePath);
+    if signed_area_of_path(&segments) <= tolerance.distance * tolerance.distance {
+        return ResolvedShape::default();
+    }
+    ResolvedShape {
+        contours: vec![Contour::outer(segments)],
     }
-    BoundingBox::from_points(&points)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::l_shape;
 
     // ...existing code...
 
+    #[test]
+    fn test_convex_hull_of_polygons() {
+        let hull = convex_hull(&l_shape());
+        assert_eq!(hull.contours.len(), 1);
+        // The reflex corner is cut off by one bridge: five sides
+        assert_eq!(hull.contours[0].segments.len(), 6);
+        assert!((signed_area_of_path(&hull.contours[0].segments) - 3.5).abs() < 1e-12);
+
+        let flat = ResolvedShape::from_segments(vec![PathSegment::Line(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+        )]);
+        assert!(convex_hull(&flat).is_empty());
+    }
+
+    #[test]
+    fn test_convex_hull_with_arcs() {
+        let circle = |x: f64, radius: f64| Contour {
+            segments: vec![PathSegment::Arc(
+                Point { x, y: 0.0 },
+                radius,
+                Angle::ZERO,
+                Angle::FULL_TURN,
+                ArcDirection::CounterClockwise,
+            )],
+            role: crate::types::ContourRole::Outer,
+        };
+        // Two equal discs: a stadium of two half circles and two bridges
+        let pair = ResolvedShape {
+            contours: vec![circle(0.0, 1.0), circle(4.0, 1.0)],
+        };
+        let hull = convex_hull(&pair);
+        let segments = &hull.contours[0].segments;
+        let arcs = segments
+            .iter()
+            .filter(|segment| matches!(segment, PathSegment::ConnectedArc(..)))
+            .count();
+        let lines = segments
+            .iter()
+            .filter(|segment| matches!(segment, PathSegment::Line(..)))
+            .count();
+        assert_eq!((arcs, lines), (3, 2)); // the right half circle is split at 0°
+        let area = signed_area_of_path(segments);
+        assert!((area - (std::f64::consts::PI + 8.0)).abs() < 1e-9);
+
+        // Unequal discs, against the hull of many points on them
+        let pair = ResolvedShape {
+            contours: vec![circle(0.0, 1.0), circle(5.0, 2.0)],
+        };
+        let area = signed_area_of_path(&convex_hull(&pair).contours[0].segments);
+        let samples: Vec<PathSegment> = (0..2000)
+            .flat_map(|i| {
+                let angle = i as f64 * 360.0 / 2000.0;
+                [
+                    point_on_circle(Point { x: 0.0, y: 0.0 }, 1.0, angle),
+                    point_on_circle(Point { x: 5.0, y: 0.0 }, 2.0, angle),
+                ]
+            })
+            .map(|point| PathSegment::Line(point, point))
+            .collect();
+        let sampled = convex_hull(&ResolvedShape::from_segments(samples));
+        let sampled_area = signed_area_of_path(&sampled.contours[0].segments);
+        assert!(area > sampled_area && area - sampled_area < 1e-4);
+    }
+
+    #[test]
+    fn test_convex_hull_skips_concave_arcs() {
+        // A square with a half-disc bite out of its top edge and a half-disc
+        // bump on its right edge
+        let shape = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 4.0, y: 0.0 }, Point { x: 4.0, y: 1.0 }),
+            PathSegment::Arc(
+                Point { x: 4.0, y: 2.0 },
+                1.0,
+                Angle::from_degrees(-90.0),
+                Angle::from_degrees(90.0),
+                ArcDirection::CounterClockwise,
+            ),
+            PathSegment::Line(Point { x: 4.0, y: 3.0 }, Point { x: 4.0, y: 4.0 }),
+            PathSegment::Line(Point { x: 4.0, y: 4.0 }, Point { x: 3.0, y: 4.0 }),
+            PathSegment::Arc(
+                Point { x: 2.0, y: 4.0 },
+                1.0,
+                Angle::ZERO,
+                Angle::from_degrees(180.0),
+                ArcDirection::Clockwise,
+            ),
+            PathSegment::Line(Point { x: 1.0, y: 4.0 }, Point { x: 0.0, y: 4.0 }),
+            PathSegment::Line(Point { x: 0.0, y: 4.0 }, Point { x: 0.0, y: 0.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let hull = convex_hull(&shape);
+        // The bite is bridged over; the bump stays, joined by tangents to the
+        // square's right corners
+        let segments = &hull.contours[0].segments;
+        assert!(segments.iter().any(|segment| matches!(
+            segment,
+            PathSegment::ConnectedArc(center, ..) if center.distance_to(Point { x: 4.0, y: 2.0 }) < 1e-12
+        )));
+        assert!(!segments.iter().any(|segment| matches!(
+            segment,
+            PathSegment::ConnectedArc(center, ..) if center.distance_to(Point { x: 2.0, y: 4.0 }) < 1e-12
+        )));
+        // Each bridge leaves a corner 2 from the bump's center, so it touches
+        // the bump 60° round from the corner: two right triangles with legs
+        // 1 and √3 plus a 60° sector
+        let expected = 16.0 + 3f64.sqrt() + std::f64::consts::PI / 6.0;
+        assert!((signed_area_of_path(segments) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_convex_hull_contains_rotated_half_discs() {
+        let center = Point { x: 0.0, y: 0.0 };
+        let radius = 1.5;
+        for step in 0..72 {
+            let start = 5.0 * step as f64;
+            let shape = ResolvedShape::from_segments(vec![
+                PathSegment::Arc(
+                    center,
+                    radius,
+                    Angle::from_degrees(start),
+                    Angle::from_degrees(start + 180.0),
+                    ArcDirection::CounterClockwise,
+                ),
+                PathSegment::Line(
+                    point_on_circle(center, radius, start + 180.0),
+                    point_on_circle(center, radius, start),
+                ),
+                PathSegment::ClosePath,
+            ]);
+            let hull = convex_hull(&shape);
+            let boundary = &hull.contours[0].segments;
+            let area = signed_area_of_path(&shape.contours[0].segments);
+            assert!(
+                signed_area_of_path(boundary) >= area - 1e-9,
+                "start {start}"
+            );
+            for i in 0..=36 {
+                let point = point_on_circle(center, radius, start + 5.0 * i as f64);
+                let on_boundary = boundary
+                    .iter()
+                    .any(|segment| distance_to_segment(point, segment) <= 1e-9);
+                assert!(
+                    on_boundary || winding_number(point, boundary) != 0,
+                    "start {start}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_distance() {
         let p1 = Point { x: 0.0, y: 0.0 };
@@ -359,16 +1623,16 @@ mod tests {
     #[test]
     fn test_is_angle_in_arc() {
         // Normal arc
-        assert!(is_angle_in_arc(45.0, 0.0, 90.0));
-        assert!(!is_angle_in_arc(100.0, 0.0, 90.0));
+        assert!(is_angle_in_arc(45.0, 0.0, 90.0, Tolerance::default()));
+        assert!(!is_angle_in_arc(100.0, 0.0, 90.0, Tolerance::default()));
 
         // Arc crossing 0 degrees
-        assert!(is_angle_in_arc(350.0, 340.0, 10.0));
-        assert!(is_angle_in_arc(5.0, 340.0, 10.0));
-        assert!(!is_angle_in_arc(180.0, 340.0, 10.0));
+        assert!(is_angle_in_arc(350.0, 340.0, 10.0, Tolerance::default()));
+        assert!(is_angle_in_arc(5.0, 340.0, 10.0, Tolerance::default()));
+        assert!(!is_angle_in_arc(180.0, 340.0, 10.0, Tolerance::default()));
 
         // Full circle
-        assert!(is_angle_in_arc(180.0, 0.0, 360.0));
+        assert!(is_angle_in_arc(180.0, 0.0, 360.0, Tolerance::default()));
     }
 
     #[test]
@@ -380,7 +1644,7 @@ mod tests {
         assert!((mid.y - 5.0).abs() < 1e-10);
 
         // Arc segment
-        let arc = PathSegment::Arc(Point { x: 0.0, y: 0.0 }, 10.0, 0.0, 90.0);
+        let arc = arc_from_span(Point { x: 0.0, y: 0.0 }, 10.0, 0.0, 90.0);
         let mid = get_segment_midpoint(&arc);
         // Midpoint should be at 45 degrees
         let expected_x = 10.0 * (45.0_f64.to_radians()).cos();
@@ -457,12 +1721,7 @@ mod tests {
     fn test_signed_area_circle() {
         // Full circle with radius 5
         let radius = 5.0;
-        let segments = vec![PathSegment::Arc(
-            Point { x: 0.0, y: 0.0 },
-            radius,
-            0.0,
-            360.0,
-        )];
+        let segments = vec![arc_from_span(Point { x: 0.0, y: 0.0 }, radius, 0.0, 360.0)];
         let area = signed_area_of_path(&segments);
         let expected = std::f64::consts::PI * radius * radius;
         assert!((area - expected).abs() < 1e-6, "Circle area should be π*r²");
@@ -473,7 +1732,7 @@ mod tests {
         // Semicircle (arc from 0° to 180°) plus diameter
         let radius = 5.0;
         let segments = vec![
-            PathSegment::Arc(Point { x: 0.0, y: 0.0 }, radius, 0.0, 180.0),
+            arc_from_span(Point { x: 0.0, y: 0.0 }, radius, 0.0, 180.0),
             PathSegment::Line(Point { x: -5.0, y: 0.0 }, Point { x: 5.0, y: 0.0 }),
             PathSegment::ClosePath,
         ];
@@ -491,7 +1750,7 @@ mod tests {
         // Simple path: line + semicircle
         let segments = vec![
             PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
-            PathSegment::Arc(Point { x: 5.0, y: 0.0 }, 5.0, 0.0, 180.0),
+            arc_from_span(Point { x: 5.0, y: 0.0 }, 5.0, 0.0, 180.0),
             PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
             PathSegment::ClosePath,
         ];
@@ -516,16 +1775,16 @@ mod tests {
         let center = Point { x: 0.0, y: 0.0 };
 
         // Use single full circle arc for comparison
-        let segments = vec![PathSegment::Arc(center, radius, 0.0, 360.0)];
+        let segments = vec![arc_from_span(center, radius, 0.0, 360.0)];
 
         let area_full = signed_area_of_path(&segments);
 
         // Now try with multiple arcs
         let segments_multi = vec![
-            PathSegment::Arc(center, radius, 0.0, 70.0),
-            PathSegment::Arc(center, radius, 70.0, 160.0),
-            PathSegment::Arc(center, radius, 160.0, 270.0),
-            PathSegment::Arc(center, radius, 270.0, 360.0),
+            arc_from_span(center, radius, 0.0, 70.0),
+            arc_from_span(center, radius, 70.0, 160.0),
+            arc_from_span(center, radius, 160.0, 270.0),
+            arc_from_span(center, radius, 270.0, 360.0),
         ];
 
         let area_multi = signed_area_of_path(&segments_multi);
@@ -578,8 +1837,9 @@ mod tests {
             PathSegment::Arc(
                 Point { x: 5.0, y: 10.0 },
                 5.0,
-                0.0,   // Start at (10, 10)
-                180.0, // End at (0, 10), curving upward
+                Angle::from_degrees(0.0),   // Start at (10, 10)
+                Angle::from_degrees(180.0), // End at (0, 10), curving upward
+                ArcDirection::CounterClockwise,
             ),
             // Left edge: top to bottom
             PathSegment::Line(Point { x: 0.0, y: 10.0 }, Point { x: 0.0, y: 0.0 }),
@@ -600,4 +1860,415 @@ mod tests {
         // And verify it's positive (CCW orientation)
         assert!(area > 0.0, "Polygon should have positive area (CCW)");
     }
+
+    #[test]
+    fn test_signed_area_arc_away_from_origin() {
+        // Rectangle (0,0)-(10,10) with a semicircular bump on its top edge
+        let segments = vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }),
+            arc_from_span(Point { x: 5.0, y: 10.0 }, 5.0, 0.0, 180.0),
+            PathSegment::Line(Point { x: 0.0, y: 10.0 }, Point { x: 0.0, y: 0.0 }),
+            PathSegment::ClosePath,
+        ];
+        let expected = 100.0 + std::f64::consts::PI * 25.0 / 2.0;
+        assert!((signed_area_of_path(&segments) - expected).abs() < 1e-9);
+
+        // Reversing the path flips the sign
+        let reversed: Vec<PathSegment> = segments
+            .iter()
+            .rev()
+            .filter(|s| !matches!(s, PathSegment::ClosePath))
+            .map(reverse_segment)
+            .collect();
+        assert!((signed_area_of_path(&reversed) + expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_signed_area_multiple_subpaths() {
+        // Two disjoint squares, each closed by its own ClosePath
+        let segments = vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 }),
+            PathSegment::ClosePath,
+            PathSegment::Line(Point { x: 20.0, y: 0.0 }, Point { x: 30.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 30.0, y: 0.0 }, Point { x: 30.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 30.0, y: 10.0 }, Point { x: 20.0, y: 10.0 }),
+            PathSegment::ClosePath,
+        ];
+        assert!((signed_area_of_path(&segments) - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_winding_number_square() {
+        let ccw = vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 10.0 }),
+            PathSegment::Line(Point { x: 10.0, y: 10.0 }, Point { x: 0.0, y: 10.0 }),
+            PathSegment::ClosePath,
+        ];
+        assert_eq!(winding_number(Point { x: 5.0, y: 5.0 }, &ccw), 1);
+        assert_eq!(winding_number(Point { x: 15.0, y: 5.0 }, &ccw), 0);
+        // Same height as the vertices
+        assert_eq!(winding_number(Point { x: 5.0, y: 0.0 }, &ccw), 1);
+        assert_eq!(winding_number(Point { x: -5.0, y: 10.0 }, &ccw), 0);
+
+        let cw = orient_path(&ccw, false);
+        assert_eq!(winding_number(Point { x: 5.0, y: 5.0 }, &cw), -1);
+    }
+
+    #[test]
+    fn test_winding_number_diamond_vertex_height() {
+        // The horizontal line through the point passes exactly through two vertices
+        let diamond = vec![
+            PathSegment::Line(Point { x: 0.0, y: -5.0 }, Point { x: 5.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 5.0, y: 0.0 }, Point { x: 0.0, y: 5.0 }),
+            PathSegment::Line(Point { x: 0.0, y: 5.0 }, Point { x: -5.0, y: 0.0 }),
+            PathSegment::Line(Point { x: -5.0, y: 0.0 }, Point { x: 0.0, y: -5.0 }),
+        ];
+        assert_eq!(winding_number(Point { x: 0.0, y: 0.0 }, &diamond), 1);
+        assert_eq!(winding_number(Point { x: -7.0, y: 0.0 }, &diamond), 0);
+    }
+
+    #[test]
+    fn test_winding_number_arcs() {
+        let circle = vec![arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 0.0, 360.0)];
+        assert_eq!(winding_number(Point { x: 0.0, y: 0.0 }, &circle), 1);
+        assert_eq!(winding_number(Point { x: 4.9, y: 0.0 }, &circle), 1);
+        assert_eq!(winding_number(Point { x: -4.9, y: 4.9 }, &circle), 0);
+        assert_eq!(winding_number(Point { x: 0.0, y: 6.0 }, &circle), 0);
+
+        let clockwise = vec![arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 360.0, 0.0)];
+        assert_eq!(winding_number(Point { x: 1.0, y: 1.0 }, &clockwise), -1);
+
+        // Upper half disk: arc plus the diameter closing it
+        let half_disk = vec![
+            arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 0.0, 180.0),
+            PathSegment::ClosePath,
+        ];
+        assert_eq!(winding_number(Point { x: 0.0, y: 2.0 }, &half_disk), 1);
+        assert_eq!(winding_number(Point { x: 0.0, y: -2.0 }, &half_disk), 0);
+    }
+
+    #[test]
+    fn test_winding_number_large_shape() {
+        // Far larger than any fixed ray length
+        let circle = vec![arc_from_span(Point { x: 0.0, y: 0.0 }, 1e6, 0.0, 360.0)];
+        assert_eq!(winding_number(Point { x: -9.9e5, y: 0.0 }, &circle), 1);
+        assert_eq!(winding_number(Point { x: 1.1e6, y: 0.0 }, &circle), 0);
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        let line = PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 });
+        assert!((distance_to_segment(Point { x: 5.0, y: 3.0 }, &line) - 3.0).abs() < 1e-12);
+        assert!((distance_to_segment(Point { x: 13.0, y: 4.0 }, &line) - 5.0).abs() < 1e-12);
+
+        // Upper half of a circle, travelled clockwise
+        let arc = arc_from_span(Point { x: 0.0, y: 0.0 }, 5.0, 180.0, 0.0);
+        assert!((distance_to_segment(Point { x: 0.0, y: 7.0 }, &arc) - 2.0).abs() < 1e-12);
+        assert!((distance_to_segment(Point { x: 5.0, y: -3.0 }, &arc) - 3.0).abs() < 1e-12);
+        // Just before the start, within the angle tolerance only when it is loose
+        let before_start = point_on_circle(Point { x: 0.0, y: 0.0 }, 7.0, 181.0);
+        assert!(distance_to_segment(before_start, &arc) - 2.0 > 1e-3);
+        let loose = Tolerance {
+            angle: 2.0,
+            ..Tolerance::default()
+        };
+        let radial = distance_to_segment_with_tolerance(before_start, &arc, loose);
+        assert!((radial - 2.0).abs() < 1e-12);
+
+        let tangent = segment_tangent(&arc, Point { x: 0.0, y: 5.0 }).unwrap();
+        assert!(tangent.x > 0.0 && tangent.y.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_arc_sweep_follows_direction() {
+        let degrees = Angle::from_degrees;
+        let ccw = ArcDirection::CounterClockwise;
+        let cw = ArcDirection::Clockwise;
+        assert_eq!(arc_sweep(degrees(0.0), degrees(90.0), ccw), 90.0);
+        assert_eq!(arc_sweep(degrees(0.0), degrees(90.0), cw), -270.0);
+        assert_eq!(arc_sweep(degrees(90.0), degrees(0.0), cw), -90.0);
+        assert_eq!(arc_sweep(degrees(-90.0), degrees(270.0), ccw), 360.0);
+        assert_eq!(arc_sweep(Angle::FULL_TURN, Angle::ZERO, cw), -360.0);
+        assert_eq!(arc_sweep(degrees(45.0), degrees(45.0), ccw), 0.0);
+
+        let angle = Angle::from_radians(std::f64::consts::FRAC_PI_2);
+        assert!((angle.degrees() - 90.0).abs() < 1e-12);
+        assert!((degrees(-90.0).normalized().degrees() - 270.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_clockwise_arc_geometry() {
+        // Three quarters of a circle, clockwise from 0° through 270° to 90°
+        let center = Point { x: 0.0, y: 0.0 };
+        let arc = PathSegment::Arc(
+            center,
+            2.0,
+            Angle::ZERO,
+            Angle::from_degrees(90.0),
+            ArcDirection::Clockwise,
+        );
+
+        let midpoint = get_segment_midpoint(&arc);
+        assert!(distance(midpoint, point_on_circle(center, 2.0, -135.0)) < 1e-12);
+
+        let bbox = get_segment_bounding_box(&arc).unwrap();
+        assert!((bbox.min.x + 2.0).abs() < 1e-12 && (bbox.min.y + 2.0).abs() < 1e-12);
+        assert!((bbox.max.x - 2.0).abs() < 1e-12 && (bbox.max.y - 2.0).abs() < 1e-12);
+
+        // Reversing keeps the same three quarters, traversed the other way
+        let reversed = reverse_segment(&arc);
+        assert!(matches!(
+            reversed,
+            PathSegment::Arc(_, _, _, _, ArcDirection::CounterClockwise)
+        ));
+        assert!(distance(get_segment_midpoint(&reversed), midpoint) < 1e-12);
+        let pi = std::f64::consts::PI;
+        let closed = |segment: PathSegment| {
+            let start = get_segment_start_point(&segment).unwrap();
+            let end = get_segment_end_point(&segment).unwrap();
+            vec![
+                segment,
+                PathSegment::Line(end, center),
+                PathSegment::Line(center, start),
+            ]
+        };
+        // A 270° sector has area ¾·π·r² = 3π
+        assert!((signed_area_of_path(&closed(arc)) + 3.0 * pi).abs() < 1e-9);
+        assert!((signed_area_of_path(&closed(reversed)) - 3.0 * pi).abs() < 1e-9);
+    }
+
+    fn rotated_ellipse() -> Ellipse {
+        Ellipse {
+            center: Point { x: 3.0, y: -1.0 },
+            radius_x: 4.0,
+            radius_y: 1.5,
+            rotation: Angle::from_degrees(30.0),
+        }
+    }
+
+    #[test]
+    fn test_elliptical_arc_area_and_bounding_box() {
+        let ellipse = rotated_ellipse();
+        let pi = std::f64::consts::PI;
+        let full = vec![PathSegment::EllipticalArc(
+            ellipse,
+            Angle::ZERO,
+            Angle::FULL_TURN,
+            ArcDirection::CounterClockwise,
+        )];
+        assert!((signed_area_of_path(&full) - pi * 6.0).abs() < 1e-9);
+
+        // Half of it, closed along the major axis, traversed clockwise
+        let half = PathSegment::EllipticalArc(
+            ellipse,
+            Angle::from_degrees(180.0),
+            Angle::ZERO,
+            ArcDirection::Clockwise,
+        );
+        let path = vec![half, PathSegment::ClosePath];
+        assert!((signed_area_of_path(&path) + pi * 3.0).abs() < 1e-9);
+
+        // Half-widths of a rotated ellipse's box: √(a²cos²φ + b²sin²φ) and
+        // √(a²sin²φ + b²cos²φ)
+        let (sin_r, cos_r) = ellipse.rotation.radians().sin_cos();
+        let half_width = (16.0 * cos_r * cos_r + 2.25 * sin_r * sin_r).sqrt();
+        let half_height = (16.0 * sin_r * sin_r + 2.25 * cos_r * cos_r).sqrt();
+        let bbox = get_segment_bounding_box(&full[0]).unwrap();
+        assert!((bbox.min.x - (3.0 - half_width)).abs() < 1e-12);
+        assert!((bbox.max.x - (3.0 + half_width)).abs() < 1e-12);
+        assert!((bbox.min.y - (-1.0 - half_height)).abs() < 1e-12);
+        assert!((bbox.max.y - (-1.0 + half_height)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_elliptical_arc_winding_and_distance() {
+        let ellipse = rotated_ellipse();
+        let full = [PathSegment::EllipticalArc(
+            ellipse,
+            Angle::from_degrees(-90.0),
+            Angle::from_degrees(270.0),
+            ArcDirection::CounterClockwise,
+        )];
+        let clockwise = [reverse_segment(&full[0])];
+        let unit = |x: f64, y: f64| ellipse.from_unit_frame(Point { x, y });
+
+        for (x, y) in [
+            (0.0, 0.0),
+            (0.9, 0.0),
+            (-0.5, 0.8),
+            (0.0, -0.99),
+            (0.7, 0.7),
+        ] {
+            assert_eq!(winding_number(unit(x, y), &full), 1);
+            assert_eq!(winding_number(unit(x, y), &clockwise), -1);
+        }
+        for (x, y) in [(1.1, 0.0), (0.0, 1.05), (-0.8, -0.8), (3.0, 3.0)] {
+            assert_eq!(winding_number(unit(x, y), &full), 0);
+        }
+
+        // Along the minor axis the nearest point is the co-vertex
+        let outside = unit(0.0, 3.0);
+        assert!((distance_to_segment(outside, &full[0]) - 3.0).abs() < 1e-9);
+        let on_curve = ellipse.point_at(Angle::from_degrees(123.0));
+        assert!(distance_to_segment(on_curve, &full[0]) < 1e-9);
+        assert!((segment_parameter(&full[0], on_curve) - 123.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_closest_ellipse_parameter_across_seam() {
+        let ellipse = Ellipse {
+            center: Point { x: 10.0, y: 50.0 },
+            radius_x: 3.0,
+            radius_y: 1.0,
+            rotation: Angle::ZERO,
+        };
+        let full = PathSegment::EllipticalArc(
+            ellipse,
+            Angle::ZERO,
+            Angle::FULL_TURN,
+            ArcDirection::CounterClockwise,
+        );
+        // Points just below the seam start out nearest to 0° in the coarse
+        // sample and must wrap to the far end of the span
+        for t in [359.0, 359.9, 0.5, 1.0] {
+            let on_curve = ellipse.point_at(Angle::from_degrees(t));
+            assert!(distance_to_segment(on_curve, &full) < 1e-9);
+            let found = closest_ellipse_parameter(&ellipse, 0.0, 360.0, on_curve);
+            let error = (found - t + 180.0).rem_euclid(360.0) - 180.0;
+            assert!(error.abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_elliptical_arc_length() {
+        // A circle, and the quarter of a 2:1 ellipse (a·E(e), e² = 3/4)
+        let circle = Ellipse::circle(Point { x: 1.0, y: 1.0 }, 2.0);
+        let pi = std::f64::consts::PI;
+        assert!((elliptical_arc_length(&circle, 0.0, 360.0) - 4.0 * pi).abs() < 1e-12);
+        let ellipse = Ellipse {
+            radius_x: 2.0,
+            radius_y: 1.0,
+            ..circle
+        };
+        let quarter = elliptical_arc_length(&ellipse, 90.0, 0.0);
+        assert!((quarter - 2.422_112_055_920_8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_segment_and_path_lengths() {
+        let pi = std::f64::consts::PI;
+        let origin = Point { x: 0.0, y: 0.0 };
+        let line = PathSegment::Line(origin, Point { x: 3.0, y: 4.0 });
+        assert_eq!(segment_length(&line), 5.0);
+
+        // The same two end angles give a quarter or three quarters of a turn
+        let arc = |direction| {
+            PathSegment::Arc(
+                origin,
+                2.0,
+                Angle::from_degrees(90.0),
+                Angle::ZERO,
+                direction,
+            )
+        };
+        assert!((segment_length(&arc(ArcDirection::Clockwise)) - pi).abs() < 1e-12);
+        assert!((segment_length(&arc(ArcDirection::CounterClockwise)) - 3.0 * pi).abs() < 1e-12);
+        let circle = PathSegment::Arc(
+            origin,
+            2.0,
+            Angle::ZERO,
+            Angle::FULL_TURN,
+            ArcDirection::CounterClockwise,
+        );
+        assert!((segment_length(&circle) - 4.0 * pi).abs() < 1e-12);
+
+        // y = x·(2 - x) over [0, 2] has length √5 + asinh(2)/2
+        let parabola =
+            PathSegment::QuadBezier(origin, Point { x: 1.0, y: 2.0 }, Point { x: 2.0, y: 0.0 });
+        let expected = 5f64.sqrt() + 2f64.asinh() / 2.0;
+        assert!((segment_length(&parabola) - expected).abs() < 1e-9);
+
+        // ClosePath adds the line back across the diameter
+        let half_disc = vec![
+            PathSegment::Arc(
+                origin,
+                1.0,
+                Angle::ZERO,
+                Angle::from_degrees(180.0),
+                ArcDirection::CounterClockwise,
+            ),
+            PathSegment::DrawPoint(Point { x: 5.0, y: 5.0 }),
+            PathSegment::ClosePath,
+        ];
+        assert!((path_length(&half_disc) - (pi + 2.0)).abs() < 1e-12);
+
+        let square = |size: f64| {
+            let corners = [(0.0, 0.0), (size, 0.0), (size, size), (0.0, size)];
+            (0..4)
+                .map(|i| {
+                    let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                    PathSegment::Line(Point { x: a.0, y: a.1 }, Point { x: b.0, y: b.1 })
+                })
+                .collect::<Vec<_>>()
+        };
+        let frame = ResolvedShape {
+            contours: vec![Contour::outer(square(4.0)), Contour::hole(square(2.0))],
+        };
+        assert_eq!(contour_lengths(&frame), vec![16.0, 8.0]);
+        assert_eq!(perimeter(&frame), 24.0);
+    }
+
+    #[test]
+    fn test_bezier_area_bounds_and_winding() {
+        // The parabola y = x·(2 - x) over [0, 2], closed along the x axis,
+        // encloses ⅔·base·height = 4/3
+        let quad = PathSegment::QuadBezier(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 2.0, y: 0.0 },
+        );
+        let path = vec![quad, PathSegment::ClosePath];
+        assert!((signed_area_of_path(&path) + 4.0 / 3.0).abs() < 1e-12);
+
+        // The same curve as a cubic, traversed the other way
+        let cubic = PathSegment::CubicBezier(
+            Point { x: 2.0, y: 0.0 },
+            Point {
+                x: 4.0 / 3.0,
+                y: 4.0 / 3.0,
+            },
+            Point {
+                x: 2.0 / 3.0,
+                y: 4.0 / 3.0,
+            },
+            Point { x: 0.0, y: 0.0 },
+        );
+        let path = vec![cubic, PathSegment::ClosePath];
+        assert!((signed_area_of_path(&path) - 4.0 / 3.0).abs() < 1e-12);
+
+        // The box reaches the top of the curve, not the control point
+        let bbox = get_segment_bounding_box(&quad).unwrap();
+        assert!((bbox.max.y - 1.0).abs() < 1e-12);
+        assert!(bbox.min.x.abs() < 1e-12 && (bbox.max.x - 2.0).abs() < 1e-12);
+
+        assert_eq!(winding_number(Point { x: 1.0, y: 0.5 }, &path), 1);
+        assert_eq!(winding_number(Point { x: 1.0, y: 0.99 }, &path), 1);
+        assert_eq!(winding_number(Point { x: 1.0, y: 1.01 }, &path), 0);
+        assert_eq!(winding_number(Point { x: 0.1, y: 0.5 }, &path), 0);
+
+        let midpoint = get_segment_midpoint(&quad);
+        assert!(distance(midpoint, Point { x: 1.0, y: 1.0 }) < 1e-12);
+        assert!((distance_to_segment(Point { x: 1.0, y: 3.0 }, &quad) - 2.0).abs() < 1e-9);
+        let reversed = reverse_segment(&cubic);
+        assert!(
+            distance(
+                get_segment_start_point(&reversed).unwrap(),
+                Point { x: 0.0, y: 0.0 }
+            ) < 1e-12
+        );
+    }
 }
@@ -1,89 +1,205 @@
-use crate::geometry::{distance, is_angle_in_arc};
-use crate::types::Point;
+use crate::bezier::{
+    closest_cubic_parameter, cubic_cubic_candidates, cubic_point, refine_cubic_intersection,
+};
+use crate::geometry::{
+    arc_from_span, distance, distance_to_segment, is_angle_in_arc, point_on_circle,
+    polynomial_roots, sampled_roots,
+};
+use crate::predicates::{CircleRelation, circle_relation, circle_side, orient2d};
+use crate::types::{Angle, ArcDirection, Ellipse, PathSegment, Point, Tolerance};
+
+/// Point at parameter `t` on the segment from `start` to `end`
+fn lerp(start: Point, end: Point, t: f64) -> Point {
+    Point {
+        x: start.x + t * (end.x - start.x),
+        y: start.y + t * (end.y - start.y),
+    }
+}
+
+/// Whether two non-degenerate segments lie on one line, exactly or within
+/// `eps` of each other's line
+fn lines_collinear(
+    l1_start: Point,
+    l1_end: Point,
+    l2_start: Point,
+    l2_end: Point,
+    eps: f64,
+) -> bool {
+    // orient2d is the distance to the line times the segment length
+    let o1 = orient2d(l1_start, l1_end, l2_start);
+    let o2 = orient2d(l1_start, l1_end, l2_end);
+    if o1 == 0.0 && o2 == 0.0 {
+        return true;
+    }
+    let o3 = orient2d(l2_start, l2_end, l1_start);
+    let o4 = orient2d(l2_start, l2_end, l1_end);
+    o1.abs().max(o2.abs()) < eps * distance(l1_start, l1_end)
+        && o3.abs().max(o4.abs()) < eps * distance(l2_start, l2_end)
+}
+
+/// Interval shared by two collinear segments, as its ends in the direction
+/// of the first segment
+///
+/// Both ends are the same point when the segments only touch. The ends are
+/// always endpoints of one of the segments, so no new coordinates appear.
+fn collinear_overlap(
+    l1_start: Point,
+    l1_end: Point,
+    l2_start: Point,
+    l2_end: Point,
+    eps: f64,
+) -> Option<(Point, Point)> {
+    let dx = l1_end.x - l1_start.x;
+    let dy = l1_end.y - l1_start.y;
+    let length_squared = dx * dx + dy * dy;
+    let param = |p: Point| ((p.x - l1_start.x) * dx + (p.y - l1_start.y) * dy) / length_squared;
+
+    // (parameter along the first segment, point) of the second segment's ends
+    let (low, high) = {
+        let a = (param(l2_start), l2_start);
+        let b = (param(l2_end), l2_end);
+        if a.0 <= b.0 { (a, b) } else { (b, a) }
+    };
+    let from = if low.0 > 0.0 { low } else { (0.0, l1_start) };
+    let to = if high.0 < 1.0 { high } else { (1.0, l1_end) };
+
+    let overlap = (to.0 - from.0) * length_squared.sqrt();
+    if overlap <= -eps {
+        None
+    } else if overlap < eps {
+        Some((from.1, from.1))
+    } else {
+        Some((from.1, to.1))
+    }
+}
 
 /// Find intersection points between two line segments
+///
+/// Crossings are decided with exact orientation tests, so swapping the
+/// segments or their directions never changes the answer. Endpoints within
+/// `tolerance.distance` of the other segment count as touching it. Collinear
+/// segments intersect at the ends of their shared interval, see
+/// `line_line_overlap` for the interval itself.
 pub fn line_line_intersection(
     l1_start: Point,
     l1_end: Point,
     l2_start: Point,
     l2_end: Point,
+    tolerance: Tolerance,
 ) -> Vec<Point> {
     let mut intersections = Vec::new();
-
-    let x1 = l1_start.x;
-    let y1 = l1_start.y;
-    let x2 = l1_end.x;
-    let y2 = l1_end.y;
-    let x3 = l2_start.x;
-    let y3 = l2_start.y;
-    let x4 = l2_end.x;
-    let y4 = l2_end.y;
+    let eps = tolerance.distance;
 
     // Check if either segment is actually a point
-    let l1_is_point = (x1 - x2).abs() < 1e-10 && (y1 - y2).abs() < 1e-10;
-    let l2_is_point = (x3 - x4).abs() < 1e-10 && (y3 - y4).abs() < 1e-10;
+    let l1_is_point = distance(l1_start, l1_end) < eps;
+    let l2_is_point = distance(l2_start, l2_end) < eps;
 
     if l1_is_point && l2_is_point {
         // Both are points - check if they're the same
-        if (x1 - x3).abs() < 1e-10 && (y1 - y3).abs() < 1e-10 {
-            intersections.push(Point { x: x1, y: y1 });
+        if distance(l1_start, l2_start) < eps {
+            intersections.push(l1_start);
         }
         return intersections;
     }
 
     if l1_is_point {
         // Check if point l1 is on line segment l2
-        let t = if (x4 - x3).abs() > (y4 - y3).abs() {
-            (x1 - x3) / (x4 - x3)
-        } else {
-            (y1 - y3) / (y4 - y3)
-        };
-        if t >= -1e-10 && t <= 1.0 + 1e-10 {
-            let px = x3 + t * (x4 - x3);
-            let py = y3 + t * (y4 - y3);
-            if (px - x1).abs() < 1e-10 && (py - y1).abs() < 1e-10 {
-                intersections.push(Point { x: x1, y: y1 });
-            }
+        if distance_to_segment(l1_start, &PathSegment::Line(l2_start, l2_end)) < eps {
+            intersections.push(l1_start);
         }
         return intersections;
     }
 
     if l2_is_point {
         // Check if point l2 is on line segment l1
-        let t = if (x2 - x1).abs() > (y2 - y1).abs() {
-            (x3 - x1) / (x2 - x1)
-        } else {
-            (y3 - y1) / (y2 - y1)
-        };
-        if t >= -1e-10 && t <= 1.0 + 1e-10 {
-            let px = x1 + t * (x2 - x1);
-            let py = y1 + t * (y2 - y1);
-            if (px - x3).abs() < 1e-10 && (py - y3).abs() < 1e-10 {
-                intersections.push(Point { x: x3, y: y3 });
+        if distance_to_segment(l2_start, &PathSegment::Line(l1_start, l1_end)) < eps {
+            intersections.push(l2_start);
+        }
+        return intersections;
+    }
+
+    // Side of each endpoint relative to the other segment's line
+    let o1 = orient2d(l1_start, l1_end, l2_start);
+    let o2 = orient2d(l1_start, l1_end, l2_end);
+    let o3 = orient2d(l2_start, l2_end, l1_start);
+    let o4 = orient2d(l2_start, l2_end, l1_end);
+
+    if lines_collinear(l1_start, l1_end, l2_start, l2_end, eps) {
+        if let Some((from, to)) = collinear_overlap(l1_start, l1_end, l2_start, l2_end, eps) {
+            intersections.push(from);
+            if distance(from, to) >= eps {
+                intersections.push(to);
             }
         }
         return intersections;
     }
 
-    let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+    // Proper crossing: each segment strictly separates the other's endpoints
+    if o1.signum() * o2.signum() < 0.0 && o3.signum() * o4.signum() < 0.0 {
+        intersections.push(lerp(l1_start, l1_end, o3 / (o3 - o4)));
+        return intersections;
+    }
 
-    if denom.abs() < 1e-10 {
-        return intersections; // Lines are parallel or coincident
+    // Touching: an endpoint lies on, or within tolerance of, the other segment
+    let line1 = PathSegment::Line(l1_start, l1_end);
+    let line2 = PathSegment::Line(l2_start, l2_end);
+    let touching = [
+        (l1_start, &line2),
+        (l1_end, &line2),
+        (l2_start, &line1),
+        (l2_end, &line1),
+    ]
+    .into_iter()
+    .find(|(point, other)| distance_to_segment(*point, other) < eps);
+    if let Some((point, _)) = touching {
+        intersections.push(point);
     }
 
-    let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / denom;
-    let u = -((x1 - x2) * (y1 - y3) - (y1 - y2) * (x1 - x3)) / denom;
+    intersections
+}
 
-    if t >= 0.0 && t <= 1.0 && u >= 0.0 && u <= 1.0 {
-        let ix = x1 + t * (x2 - x1);
-        let iy = y1 + t * (y2 - y1);
-        intersections.push(Point { x: ix, y: iy });
+/// Part of the first line segment shared with the second, in the first
+/// segment's direction
+///
+/// Returns `None` unless the segments are collinear and overlap by more than
+/// `tolerance.distance`.
+pub fn line_line_overlap(
+    l1_start: Point,
+    l1_end: Point,
+    l2_start: Point,
+    l2_end: Point,
+    tolerance: Tolerance,
+) -> Option<PathSegment> {
+    let eps = tolerance.distance;
+    if distance(l1_start, l1_end) < eps
+        || distance(l2_start, l2_end) < eps
+        || !lines_collinear(l1_start, l1_end, l2_start, l2_end, eps)
+    {
+        return None;
     }
+    collinear_overlap(l1_start, l1_end, l2_start, l2_end, eps)
+        .filter(|&(from, to)| distance(from, to) >= eps)
+        .map(|(from, to)| PathSegment::Line(from, to))
+}
 
-    intersections
+/// Side of a circle that `point` lies on, snapped to the circle within
+/// `tolerance`: 1 inside, 0 on the circle and -1 outside
+fn circle_side_with_tolerance(center: Point, radius: f64, point: Point, eps: f64) -> i32 {
+    if (distance(point, center) - radius).abs() < eps {
+        0
+    } else if circle_side(center, radius, point) > 0.0 {
+        1
+    } else {
+        -1
+    }
 }
 
 /// Find intersection points between a line segment and a circle arc
+///
+/// Which of the circle crossings lie on the segment is decided by the exact
+/// side of the circle each endpoint is on, so the number of crossings is
+/// always consistent with the endpoints. A line passing within
+/// `tolerance.distance` of the circle touches it at a single point.
 pub fn line_arc_intersection(
     line_start: Point,
     line_end: Point,
@@ -91,8 +207,14 @@ pub fn line_arc_intersection(
     radius: f64,
     start_angle: f64,
     end_angle: f64,
+    tolerance: Tolerance,
 ) -> Vec<Point> {
     let mut intersections = Vec::new();
+    let eps = tolerance.distance;
+    let angle_tolerance = Tolerance {
+        angle: tolerance.angle_at_radius(radius),
+        ..tolerance
+    };
 
     let dx = line_end.x - line_start.x;
     let dy = line_end.y - line_start.y;
@@ -102,52 +224,87 @@ pub fn line_arc_intersection(
     let a = dx * dx + dy * dy;
 
     // Handle degenerate case: line segment is a point
-    if a < 1e-10 {
+    if a < eps * eps {
         let dist = (fx * fx + fy * fy).sqrt();
-        if (dist - radius).abs() < 1e-10 {
+        if (dist - radius).abs() < eps {
             let angle = fy.atan2(fx).to_degrees();
-            if is_angle_in_arc(angle, start_angle, end_angle) {
+            if is_angle_in_arc(angle, start_angle, end_angle, angle_tolerance) {
                 intersections.push(line_start);
             }
         }
         return intersections;
     }
 
+    // |line(t) - center|² - r² = a·t² + b·t + c
+    let length = a.sqrt();
     let b = 2.0 * (fx * dx + fy * dy);
     let c = fx * fx + fy * fy - radius * radius;
-
     let discriminant = b * b - 4.0 * a * c;
+    let roots = || {
+        let sqrt_disc = discriminant.max(0.0).sqrt();
+        ((-b - sqrt_disc) / (2.0 * a), (-b + sqrt_disc) / (2.0 * a))
+    };
 
-    if discriminant < 0.0 {
-        return intersections; // No intersection
-    }
-
-    let sqrt_disc = discriminant.sqrt();
-    let t1 = (-b - sqrt_disc) / (2.0 * a);
-    let t2 = (-b + sqrt_disc) / (2.0 * a);
-
-    // Check first intersection point
-    if t1 >= -1e-10 && t1 <= 1.0 + 1e-10 {
-        let ix = line_start.x + t1 * dx;
-        let iy = line_start.y + t1 * dy;
-        let pt = Point { x: ix, y: iy };
-
-        // Check if point is on the arc (within start_angle and end_angle)
-        let angle = (iy - center.y).atan2(ix - center.x).to_degrees();
-        if is_angle_in_arc(angle, start_angle, end_angle) {
-            intersections.push(pt);
+    let start_side = circle_side_with_tolerance(center, radius, line_start, eps);
+    let end_side = circle_side_with_tolerance(center, radius, line_end, eps);
+
+    // Parameters of the crossings on the segment, by the sides of its endpoints
+    let mut params: Vec<f64> = Vec::new();
+    match (start_side, end_side) {
+        // A chord of a disk cannot leave it
+        (1, 1) => {}
+        (1, -1) => params.push(roots().1.clamp(0.0, 1.0)),
+        (-1, 1) => params.push(roots().0.clamp(0.0, 1.0)),
+        (0, other) => {
+            params.push(0.0);
+            // The other root is -b/a; it is on the segment when the line
+            // heads into the disk and leaves it again before the end
+            if other == 0 {
+                params.push(1.0);
+            } else if other == -1 && b < 0.0 {
+                params.push((-b / a).clamp(0.0, 1.0));
+            }
+        }
+        (other, 0) => {
+            params.push(1.0);
+            if other == -1 && 2.0 * a + b > 0.0 {
+                params.push((-b / a - 1.0).clamp(0.0, 1.0));
+            }
+        }
+        // Both endpoints outside: two crossings or none, around the vertex
+        _ => {
+            let vertex = -b / (2.0 * a);
+            if (0.0..=1.0).contains(&vertex) {
+                // discriminant = 4a(r² - d²) with d the distance from the center to the line
+                let line_distance = (radius * radius - discriminant / (4.0 * a)).max(0.0).sqrt();
+                if (line_distance - radius).abs() <= eps {
+                    // Touching: the roots of a vanishing discriminant are
+                    // only apart by the square root of its rounding error
+                    params.push(vertex);
+                } else if discriminant > 0.0 {
+                    let (t1, t2) = roots();
+                    params.push(t1.clamp(0.0, 1.0));
+                    params.push(t2.clamp(0.0, 1.0));
+                }
+            }
         }
     }
+    // A tangent gives the same crossing twice
+    params.sort_by(f64::total_cmp);
+    params.dedup_by(|t2, t1| (*t2 - *t1).abs() * length <= eps);
+
+    for t in params {
+        let pt = if t == 0.0 {
+            line_start
+        } else if t == 1.0 {
+            line_end
+        } else {
+            lerp(line_start, line_end, t)
+        };
 
-    // Check second intersection point
-    if discriminant > 1e-10 && t2 >= -1e-10 && t2 <= 1.0 + 1e-10 {
-        let ix = line_start.x + t2 * dx;
-        let iy = line_start.y + t2 * dy;
-        let pt = Point { x: ix, y: iy };
-
-        // Check if point is on the arc
-        let angle = (iy - center.y).atan2(ix - center.x).to_degrees();
-        if is_angle_in_arc(angle, start_angle, end_angle) {
+        // Check if point is on the arc (within start_angle and end_angle)
+        let angle = (pt.y - center.y).atan2(pt.x - center.x).to_degrees();
+        if is_angle_in_arc(angle, start_angle, end_angle, angle_tolerance) {
             intersections.push(pt);
         }
     }
@@ -156,6 +313,10 @@ pub fn line_arc_intersection(
 }
 
 /// Find intersection points between two circle arcs
+///
+/// How the circles are placed is decided exactly. Circles whose gap or
+/// overlap is within `tolerance.distance` touch at a single point.
+#[allow(clippy::too_many_arguments)]
 pub fn arc_arc_intersection(
     c1: Point,
     r1: f64,
@@ -165,85 +326,564 @@ pub fn arc_arc_intersection(
     r2: f64,
     start2: f64,
     end2: f64,
+    tolerance: Tolerance,
 ) -> Vec<Point> {
     let mut intersections = Vec::new();
+    let eps = tolerance.distance;
+    let tolerance1 = Tolerance {
+        angle: tolerance.angle_at_radius(r1),
+        ..tolerance
+    };
+    let tolerance2 = Tolerance {
+        angle: tolerance.angle_at_radius(r2),
+        ..tolerance
+    };
 
     let d = distance(c1, c2);
+    let relation = if d < eps {
+        if (r1 - r2).abs() < eps {
+            CircleRelation::Coincident
+        } else {
+            CircleRelation::Nested
+        }
+    } else if (d - (r1 + r2)).abs() < eps {
+        CircleRelation::ExternallyTangent
+    } else if (d - (r1 - r2).abs()).abs() < eps {
+        CircleRelation::InternallyTangent
+    } else {
+        circle_relation(c1, r1, c2, r2)
+    };
 
-    // No intersection if circles are too far apart or one contains the other
-    if d > r1 + r2 + 1e-10 || d < (r1 - r2).abs() - 1e-10 {
-        return intersections;
-    }
-
-    // Circles are coincident
-    if d < 1e-10 {
-        if (r1 - r2).abs() < 1e-10 {
-            // Same circle - infinite intersections, return empty for now
+    let candidates = match relation {
+        CircleRelation::Separate | CircleRelation::Nested => return intersections,
+        CircleRelation::Coincident => {
+            // Arcs of the same circle meet at the ends of their shared ranges,
+            // see `arc_arc_overlap` for the ranges themselves
+            let angle_eps = tolerance1.angle;
+            for (from, to) in cocircular_overlap(start1, end1, start2, end2, angle_eps) {
+                if to - from >= 360.0 - angle_eps {
+                    continue; // Whole circle shared, no ends
+                }
+                for angle in [from, to] {
+                    let pt = point_on_circle(c1, r1, angle);
+                    if intersections.iter().all(|p| distance(*p, pt) >= eps) {
+                        intersections.push(pt);
+                    }
+                }
+            }
             return intersections;
         }
-        return intersections;
+        CircleRelation::ExternallyTangent | CircleRelation::InternallyTangent => {
+            // The touching point is on the line through the centers, on the
+            // far side of c1 when circle 1 is the inner one
+            let t = if relation == CircleRelation::InternallyTangent && r1 < r2 {
+                -r1 / d
+            } else {
+                r1 / d
+            };
+            vec![lerp(c1, c2, t)]
+        }
+        CircleRelation::Crossing => {
+            let a = (r1 * r1 - r2 * r2 + d * d) / (2.0 * d);
+            let h = (r1 * r1 - a * a).max(0.0).sqrt();
+
+            let px = c1.x + a * (c2.x - c1.x) / d;
+            let py = c1.y + a * (c2.y - c1.y) / d;
+            let pt1 = Point {
+                x: px + h * (c2.y - c1.y) / d,
+                y: py - h * (c2.x - c1.x) / d,
+            };
+            let pt2 = Point {
+                x: px - h * (c2.y - c1.y) / d,
+                y: py + h * (c2.x - c1.x) / d,
+            };
+            if h > eps { vec![pt1, pt2] } else { vec![pt1] }
+        }
+    };
+
+    for pt in candidates {
+        let angle1 = (pt.y - c1.y).atan2(pt.x - c1.x).to_degrees();
+        let angle2 = (pt.y - c2.y).atan2(pt.x - c2.x).to_degrees();
+
+        if is_angle_in_arc(angle1, start1, end1, tolerance1)
+            && is_angle_in_arc(angle2, start2, end2, tolerance2)
+        {
+            intersections.push(pt);
+        }
+    }
+
+    intersections
+}
+
+/// Counter-clockwise sweep of an arc from `from` to `to`, where equal angles
+/// mean the whole circle as in `is_angle_in_arc`
+fn arc_span(from: f64, to: f64) -> f64 {
+    let sweep = to - from;
+    if sweep.abs() >= 360.0 {
+        return 360.0;
+    }
+    match sweep.rem_euclid(360.0) {
+        0.0 => 360.0,
+        span => span,
     }
+}
 
-    // Circles are tangent (one intersection point)
-    if (d - (r1 + r2)).abs() < 1e-10 || (d - (r1 - r2).abs()).abs() < 1e-10 {
-        let t = r1 / d;
-        let px = c1.x + t * (c2.x - c1.x);
-        let py = c1.y + t * (c2.y - c1.y);
-        let pt = Point { x: px, y: py };
+/// Angle ranges shared by two arcs of the same circle, both given
+/// counter-clockwise
+///
+/// The ranges are counter-clockwise and start at or after `from1`. A range of
+/// zero length means the arcs only touch there.
+fn cocircular_overlap(from1: f64, to1: f64, from2: f64, to2: f64, eps: f64) -> Vec<(f64, f64)> {
+    let span1 = arc_span(from1, to1);
+    let span2 = arc_span(from2, to2);
+    if span1 >= 360.0 - eps && span2 >= 360.0 - eps {
+        return vec![(from1, from1 + 360.0)];
+    }
+    if span1 >= 360.0 - eps {
+        return vec![(from2, from2 + span2)];
+    }
+    if span2 >= 360.0 - eps {
+        return vec![(from1, from1 + span1)];
+    }
+
+    // The second arc starts `offset` degrees after the first; it can also
+    // wrap around and overlap the first arc's beginning
+    let offset = (from2 - from1).rem_euclid(360.0);
+    [offset - 360.0, offset]
+        .into_iter()
+        .filter_map(|shift| {
+            let low = shift.max(0.0);
+            let high = (shift + span2).min(span1);
+            (high - low > -eps).then_some((from1 + low, from1 + high.max(low)))
+        })
+        .collect()
+}
 
-        let angle1 = (py - c1.y).atan2(px - c1.x).to_degrees();
-        let angle2 = (py - c2.y).atan2(px - c2.x).to_degrees();
+/// Parts of the first arc shared with the second, as counter-clockwise arcs
+///
+/// Arcs are given as counter-clockwise ranges. Returns nothing unless both
+/// lie on the same circle; two arcs can share up to two separate pieces.
+#[allow(clippy::too_many_arguments)]
+pub fn arc_arc_overlap(
+    c1: Point,
+    r1: f64,
+    start1: f64,
+    end1: f64,
+    c2: Point,
+    r2: f64,
+    start2: f64,
+    end2: f64,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    if distance(c1, c2) >= tolerance.distance || (r1 - r2).abs() >= tolerance.distance {
+        return Vec::new();
+    }
+    let angle_eps = tolerance.angle_at_radius(r1);
+    cocircular_overlap(start1, end1, start2, end2, angle_eps)
+        .into_iter()
+        .filter(|(from, to)| to - from > angle_eps)
+        .map(|(from, to)| arc_from_span(c1, r1, from, to))
+        .collect()
+}
 
-        if is_angle_in_arc(angle1, start1, end1) && is_angle_in_arc(angle2, start2, end2) {
+/// Find intersection points between a line segment and an elliptical arc
+///
+/// The arc is given by its counter-clockwise parametric range. The segment is
+/// mapped into the frame where the ellipse is the unit circle, which keeps
+/// straight lines straight, and intersected there like a circle arc.
+pub fn line_ellipse_intersection(
+    line_start: Point,
+    line_end: Point,
+    ellipse: &Ellipse,
+    start_angle: f64,
+    end_angle: f64,
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    let eps = tolerance.distance;
+    // A distance in the unit frame is at most 1/min(radius) times the original
+    let unit_tolerance = Tolerance {
+        distance: eps / ellipse.radius_x.min(ellipse.radius_y),
+        ..tolerance
+    };
+    line_arc_intersection(
+        ellipse.to_unit_frame(line_start),
+        ellipse.to_unit_frame(line_end),
+        Point { x: 0.0, y: 0.0 },
+        1.0,
+        start_angle,
+        end_angle,
+        unit_tolerance,
+    )
+    .into_iter()
+    .map(|pt| {
+        // Endpoints found on the ellipse are reported exactly, as for arcs
+        let pt = ellipse.from_unit_frame(pt);
+        [line_start, line_end]
+            .into_iter()
+            .find(|end| distance(*end, pt) < eps)
+            .unwrap_or(pt)
+    })
+    .collect()
+}
+
+/// Whether two ellipses are the same curve with the same parametric angles
+fn same_ellipse(e1: &Ellipse, e2: &Ellipse, tolerance: Tolerance) -> bool {
+    let rotation = (e1.rotation.degrees() - e2.rotation.degrees()).rem_euclid(360.0);
+    distance(e1.center, e2.center) < tolerance.distance
+        && (e1.radius_x - e2.radius_x).abs() < tolerance.distance
+        && (e1.radius_y - e2.radius_y).abs() < tolerance.distance
+        && rotation.min(360.0 - rotation) < tolerance.angle_at_radius(e1.radius_x.max(e1.radius_y))
+}
+
+/// Find intersection points between two elliptical arcs
+///
+/// Both arcs are given by counter-clockwise parametric ranges. Two ellipses
+/// meet where a quartic vanishes: `f(t) = |U(t)|² - 1`, with `U(t)` the point
+/// of the second ellipse at `t` in the first ellipse's unit frame, becomes
+/// one in `tan(t / 2)`. Its roots are isolated between those of its
+/// derivatives, so close crossings stay apart, and turning points within the
+/// tolerance are kept as touching points. Arcs of the same ellipse meet at
+/// the ends of their shared ranges.
+#[allow(clippy::too_many_arguments)]
+pub fn ellipse_ellipse_intersection(
+    e1: &Ellipse,
+    start1: f64,
+    end1: f64,
+    e2: &Ellipse,
+    start2: f64,
+    end2: f64,
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    let eps = tolerance.distance;
+    let tolerance1 = Tolerance {
+        angle: tolerance.angle_at_radius(e1.radius_x.min(e1.radius_y)),
+        ..tolerance
+    };
+    let tolerance2 = Tolerance {
+        angle: tolerance.angle_at_radius(e2.radius_x.min(e2.radius_y)),
+        ..tolerance
+    };
+    let mut intersections: Vec<Point> = Vec::new();
+    let push = |pt: Point, intersections: &mut Vec<Point>| {
+        if intersections.iter().all(|p| distance(*p, pt) >= eps) {
             intersections.push(pt);
         }
+    };
+
+    if same_ellipse(e1, e2, tolerance) {
+        for (from, to) in cocircular_overlap(start1, end1, start2, end2, tolerance1.angle) {
+            if to - from >= 360.0 - tolerance1.angle {
+                continue; // Whole ellipse shared, no ends
+            }
+            for angle in [from, to] {
+                push(e1.point_at(Angle::from_degrees(angle)), &mut intersections);
+            }
+        }
         return intersections;
     }
 
-    // Two intersection points
-    let a = (r1 * r1 - r2 * r2 + d * d) / (2.0 * d);
-    let h_squared = r1 * r1 - a * a;
+    let on_ellipse = |pt: Point, ellipse: &Ellipse| {
+        let full = PathSegment::EllipticalArc(
+            *ellipse,
+            Angle::ZERO,
+            Angle::FULL_TURN,
+            ArcDirection::CounterClockwise,
+        );
+        distance_to_segment(pt, &full) < eps
+    };
+    let in_range1 =
+        |pt: Point| is_angle_in_arc(e1.parameter_of(pt).degrees(), start1, end1, tolerance1);
+    let in_range2 =
+        |pt: Point| is_angle_in_arc(e2.parameter_of(pt).degrees(), start2, end2, tolerance2);
+
+    // Ends of either arc lying on the other, which sampling can miss when
+    // the arcs only touch there
+    for angle in [start1, end1] {
+        let pt = e1.point_at(Angle::from_degrees(angle));
+        if on_ellipse(pt, e2) && in_range2(pt) {
+            push(pt, &mut intersections);
+        }
+    }
+    for angle in [start2, end2] {
+        let pt = e2.point_at(Angle::from_degrees(angle));
+        if on_ellipse(pt, e1) && in_range1(pt) {
+            push(pt, &mut intersections);
+        }
+    }
+
+    // In the first ellipse's unit frame the second is the affine image
+    // U(t) = a + b cos t + c sin t of the unit circle, so f(t) = |U(t)|² - 1
+    // is a trigonometric polynomial of degree two
+    let a = e1.to_unit_frame(e2.center);
+    let axis = |x: f64, y: f64| {
+        let p = e1.to_unit_frame(e2.from_unit_frame(Point { x, y }));
+        Point {
+            x: p.x - a.x,
+            y: p.y - a.y,
+        }
+    };
+    let (b, c) = (axis(1.0, 0.0), axis(0.0, 1.0));
+    let dot = |p: Point, q: Point| p.x * q.x + p.y * q.y;
+    let constant = dot(a, a) + (dot(b, b) + dot(c, c)) / 2.0 - 1.0;
+    let (cos1, sin1) = (2.0 * dot(a, b), 2.0 * dot(a, c));
+    let (cos2, sin2) = ((dot(b, b) - dot(c, c)) / 2.0, dot(b, c));
+
+    // z = tan(t / 2) turns f into a quartic, solved for |z| <= 1 on the half
+    // turns around 0° and 180°. Within `touching` of zero a point of the
+    // second ellipse is within the tolerance of the first.
+    let touching = 2.0 * eps / e1.radius_x.max(e1.radius_y);
+    for (offset, sign) in [(0.0, 1.0), (180.0, -1.0)] {
+        let (cos1, sin1) = (sign * cos1, sign * sin1);
+        let quartic = [
+            constant + cos1 + cos2,
+            2.0 * sin1 + 4.0 * sin2,
+            2.0 * constant - 6.0 * cos2,
+            2.0 * sin1 - 4.0 * sin2,
+            constant - cos1 + cos2,
+        ];
+        for z in polynomial_roots(&quartic, -1.0, 1.0, touching) {
+            let t = Angle::from_degrees(offset) + Angle::from_radians(2.0 * z.atan());
+            let pt = e2.point_at(t);
+            if on_ellipse(pt, e1) && in_range1(pt) && in_range2(pt) {
+                push(pt, &mut intersections);
+            }
+        }
+    }
+
+    intersections
+}
 
-    if h_squared < -1e-10 {
-        return intersections; // No real intersection
+/// Parts of the first elliptical arc shared with the second, as
+/// counter-clockwise arcs
+///
+/// Like `arc_arc_overlap`, this only finds anything for arcs of the same
+/// ellipse.
+#[allow(clippy::too_many_arguments)]
+pub fn ellipse_ellipse_overlap(
+    e1: &Ellipse,
+    start1: f64,
+    end1: f64,
+    e2: &Ellipse,
+    start2: f64,
+    end2: f64,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    if !same_ellipse(e1, e2, tolerance) {
+        return Vec::new();
     }
+    let angle_eps = tolerance.angle_at_radius(e1.radius_x.min(e1.radius_y));
+    cocircular_overlap(start1, end1, start2, end2, angle_eps)
+        .into_iter()
+        .filter(|(from, to)| to - from > angle_eps)
+        .map(|(from, to)| {
+            PathSegment::EllipticalArc(
+                *e1,
+                Angle::from_degrees(from),
+                Angle::from_degrees(to),
+                ArcDirection::CounterClockwise,
+            )
+        })
+        .collect()
+}
 
-    let h = if h_squared < 0.0 {
-        0.0
-    } else {
-        h_squared.sqrt()
+/// Keep `pt` unless a point within `eps` of it is already listed
+fn push_distinct(intersections: &mut Vec<Point>, pt: Point, eps: f64) {
+    if intersections.iter().all(|p| distance(*p, pt) >= eps) {
+        intersections.push(pt);
+    }
+}
+
+/// Distance from `point` to a cubic Bézier curve
+fn distance_to_cubic(curve: &[Point; 4], point: Point) -> f64 {
+    distance(
+        point,
+        cubic_point(curve, closest_cubic_parameter(curve, point)),
+    )
+}
+
+/// Find intersection points between a line segment and a Bézier curve,
+/// given by its control points as a cubic
+///
+/// The signed distance of the curve from the line is a cubic in `t`, whose
+/// roots on `[0, 1]` are found by sampling and bisection. Ends of either
+/// segment lying on the other are reported exactly.
+pub fn line_bezier_intersection(
+    line_start: Point,
+    line_end: Point,
+    curve: &[Point; 4],
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    let eps = tolerance.distance;
+    let line = PathSegment::Line(line_start, line_end);
+    let mut intersections = Vec::new();
+
+    for end in [line_start, line_end] {
+        if distance_to_cubic(curve, end) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
+    for end in [curve[0], curve[3]] {
+        if distance_to_segment(end, &line) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
+
+    let dx = line_end.x - line_start.x;
+    let dy = line_end.y - line_start.y;
+    let length = dx.hypot(dy);
+    if length < eps {
+        return intersections;
+    }
+    let signed_distance = |t: f64| {
+        let p = cubic_point(curve, t);
+        (dx * (p.y - line_start.y) - dy * (p.x - line_start.x)) / length
     };
+    for t in sampled_roots(signed_distance, 0.0, 1.0, 64) {
+        let pt = cubic_point(curve, t);
+        if signed_distance(t).abs() < eps && distance_to_segment(pt, &line) < eps {
+            push_distinct(&mut intersections, pt, eps);
+        }
+    }
 
-    let px = c1.x + a * (c2.x - c1.x) / d;
-    let py = c1.y + a * (c2.y - c1.y) / d;
+    intersections
+}
 
-    let ix1 = px + h * (c2.y - c1.y) / d;
-    let iy1 = py - h * (c2.x - c1.x) / d;
-    let pt1 = Point { x: ix1, y: iy1 };
+/// Find intersection points between an elliptical (or circular) arc and a
+/// Bézier curve, given by its control points as a cubic
+///
+/// The arc is given by its counter-clockwise parametric range. Mapping the
+/// control points into the ellipse's unit frame maps the curve with them, so
+/// the points are the roots of `|B(t)|² - 1`, found by sampling and
+/// bisection and then checked against the arc's range.
+pub fn ellipse_bezier_intersection(
+    ellipse: &Ellipse,
+    start_angle: f64,
+    end_angle: f64,
+    curve: &[Point; 4],
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    let eps = tolerance.distance;
+    let angle_tolerance = Tolerance {
+        angle: tolerance.angle_at_radius(ellipse.radius_x.min(ellipse.radius_y)),
+        ..tolerance
+    };
+    let arc = PathSegment::EllipticalArc(
+        *ellipse,
+        Angle::from_degrees(start_angle),
+        Angle::from_degrees(end_angle),
+        ArcDirection::CounterClockwise,
+    );
+    let full = PathSegment::EllipticalArc(
+        *ellipse,
+        Angle::ZERO,
+        Angle::FULL_TURN,
+        ArcDirection::CounterClockwise,
+    );
+    let in_range = |pt: Point| {
+        is_angle_in_arc(
+            ellipse.parameter_of(pt).degrees(),
+            start_angle,
+            end_angle,
+            angle_tolerance,
+        )
+    };
+    let mut intersections = Vec::new();
 
-    let angle1_1 = (iy1 - c1.y).atan2(ix1 - c1.x).to_degrees();
-    let angle2_1 = (iy1 - c2.y).atan2(ix1 - c2.x).to_degrees();
+    for end in [curve[0], curve[3]] {
+        if distance_to_segment(end, &arc) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
+    for angle in [start_angle, end_angle] {
+        let end = ellipse.point_at(Angle::from_degrees(angle));
+        if distance_to_cubic(curve, end) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
 
-    if is_angle_in_arc(angle1_1, start1, end1) && is_angle_in_arc(angle2_1, start2, end2) {
-        intersections.push(pt1);
+    let unit = curve.map(|p| ellipse.to_unit_frame(p));
+    let f = |t: f64| {
+        let p = cubic_point(&unit, t);
+        p.x * p.x + p.y * p.y - 1.0
+    };
+    for t in sampled_roots(f, 0.0, 1.0, 64) {
+        let pt = cubic_point(curve, t);
+        if distance_to_segment(pt, &full) < eps && in_range(pt) {
+            push_distinct(&mut intersections, pt, eps);
+        }
     }
 
-    if h > 1e-10 {
-        let ix2 = px - h * (c2.y - c1.y) / d;
-        let iy2 = py + h * (c2.x - c1.x) / d;
-        let pt2 = Point { x: ix2, y: iy2 };
+    intersections
+}
 
-        let angle1_2 = (iy2 - c1.y).atan2(ix2 - c1.x).to_degrees();
-        let angle2_2 = (iy2 - c2.y).atan2(ix2 - c2.x).to_degrees();
+/// Find intersection points between two Bézier curves, given by their
+/// control points as cubics
+///
+/// Candidates come from recursive subdivision (`cubic_cubic_candidates`) and
+/// are refined numerically; those that close to within the tolerance are
+/// kept. Two copies of the same curve only meet at their ends.
+pub fn bezier_bezier_intersection(
+    curve1: &[Point; 4],
+    curve2: &[Point; 4],
+    tolerance: Tolerance,
+) -> Vec<Point> {
+    let eps = tolerance.distance;
+    let mut intersections = Vec::new();
 
-        if is_angle_in_arc(angle1_2, start1, end1) && is_angle_in_arc(angle2_2, start2, end2) {
-            intersections.push(pt2);
+    for end in [curve1[0], curve1[3]] {
+        if distance_to_cubic(curve2, end) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
+    for end in [curve2[0], curve2[3]] {
+        if distance_to_cubic(curve1, end) < eps {
+            push_distinct(&mut intersections, end, eps);
+        }
+    }
+    if same_cubic(curve1, curve2, eps) {
+        return intersections;
+    }
+
+    for guess in cubic_cubic_candidates(curve1, curve2, eps) {
+        let (t1, _, gap) = refine_cubic_intersection(curve1, curve2, guess);
+        if gap < eps {
+            push_distinct(&mut intersections, cubic_point(curve1, t1), eps);
         }
     }
 
     intersections
 }
 
+/// Whether two cubics have the same control points, in either direction
+fn same_cubic(curve1: &[Point; 4], curve2: &[Point; 4], eps: f64) -> bool {
+    let matches = |other: [Point; 4]| {
+        curve1
+            .iter()
+            .zip(other.iter())
+            .all(|(p, q)| distance(*p, *q) < eps)
+    };
+    let mut reversed = *curve2;
+    reversed.reverse();
+    matches(*curve2) || matches(reversed)
+}
+
+/// The first Bézier curve when it runs along all of the second
+///
+/// Only copies of the same curve are detected, traversed either way; curves
+/// that share just part of their length are not.
+pub fn bezier_bezier_overlap(
+    segment1: &PathSegment,
+    curve1: &[Point; 4],
+    curve2: &[Point; 4],
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    if same_cubic(curve1, curve2, tolerance.distance) {
+        vec![*segment1]
+    } else {
+        Vec::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +895,7 @@ mod tests {
             Point { x: 10.0, y: 10.0 },
             Point { x: 0.0, y: 10.0 },
             Point { x: 10.0, y: 0.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1);
         assert!((pts[0].x - 5.0).abs() < 1e-10);
@@ -268,6 +909,7 @@ mod tests {
             Point { x: 10.0, y: 0.0 },
             Point { x: 0.0, y: 5.0 },
             Point { x: 10.0, y: 5.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 0);
     }
@@ -279,6 +921,7 @@ mod tests {
             Point { x: 2.0, y: 2.0 },
             Point { x: 5.0, y: 5.0 },
             Point { x: 10.0, y: 10.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 0);
     }
@@ -292,6 +935,7 @@ mod tests {
             5.0,
             0.0,
             360.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 2);
     }
@@ -307,6 +951,7 @@ mod tests {
             5.0,
             0.0,
             360.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 2);
     }
@@ -322,6 +967,7 @@ mod tests {
             3.0,
             0.0,
             360.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1);
         assert!((pts[0].x - 3.0).abs() < 1e-10);
@@ -339,6 +985,7 @@ mod tests {
             2.0,
             0.0,
             360.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 0);
     }
@@ -353,6 +1000,7 @@ mod tests {
             Point { x: 0.0, y: 0.0 },
             Point { x: 0.0, y: 0.0 },
             Point { x: 5.0, y: 5.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Should find endpoint intersection");
         assert!((pts[0].x - 0.0).abs() < 1e-10);
@@ -367,6 +1015,7 @@ mod tests {
             Point { x: 5.0, y: 5.0 },
             Point { x: 0.0, y: 0.0 },
             Point { x: 0.0, y: 10.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Should find intersection at endpoint");
         assert!((pts[0].x - 0.0).abs() < 1e-10);
@@ -381,6 +1030,7 @@ mod tests {
             Point { x: 5.0, y: 5.0 },
             Point { x: 0.0, y: 5.0 },
             Point { x: 10.0, y: 5.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Should find T-intersection");
         assert!((pts[0].x - 5.0).abs() < 1e-10);
@@ -400,6 +1050,7 @@ mod tests {
             5.0,
             90.0,
             270.0,
+            Tolerance::default(),
         );
         // The line actually intersects at two points: (0, 0) and (5, 5)
         // (0, 0) is at 180° from center (5, 0), which is in [90, 270]
@@ -407,6 +1058,24 @@ mod tests {
         assert_eq!(pts.len(), 2, "Should find both intersections");
     }
 
+    #[test]
+    fn test_line_arc_second_crossing_before_end_on_circle() {
+        // (3, 0) is on the circle around (3, 3) of radius 3 and the line
+        // crosses it again at (1.2, 0.6) on the way there; both directions
+        // must find both points
+        let a = Point { x: 0.0, y: 1.0 };
+        let b = Point { x: 3.0, y: 0.0 };
+        let center = Point { x: 3.0, y: 3.0 };
+        for (start, end) in [(a, b), (b, a)] {
+            let pts =
+                line_arc_intersection(start, end, center, 3.0, 0.0, 360.0, Tolerance::default());
+            assert_eq!(pts.len(), 2);
+            for expected in [b, Point { x: 1.2, y: 0.6 }] {
+                assert!(pts.iter().any(|p| p.distance_to(expected) < 1e-9));
+            }
+        }
+    }
+
     #[test]
     fn test_line_arc_tangent_at_endpoint() {
         // Line tangent to circle at its endpoint
@@ -419,6 +1088,7 @@ mod tests {
             5.0,
             0.0,
             180.0, // Top half of circle
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Should find tangent point at endpoint");
         assert!((pts[0].x - 0.0).abs() < 1e-10);
@@ -440,6 +1110,7 @@ mod tests {
             5.0,
             end_angle,
             start_angle,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 2, "Should find both arc endpoints");
     }
@@ -460,6 +1131,7 @@ mod tests {
             5.0,
             180.0,
             270.0,
+            Tolerance::default(),
         );
         // These two circles intersect at (0, 5) and (5, 0)
         // Both points should be on both arcs
@@ -489,6 +1161,7 @@ mod tests {
             5.0,
             120.0,
             240.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 2, "Should find two intersection points");
         // Verify both points are symmetric about x-axis
@@ -519,12 +1192,19 @@ mod tests {
             5.0,
             45.0,
             180.0,
+            Tolerance::default(),
+        );
+        // Same circle, overlapping arcs - the overlap 45°..90° is bounded by
+        // Arc2's start and Arc1's end
+        assert_eq!(pts.len(), 2, "Should find both ends of the overlap");
+        assert!(
+            pts.iter()
+                .any(|p| p.x.abs() < 1e-10 && (p.y - 5.0).abs() < 1e-10)
         );
-        // Same circle, overlapping arcs - intersection is the overlapping portion
-        // Since they share the same circle, intersection at endpoints/overlap
+        let diagonal = 5.0 / 2f64.sqrt();
         assert!(
-            pts.len() == 0,
-            "Same circle arcs return empty (infinite overlap)"
+            pts.iter()
+                .any(|p| (p.x - diagonal).abs() < 1e-10 && (p.y - diagonal).abs() < 1e-10)
         );
     }
 
@@ -536,6 +1216,7 @@ mod tests {
             Point { x: 5.0, y: 5.0 },
             Point { x: 0.0, y: 5.0 },
             Point { x: 10.0, y: 5.0 },
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Point on line should be detected");
         assert!((pts[0].x - 5.0).abs() < 1e-10);
@@ -552,6 +1233,7 @@ mod tests {
             5.0,
             0.0,
             360.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 2, "Line through center intersects at two points");
         // Should intersect at (-5, 0) and (5, 0)
@@ -570,6 +1252,7 @@ mod tests {
             5.0,
             89.0, // Very small arc near 90°
             91.0,
+            Tolerance::default(),
         );
         assert_eq!(pts.len(), 1, "Should intersect small arc");
         assert!((pts[0].y - 5.0).abs() < 0.1, "Should be near (0, 5)");
@@ -583,9 +1266,547 @@ mod tests {
             Point { x: 10.0, y: 0.0 },
             Point { x: 5.0, y: 0.0 },
             Point { x: 15.0, y: 0.0 },
+            Tolerance::default(),
+        );
+        // The overlap 5..10 is reported by its two ends
+        assert_eq!(
+            pts,
+            vec![Point { x: 5.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }],
+            "Collinear segments intersect at the ends of their overlap"
+        );
+    }
+
+    #[test]
+    fn test_line_arc_near_tangent_uses_tolerance() {
+        // The line misses the circle by 1e-6
+        let line_start = Point {
+            x: -10.0,
+            y: 5.0 + 1e-6,
+        };
+        let line_end = Point {
+            x: 10.0,
+            y: 5.0 + 1e-6,
+        };
+        let center = Point { x: 0.0, y: 0.0 };
+
+        let strict = line_arc_intersection(
+            line_start,
+            line_end,
+            center,
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert!(strict.is_empty());
+
+        let loose = line_arc_intersection(
+            line_start,
+            line_end,
+            center,
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::new(1e-5, 1e-6),
+        );
+        assert_eq!(loose.len(), 1);
+        assert!(loose[0].x.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_arc_intersection_at_kilometre_scale() {
+        let pts = arc_arc_intersection(
+            Point { x: 0.0, y: 0.0 },
+            5e6,
+            0.0,
+            360.0,
+            Point { x: 6e6, y: 0.0 },
+            5e6,
+            0.0,
+            360.0,
+            Tolerance::for_scale(1e7),
+        );
+        assert_eq!(pts.len(), 2);
+        assert!((pts[0].x - 3e6).abs() < 1e-3);
+        assert!((pts[0].y.abs() - 4e6).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_line_line_intersection_symmetric() {
+        // Nearly parallel segments crossing at a shallow angle, and segments
+        // whose endpoints sit almost exactly on the other line
+        let cases = [
+            (
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 1e-17 },
+                Point { x: 0.0, y: 1e-18 },
+                Point { x: 1.0, y: 0.0 },
+            ),
+            (
+                Point { x: 0.1, y: 0.1 },
+                Point { x: 0.7, y: 0.7 },
+                Point {
+                    x: 0.3,
+                    y: 0.3 + 1e-17,
+                },
+                Point { x: 0.3, y: 5.0 },
+            ),
+            (
+                Point {
+                    x: 1e8,
+                    y: 1e8 + 1.0,
+                },
+                Point { x: -1e8, y: -1e8 },
+                Point { x: -3.0, y: 3.0 },
+                Point {
+                    x: 3.0,
+                    y: -2.999_999_999_999,
+                },
+            ),
+        ];
+        for (a, b, c, d) in cases {
+            let expected = line_line_intersection(a, b, c, d, Tolerance::default()).len();
+            for (p1, p2, p3, p4) in [(c, d, a, b), (b, a, c, d), (a, b, d, c), (d, c, b, a)] {
+                assert_eq!(
+                    line_line_intersection(p1, p2, p3, p4, Tolerance::default()).len(),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_arc_crossing_count_matches_endpoints() {
+        let center = Point { x: 0.0, y: 0.0 };
+        // From inside to outside: exactly one crossing
+        let pts = line_arc_intersection(
+            Point { x: 0.0, y: 0.0 },
+            Point {
+                x: 5.0 + 1e-12,
+                y: 0.0,
+            },
+            center,
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::new(1e-15, 1e-9),
+        );
+        assert_eq!(pts.len(), 1);
+
+        // Both endpoints strictly inside: no crossing
+        let pts = line_arc_intersection(
+            Point { x: -4.999, y: 0.0 },
+            Point { x: 4.999, y: 0.0 },
+            center,
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert!(pts.is_empty());
+    }
+
+    #[test]
+    fn test_arc_arc_internally_tangent_smaller_first() {
+        let pts = arc_arc_intersection(
+            Point { x: 2.0, y: 0.0 },
+            3.0,
+            0.0,
+            360.0,
+            Point { x: 0.0, y: 0.0 },
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 1);
+        assert!((pts[0].x - 5.0).abs() < 1e-10);
+        assert!(pts[0].y.abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_line_line_collinear_overlap() {
+        let pts = line_line_intersection(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 12.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 2);
+        assert_eq!(pts[0], Point { x: 4.0, y: 0.0 });
+        assert_eq!(pts[1], Point { x: 10.0, y: 0.0 });
+
+        match line_line_overlap(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 12.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Tolerance::default(),
+        ) {
+            Some(PathSegment::Line(from, to)) => {
+                assert_eq!(from, Point { x: 4.0, y: 0.0 });
+                assert_eq!(to, Point { x: 10.0, y: 0.0 });
+            }
+            other => panic!("Expected overlapping line, got {:?}", other),
+        }
+
+        // Collinear segments meeting end to end touch at one point
+        let pts = line_line_intersection(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 3.0 },
+            Tolerance::default(),
+        );
+        assert_eq!(pts, vec![Point { x: 1.0, y: 1.0 }]);
+        assert!(
+            line_line_overlap(
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 3.0, y: 3.0 },
+                Tolerance::default(),
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_arc_arc_cocircular_overlap() {
+        let center = Point { x: 0.0, y: 0.0 };
+        // Upper half and right half of the same circle share 0°..90°
+        let pts = arc_arc_intersection(
+            center,
+            5.0,
+            0.0,
+            180.0,
+            center,
+            5.0,
+            -90.0,
+            90.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 2);
+        assert!(
+            pts.iter()
+                .any(|p| (p.x - 5.0).abs() < 1e-10 && p.y.abs() < 1e-10)
+        );
+        assert!(
+            pts.iter()
+                .any(|p| p.x.abs() < 1e-10 && (p.y - 5.0).abs() < 1e-10)
         );
-        // Current implementation returns empty for collinear (treated as parallel)
-        // This is acceptable as there are infinite intersection points
-        assert_eq!(pts.len(), 0, "Collinear segments return empty");
+
+        let overlap = arc_arc_overlap(
+            center,
+            5.0,
+            0.0,
+            180.0,
+            center,
+            5.0,
+            -90.0,
+            90.0,
+            Tolerance::default(),
+        );
+        assert_eq!(overlap.len(), 1);
+        match overlap[0] {
+            PathSegment::Arc(_, _, from, to, direction) => {
+                assert!(from.degrees().abs() < 1e-10);
+                assert!((to.degrees() - 90.0).abs() < 1e-10);
+                assert_eq!(direction, ArcDirection::CounterClockwise);
+            }
+            _ => panic!("Expected Arc segment"),
+        }
+
+        // Two 300° arcs share two separate pieces
+        let overlap = arc_arc_overlap(
+            center,
+            5.0,
+            0.0,
+            300.0,
+            center,
+            5.0,
+            180.0,
+            480.0,
+            Tolerance::default(),
+        );
+        assert_eq!(overlap.len(), 2);
+
+        // A full circle shares the whole arc, and meets it at the arc's ends
+        let pts = arc_arc_intersection(
+            center,
+            5.0,
+            0.0,
+            360.0,
+            center,
+            5.0,
+            30.0,
+            60.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 2);
+
+        // Identical full circles share everything and have no ends
+        let pts = arc_arc_intersection(
+            center,
+            5.0,
+            0.0,
+            360.0,
+            center,
+            5.0,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert!(pts.is_empty());
+    }
+
+    #[test]
+    fn test_line_ellipse_intersection() {
+        let ellipse = Ellipse {
+            center: Point { x: 1.0, y: 2.0 },
+            radius_x: 4.0,
+            radius_y: 2.0,
+            rotation: Angle::from_degrees(90.0),
+        };
+        // The major axis is vertical: a horizontal line through the center
+        // crosses at the co-vertices
+        let pts = line_ellipse_intersection(
+            Point { x: -5.0, y: 2.0 },
+            Point { x: 5.0, y: 2.0 },
+            &ellipse,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 2);
+        for pt in &pts {
+            assert!((pt.y - 2.0).abs() < 1e-12);
+            assert!(((pt.x - 1.0).abs() - 2.0).abs() < 1e-12);
+        }
+        // Tangent at the top vertex, at t = 0, and a range that excludes it
+        // Tangent at the top vertex, and a parametric range that excludes it
+        let top = (Point { x: -5.0, y: 6.0 }, Point { x: 5.0, y: 6.0 });
+        let pts =
+            line_ellipse_intersection(top.0, top.1, &ellipse, 0.0, 360.0, Tolerance::default());
+        assert_eq!(pts.len(), 1);
+        assert!(distance(pts[0], Point { x: 1.0, y: 6.0 }) < 1e-6);
+        let pts =
+            line_ellipse_intersection(top.0, top.1, &ellipse, 90.0, 270.0, Tolerance::default());
+        assert!(pts.is_empty());
+    }
+
+    #[test]
+    fn test_ellipse_ellipse_intersection() {
+        let wide = Ellipse {
+            center: Point { x: 0.0, y: 0.0 },
+            radius_x: 3.0,
+            radius_y: 1.0,
+            rotation: Angle::ZERO,
+        };
+        let tall = Ellipse {
+            rotation: Angle::from_degrees(90.0),
+            ..wide
+        };
+        // Crossings where x²/9 + y² = 1 and |x| = |y|
+        let pts = ellipse_ellipse_intersection(
+            &wide,
+            0.0,
+            360.0,
+            &tall,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 4);
+        let expected = 3.0 / 10f64.sqrt();
+        for pt in &pts {
+            assert!((pt.x.abs() - expected).abs() < 1e-9);
+            assert!((pt.y.abs() - expected).abs() < 1e-9);
+        }
+        // Only the first quadrant of the wide one
+        let pts =
+            ellipse_ellipse_intersection(&wide, 0.0, 90.0, &tall, 0.0, 360.0, Tolerance::default());
+        assert_eq!(pts.len(), 1);
+
+        // A circle inscribed along the minor axis touches at the co-vertices
+        let circle = Ellipse::circle(Point { x: 0.0, y: 0.0 }, 1.0);
+        let pts = ellipse_ellipse_intersection(
+            &wide,
+            0.0,
+            360.0,
+            &circle,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 2);
+        for pt in &pts {
+            assert!(pt.x.abs() < 1e-6 && (pt.y.abs() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ellipse_ellipse_near_coincident_crossings() {
+        let wide = Ellipse {
+            center: Point { x: 0.0, y: 0.0 },
+            radius_x: 3.0,
+            radius_y: 1.0,
+            rotation: Angle::from_degrees(0.5),
+        };
+        // Slightly wider than the minor axis, the circle crosses twice close
+        // to each co-vertex: x²/9 + y² = 1 and x² + y² = r² in the frame
+        // of the wide ellipse
+        let radius = 1.0 + 1e-6;
+        let circle = Ellipse::circle(Point { x: 0.0, y: 0.0 }, radius);
+        let pts = ellipse_ellipse_intersection(
+            &wide,
+            0.0,
+            360.0,
+            &circle,
+            0.0,
+            360.0,
+            Tolerance::default(),
+        );
+        assert_eq!(pts.len(), 4);
+        let expected = (9.0 * (radius * radius - 1.0) / 8.0).sqrt();
+        for pt in &pts {
+            let unit = wide.to_unit_frame(*pt);
+            assert!((unit.x.abs() * 3.0 - expected).abs() < 1e-9);
+            assert!((distance(*pt, circle.center) - radius).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ellipse_ellipse_overlap() {
+        let ellipse = Ellipse {
+            center: Point { x: 2.0, y: 1.0 },
+            radius_x: 3.0,
+            radius_y: 2.0,
+            rotation: Angle::from_degrees(45.0),
+        };
+        let tolerance = Tolerance::default();
+        let overlaps =
+            ellipse_ellipse_overlap(&ellipse, 0.0, 180.0, &ellipse, 90.0, 270.0, tolerance);
+        assert_eq!(overlaps.len(), 1);
+        match overlaps[0] {
+            PathSegment::EllipticalArc(_, start, end, ArcDirection::CounterClockwise) => {
+                assert!((start.degrees() - 90.0).abs() < 1e-9);
+                assert!((end.degrees() - 180.0).abs() < 1e-9);
+            }
+            _ => panic!("Expected a counter-clockwise elliptical arc"),
+        }
+        // The same arcs meet at the ends of the shared range
+        let pts =
+            ellipse_ellipse_intersection(&ellipse, 0.0, 180.0, &ellipse, 90.0, 270.0, tolerance);
+        assert_eq!(pts.len(), 2);
+
+        let other = Ellipse {
+            radius_y: 2.5,
+            ..ellipse
+        };
+        assert!(
+            ellipse_ellipse_overlap(&ellipse, 0.0, 360.0, &other, 0.0, 360.0, tolerance).is_empty()
+        );
+    }
+
+    fn arch(x0: f64, height: f64) -> [Point; 4] {
+        // The parabola y = height·u·(2 - u), u = x - x0, as a cubic
+        let quad = PathSegment::QuadBezier(
+            Point { x: x0, y: 0.0 },
+            Point {
+                x: x0 + 1.0,
+                y: 2.0 * height,
+            },
+            Point {
+                x: x0 + 2.0,
+                y: 0.0,
+            },
+        );
+        crate::bezier::cubic_control_points(&quad).unwrap()
+    }
+
+    #[test]
+    fn test_line_bezier_intersection() {
+        let curve = arch(0.0, 1.0);
+        let tolerance = Tolerance::default();
+        // y = 0.75 where x·(2 - x) = 0.75: x = 0.5 and 1.5
+        let pts = line_bezier_intersection(
+            Point { x: -1.0, y: 0.75 },
+            Point { x: 3.0, y: 0.75 },
+            &curve,
+            tolerance,
+        );
+        assert_eq!(pts.len(), 2);
+        for pt in &pts {
+            assert!(((pt.x - 1.0).abs() - 0.5).abs() < 1e-9);
+        }
+
+        // Tangent at the top, and a segment ending on the curve
+        let pts = line_bezier_intersection(
+            Point { x: -1.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+            &curve,
+            tolerance,
+        );
+        assert_eq!(pts.len(), 1);
+        assert!(distance(pts[0], Point { x: 1.0, y: 1.0 }) < 1e-6);
+        let pts = line_bezier_intersection(
+            Point { x: 0.5, y: 5.0 },
+            Point { x: 0.5, y: 0.75 },
+            &curve,
+            tolerance,
+        );
+        assert_eq!(pts.len(), 1);
+        assert_eq!(pts[0], Point { x: 0.5, y: 0.75 });
+    }
+
+    #[test]
+    fn test_ellipse_bezier_intersection() {
+        // With u = x - 1, the squared distance from (1, 0) to y = x·(2 - x)
+        // is 1 - u² + u⁴, so a circle of radius 0.9 crosses it where
+        // u² = (1 ± √0.24) / 2, four times
+        let curve = arch(0.0, 1.0);
+        let circle = Ellipse::circle(Point { x: 1.0, y: 0.0 }, 0.9);
+        let tolerance = Tolerance::default();
+        let pts = ellipse_bezier_intersection(&circle, 0.0, 360.0, &curve, tolerance);
+        assert_eq!(pts.len(), 4);
+        for pt in &pts {
+            let u2 = (pt.x - 1.0).powi(2);
+            let expected = [(1.0 - 0.24f64.sqrt()) / 2.0, (1.0 + 0.24f64.sqrt()) / 2.0];
+            assert!(expected.iter().any(|e| (u2 - e).abs() < 1e-9));
+            assert!((distance(*pt, circle.center) - 0.9).abs() < 1e-9);
+        }
+        // Only the right half of the circle
+        let pts = ellipse_bezier_intersection(&circle, -90.0, 90.0, &curve, tolerance);
+        assert_eq!(pts.len(), 2);
+        assert!(pts.iter().all(|pt| pt.x > 1.0));
+    }
+
+    #[test]
+    fn test_bezier_bezier_intersection() {
+        let tolerance = Tolerance::default();
+        // Two arches offset by 1 cross once at x = 1.5 and share no ends
+        let pts = bezier_bezier_intersection(&arch(0.0, 1.0), &arch(1.0, 1.0), tolerance);
+        assert_eq!(pts.len(), 1);
+        assert!(distance(pts[0], Point { x: 1.5, y: 0.75 }) < 1e-9);
+
+        // A flat arch and a tall one cross twice besides sharing both ends
+        let pts = bezier_bezier_intersection(&arch(0.0, 1.0), &arch(0.0, 2.0), tolerance);
+        assert_eq!(pts.len(), 2);
+
+        // An upside-down arch touching the top of another
+        let mut flipped = arch(0.0, 1.0).map(|p| Point {
+            x: p.x,
+            y: 2.0 - p.y,
+        });
+        flipped.reverse();
+        let pts = bezier_bezier_intersection(&arch(0.0, 1.0), &flipped, tolerance);
+        assert_eq!(pts.len(), 1);
+        assert!(distance(pts[0], Point { x: 1.0, y: 1.0 }) < 1e-6);
+
+        // Copies of the same curve meet only at their ends
+        let pts = bezier_bezier_intersection(&arch(0.0, 1.0), &arch(0.0, 1.0), tolerance);
+        assert_eq!(pts.len(), 2);
     }
 }
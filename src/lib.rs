@@ -0,0 +1,17 @@
+pub mod bezier;
+pub mod boolean_ops;
+pub mod broad_phase;
+pub mod fillet;
+pub mod geometry;
+pub mod icon;
+pub mod intersection;
+pub mod minkowski;
+pub mod offset;
+pub mod predicates;
+pub mod resolver;
+pub mod section;
+#[cfg(test)]
+mod test_fixtures;
+pub mod transform;
+pub mod types;
+pub mod viewer;
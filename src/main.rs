@@ -1,13 +1,6 @@
-mod boolean_ops;
-mod geometry;
-mod icon;
-mod intersection;
-mod resolver;
-mod types;
-mod viewer;
-
-use crate::intersection::arc_arc_intersection;
-use crate::types::{PathSegment, Point, ResolvedShape};
+use vepor::intersection::arc_arc_intersection;
+use vepor::types::{Angle, ArcDirection, PathSegment, Point, ResolvedShape, Tolerance};
+use vepor::viewer;
 
 fn main() {
     println!("=== 圆与圆弧交点计算程序 ===\n");
@@ -42,6 +35,7 @@ fn main() {
         circle2_radius,
         arc_start,
         arc_end, // 圆2只显示上半圆弧
+        Tolerance::default(),
     );
 
     println!("交点数量: {}", intersections.len());
@@ -51,14 +45,21 @@ fn main() {
     println!();
 
     // 创建第一个圆的路径段（完整圆）
-    let circle1_segments = vec![PathSegment::Arc(circle1_center, circle1_radius, 0.0, 360.0)];
+    let circle1_segments = vec![PathSegment::Arc(
+        circle1_center,
+        circle1_radius,
+        Angle::ZERO,
+        Angle::FULL_TURN,
+        ArcDirection::CounterClockwise,
+    )];
 
     // 创建第二个圆的路径段（圆弧，不是完整圆）
     let arc_segments = vec![PathSegment::Arc(
         circle2_center,
         circle2_radius,
-        arc_start,
-        arc_end,
+        Angle::from_degrees(arc_start),
+        Angle::from_degrees(arc_end),
+        ArcDirection::CounterClockwise,
     )];
 
     // 创建交点的路径段
@@ -71,16 +72,12 @@ fn main() {
     // 准备可视化的形状列表
     let mut shapes = vec![
         (
-            ResolvedShape {
-                segments: circle1_segments,
-            },
+            ResolvedShape::from_segments(circle1_segments),
             egui::Color32::BLUE,
             "圆1 (完整圆)".to_string(),
         ),
         (
-            ResolvedShape {
-                segments: arc_segments,
-            },
+            ResolvedShape::from_segments(arc_segments),
             egui::Color32::GREEN,
             format!("圆弧 ({}° - {}°)", arc_start, arc_end),
         ),
@@ -89,9 +86,7 @@ fn main() {
     // 如果有交点，添加到可视化中
     if !intersection_segments.is_empty() {
         shapes.push((
-            ResolvedShape {
-                segments: intersection_segments,
-            },
+            ResolvedShape::from_segments(intersection_segments),
             egui::Color32::RED,
             format!("交点 ({}个)", intersections.len()),
         ));
@@ -0,0 +1,664 @@
+use crate::bezier::{cubic_control_points, cubic_point};
+use crate::boolean_ops::union_all;
+use crate::geometry::{
+    arc_span, arc_sweep, get_segment_end_point, get_segment_start_point, get_starting_point,
+    orient_path, point_on_circle, segment_tangent, signed_area_of_path,
+};
+use crate::transform::transform_shape;
+use crate::types::{
+    Affine2, Angle, ArcDirection, Contour, ContourRole, PathSegment, Point, ResolvedShape,
+    Tolerance,
+};
+
+/// Number of lines each elliptical arc or Bézier curve is replaced with
+const CURVE_PIECES: usize = 32;
+
+/// Minkowski sum of two shapes: every point of one moved by every point of
+/// the other
+///
+/// See `minkowski_sum_with_tolerance`.
+pub fn minkowski_sum(shape1: &ResolvedShape, shape2: &ResolvedShape) -> ResolvedShape {
+    minkowski_sum_with_tolerance(shape1, shape2, Tolerance::default())
+}
+
+/// Minkowski sum of two shapes: every point of one moved by every point of
+/// the other
+///
+/// Lines and circular arcs are handled exactly. Elliptical arcs and Bézier
+/// curves, whose sums are not made of either, are first replaced by short
+/// lines.
+///
+/// When both shapes are convex, their boundaries are merged in order of
+/// direction: each piece of the result is a piece of one boundary moved by
+/// the point of the other facing the same way, and arcs facing the same way
+/// combine into one arc with the summed radius.
+///
+/// Otherwise the sum is assembled by a union. Moving a point `x - b` of the
+/// plane from inside `shape1` to outside along a path in `shape2` crosses
+/// the boundary of `shape1`, so the sum is covered by `shape1` moved to one
+/// point of each outer contour of `shape2`, `shape2` moved to one point of
+/// each contour of `shape1`, and the sums of every pair of boundary
+/// segments. Each pair sum is cut where the two segments face the same or
+/// opposite ways, leaving cells bounded exactly by lines and arcs.
+pub fn minkowski_sum_with_tolerance(
+    shape1: &ResolvedShape,
+    shape2: &ResolvedShape,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    if shape1.is_empty() || shape2.is_empty() {
+        return ResolvedShape::default();
+    }
+    let shape1 = lines_for_curves(shape1);
+    let shape2 = lines_for_curves(shape2);
+    if let (Some(turns1), Some(turns2)) = (
+        convex_turns(&shape1, tolerance),
+        convex_turns(&shape2, tolerance),
+    ) {
+        return convex_sum(&turns1, &turns2, tolerance);
+    }
+
+    let anchored = |shape: &ResolvedShape, contour: &Contour| {
+        let anchor = get_starting_point(&contour.segments)?;
+        Some(transform_shape(shape, &Affine2::translation(anchor)))
+    };
+    // shape1 moved into every component of shape2, and shape2 onto every
+    // contour of shape1
+    let mut pieces: Vec<ResolvedShape> = shape2
+        .contours
+        .iter()
+        .filter(|contour| contour.role == ContourRole::Outer)
+        .filter_map(|contour| anchored(&shape1, contour))
+        .collect();
+    pieces.extend(
+        shape1
+            .contours
+            .iter()
+            .filter_map(|contour| anchored(&shape2, contour)),
+    );
+    let segments1 = boundary_segments(&shape1, tolerance);
+    let segments2 = boundary_segments(&shape2, tolerance);
+    for segment1 in &segments1 {
+        for segment2 in &segments2 {
+            pieces.extend(
+                pair_sum(segment1, segment2, tolerance)
+                    .into_iter()
+                    .map(|cell| ResolvedShape {
+                        contours: vec![Contour::outer(cell)],
+                    }),
+            );
+        }
+    }
+    union_all(&pieces, tolerance)
+}
+
+fn add(p: Point, q: Point) -> Point {
+    Point {
+        x: p.x + q.x,
+        y: p.y + q.y,
+    }
+}
+
+/// The same shape with every elliptical arc and Bézier curve replaced by
+/// lines through points along it
+fn lines_for_curves(shape: &ResolvedShape) -> ResolvedShape {
+    let contours = shape
+        .contours
+        .iter()
+        .map(|contour| {
+            let mut segments = Vec::with_capacity(contour.segments.len());
+            for segment in &contour.segments {
+                let point_at: Box<dyn Fn(f64) -> Point> = match segment {
+                    PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                        let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+                        let ellipse = *ellipse;
+                        Box::new(move |t| {
+                            ellipse.point_at(Angle::from_degrees(from + (to - from) * t))
+                        })
+                    }
+                    PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                        let Some(curve) = cubic_control_points(segment) else {
+                            continue;
+                        };
+                        Box::new(move |t| cubic_point(&curve, t))
+                    }
+                    _ => {
+                        segments.push(*segment);
+                        continue;
+                    }
+                };
+                let points: Vec<Point> = (0..=CURVE_PIECES)
+                    .map(|i| point_at(i as f64 / CURVE_PIECES as f64))
+                    .collect();
+                segments.extend(
+                    points
+                        .windows(2)
+                        .map(|pair| PathSegment::Line(pair[0], pair[1])),
+                );
+            }
+            Contour {
+                segments,
+                role: contour.role,
+            }
+        })
+        .collect();
+    ResolvedShape { contours }
+}
+
+/// Lines and arcs of every contour, without degenerate lines
+fn boundary_segments(shape: &ResolvedShape, tolerance: Tolerance) -> Vec<PathSegment> {
+    shape
+        .contours
+        .iter()
+        .flat_map(|contour| contour.segments.iter().copied())
+        .filter(|segment| match segment {
+            PathSegment::Line(start, end) => start.distance_to(*end) > tolerance.distance,
+            PathSegment::Arc(..) | PathSegment::ConnectedArc(..) => true,
+            _ => false,
+        })
+        .collect()
+}
+
+/// Part of a convex boundary over which the direction of travel turns from
+/// `from` to `to` degrees, while the boundary follows a circle
+///
+/// Corners are turns with a zero radius. Straight edges turn nothing and sit
+/// between two turns, where the point of the boundary jumps.
+struct Turn {
+    center: Point,
+    radius: f64,
+    from: f64,
+    to: f64,
+}
+
+/// Outward normal of a counter-clockwise boundary travelling at `direction`
+/// degrees
+fn outward(direction: f64) -> Point {
+    let (sin, cos) = direction.to_radians().sin_cos();
+    Point { x: sin, y: -cos }
+}
+
+fn direction_of(vector: Point) -> f64 {
+    vector.y.atan2(vector.x).to_degrees()
+}
+
+/// Turns of a shape made of a single convex contour, from one direction of
+/// travel round to the same direction a full turn later
+///
+/// Gives `None` for anything else: several contours, clockwise arcs, right
+/// turns at corners, or a boundary winding round more than once.
+fn convex_turns(shape: &ResolvedShape, tolerance: Tolerance) -> Option<Vec<Turn>> {
+    let [contour] = shape.contours.as_slice() else {
+        return None;
+    };
+    if contour.role != ContourRole::Outer {
+        return None;
+    }
+    let segments: Vec<PathSegment> = orient_path(&contour.segments, true)
+        .into_iter()
+        .filter(|segment| match segment {
+            PathSegment::Line(start, end) => start.distance_to(*end) > tolerance.distance,
+            _ => !matches!(segment, PathSegment::ClosePath),
+        })
+        .collect();
+    let ends: Vec<(Point, Point, f64, f64)> = segments
+        .iter()
+        .map(|segment| {
+            let start = get_segment_start_point(segment)?;
+            let end = get_segment_end_point(segment)?;
+            let start_direction = direction_of(segment_tangent(segment, start)?);
+            let end_direction = direction_of(segment_tangent(segment, end)?);
+            Some((start, end, start_direction, end_direction))
+        })
+        .collect::<Option<_>>()?;
+    if ends.is_empty() {
+        return None;
+    }
+
+    let mut turns = Vec::new();
+    let mut direction = ends[0].2;
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            PathSegment::Line(..) => {}
+            PathSegment::Arc(center, radius, start_angle, end_angle, arc_direction)
+            | PathSegment::ConnectedArc(
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                arc_direction,
+                ..,
+            ) => {
+                let sweep = arc_sweep(*start_angle, *end_angle, *arc_direction);
+                if sweep < 0.0 {
+                    return None;
+                }
+                turns.push(Turn {
+                    center: *center,
+                    radius: *radius,
+                    from: direction,
+                    to: direction + sweep,
+                });
+                direction += sweep;
+            }
+            _ => return None,
+        }
+        let (_, corner, _, end_direction) = ends[i];
+        let next_direction = ends[(i + 1) % ends.len()].2;
+        let mut bend = (next_direction - end_direction).rem_euclid(360.0);
+        if bend > 180.0 {
+            bend -= 360.0;
+        }
+        if bend < -tolerance.angle || bend >= 180.0 - tolerance.angle {
+            return None;
+        }
+        turns.push(Turn {
+            center: corner,
+            radius: 0.0,
+            from: direction,
+            to: direction + bend,
+        });
+        direction += bend;
+    }
+    let total = direction - ends[0].2;
+    ((total - 360.0).abs() <= tolerance.angle).then_some(turns)
+}
+
+/// The turn covering `direction`, or failing that, the one ending just
+/// before it
+fn turn_at(turns: &[Turn], direction: f64) -> &Turn {
+    let past_end = |turn: &Turn| (direction - turn.from).rem_euclid(360.0) - (turn.to - turn.from);
+    turns
+        .iter()
+        .find(|turn| past_end(turn) < 0.0)
+        .unwrap_or_else(|| {
+            turns
+                .iter()
+                .min_by(|a, b| past_end(a).total_cmp(&past_end(b)))
+                .expect("a convex boundary has turns")
+        })
+}
+
+/// Merge two convex boundaries by direction
+fn convex_sum(turns1: &[Turn], turns2: &[Turn], tolerance: Tolerance) -> ResolvedShape {
+    let mut breaks: Vec<f64> = turns1
+        .iter()
+        .chain(turns2)
+        .flat_map(|turn| [turn.from, turn.to])
+        .map(|direction| direction.rem_euclid(360.0))
+        .collect();
+    breaks.sort_by(f64::total_cmp);
+    breaks.dedup_by(|b, a| *b - *a <= tolerance.angle);
+    if breaks.len() > 1 && breaks[0] + 360.0 - breaks[breaks.len() - 1] <= tolerance.angle {
+        breaks.pop();
+    }
+
+    // Between two breaks both boundaries follow a single circle, and so
+    // does their sum
+    let circles: Vec<(Point, f64)> = (0..breaks.len())
+        .map(|k| {
+            let next = breaks.get(k + 1).copied().unwrap_or(breaks[0] + 360.0);
+            let middle = (breaks[k] + next) / 2.0;
+            let (turn1, turn2) = (turn_at(turns1, middle), turn_at(turns2, middle));
+            (add(turn1.center, turn2.center), turn1.radius + turn2.radius)
+        })
+        .collect();
+    let point_on = |(center, radius): (Point, f64), direction: f64| {
+        let normal = outward(direction);
+        Point {
+            x: center.x + radius * normal.x,
+            y: center.y + radius * normal.y,
+        }
+    };
+
+    let mut segments = Vec::new();
+    for k in 0..breaks.len() {
+        let (from, to) = (
+            breaks[k],
+            breaks.get(k + 1).copied().unwrap_or(breaks[0] + 360.0),
+        );
+        let (start, end) = (point_on(circles[k], from), point_on(circles[k], to));
+        let (center, radius) = circles[k];
+        if radius > tolerance.distance {
+            segments.push(PathSegment::ConnectedArc(
+                center,
+                radius,
+                Angle::from_degrees(from - 90.0),
+                Angle::from_degrees(to - 90.0),
+                ArcDirection::CounterClockwise,
+                start,
+                end,
+            ));
+        }
+        // Edges of either boundary facing this way
+        let next = point_on(circles[(k + 1) % breaks.len()], to);
+        if end.distance_to(next) > tolerance.distance {
+            segments.push(PathSegment::Line(end, next));
+        }
+    }
+    segments.push(PathSegment::ClosePath);
+    ResolvedShape {
+        contours: vec![Contour::outer(segments)],
+    }
+}
+
+/// A boundary segment as a curve over a parameter: the fraction along a
+/// line, or the angle in degrees round an arc
+#[derive(Copy, Clone)]
+enum Curve {
+    Line(Point, Point),
+    Circle(Point, f64),
+}
+
+impl Curve {
+    /// The curve and its parameter range
+    fn of(segment: &PathSegment) -> Option<(Curve, f64, f64)> {
+        match segment {
+            PathSegment::Line(start, end) => Some((Curve::Line(*start, *end), 0.0, 1.0)),
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+            | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, ..) => {
+                let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+                Some((Curve::Circle(*center, *radius), from.min(to), from.max(to)))
+            }
+            _ => None,
+        }
+    }
+
+    fn point(&self, t: f64) -> Point {
+        match *self {
+            Curve::Line(start, end) => Point {
+                x: start.x + (end.x - start.x) * t,
+                y: start.y + (end.y - start.y) * t,
+            },
+            Curve::Circle(center, radius) => point_on_circle(center, radius, t),
+        }
+    }
+
+    /// The piece from `t0` to `t1` moved by `offset`, with exact ends
+    fn piece(&self, t0: f64, t1: f64, offset: Point, start: Point, end: Point) -> PathSegment {
+        match *self {
+            Curve::Line(..) => PathSegment::Line(start, end),
+            Curve::Circle(center, radius) => PathSegment::ConnectedArc(
+                add(center, offset),
+                radius,
+                Angle::from_degrees(t0),
+                Angle::from_degrees(t1),
+                ArcDirection::of_sweep(t1 - t0),
+                start,
+                end,
+            ),
+        }
+    }
+
+    fn length(&self, t0: f64, t1: f64) -> f64 {
+        match *self {
+            Curve::Line(start, end) => start.distance_to(end) * (t1 - t0).abs(),
+            Curve::Circle(_, radius) => radius * (t1 - t0).to_radians().abs(),
+        }
+    }
+}
+
+/// Parameter plane line `a·s + b·t = m`
+type Fold = (f64, f64, f64);
+
+/// Parameters where two segments face the same or opposite ways, as lines
+/// in the plane of their parameters `(s, t)`
+fn folds(curve1: Curve, range1: (f64, f64), curve2: Curve, range2: (f64, f64)) -> Vec<Fold> {
+    // Angles in (low, high) where a circle faces along a line's direction
+    let facing = |start: Point, end: Point, (low, high): (f64, f64)| {
+        let first = direction_of(Point {
+            x: end.x - start.x,
+            y: end.y - start.y,
+        }) + 90.0;
+        let k_low = ((low - first) / 180.0).floor() as i64;
+        let k_high = ((high - first) / 180.0).ceil() as i64;
+        (k_low..=k_high)
+            .map(move |k| first + 180.0 * k as f64)
+            .filter(move |angle| *angle > low && *angle < high)
+    };
+    match (curve1, curve2) {
+        (Curve::Line(..), Curve::Line(..)) => Vec::new(),
+        (Curve::Line(start, end), Curve::Circle(..)) => facing(start, end, range2)
+            .map(|angle| (0.0, 1.0, angle))
+            .collect(),
+        (Curve::Circle(..), Curve::Line(start, end)) => facing(start, end, range1)
+            .map(|angle| (1.0, 0.0, angle))
+            .collect(),
+        (Curve::Circle(..), Curve::Circle(..)) => {
+            let (low, high) = (range1.0 - range2.1, range1.1 - range2.0);
+            let k_low = (low / 180.0).floor() as i64;
+            let k_high = (high / 180.0).ceil() as i64;
+            (k_low..=k_high)
+                .map(|k| 180.0 * k as f64)
+                .filter(|m| *m > low && *m < high)
+                .map(|m| (1.0, -1.0, m))
+                .collect()
+        }
+    }
+}
+
+/// Split a convex polygon of the parameter plane along a fold line
+fn split_polygon(polygon: &[(f64, f64)], (a, b, m): Fold) -> [Vec<(f64, f64)>; 2] {
+    let side = |(s, t): (f64, f64)| a * s + b * t - m;
+    let mut below = Vec::new();
+    let mut above = Vec::new();
+    for (i, &p) in polygon.iter().enumerate() {
+        let q = polygon[(i + 1) % polygon.len()];
+        let (dp, dq) = (side(p), side(q));
+        if dp <= 0.0 {
+            below.push(p);
+        }
+        if dp >= 0.0 {
+            above.push(p);
+        }
+        if (dp < 0.0 && dq > 0.0) || (dp > 0.0 && dq < 0.0) {
+            let f = dp / (dp - dq);
+            let mut crossing = (p.0 + (q.0 - p.0) * f, p.1 + (q.1 - p.1) * f);
+            // Land exactly on the fold when it is a line of constant parameter
+            if b == 0.0 {
+                crossing.0 = m / a;
+            } else if a == 0.0 {
+                crossing.1 = m / b;
+            }
+            below.push(crossing);
+            above.push(crossing);
+        }
+    }
+    [below, above]
+}
+
+/// Sum of two boundary segments as cells bounded by lines and arcs, each a
+/// counter-clockwise closed path
+///
+/// The sum is the image of the rectangle of both parameters. Cut along the
+/// folds, where the segments face the same or opposite ways, the image of
+/// each cell is bounded by the images of its sides: pieces of one segment
+/// moved to a point of the other, or, along a fold between two arcs, an arc
+/// of the summed or differenced radius round the summed center.
+fn pair_sum(
+    segment1: &PathSegment,
+    segment2: &PathSegment,
+    tolerance: Tolerance,
+) -> Vec<Vec<PathSegment>> {
+    let (Some((curve1, low1, high1)), Some((curve2, low2, high2))) =
+        (Curve::of(segment1), Curve::of(segment2))
+    else {
+        return Vec::new();
+    };
+    let mut cells = vec![vec![
+        (low1, low2),
+        (high1, low2),
+        (high1, high2),
+        (low1, high2),
+    ]];
+    for fold in folds(curve1, (low1, high1), curve2, (low2, high2)) {
+        cells = cells
+            .iter()
+            .flat_map(|cell| split_polygon(cell, fold))
+            .filter(|cell| cell.len() >= 3)
+            .collect();
+    }
+
+    let image = |(s, t): (f64, f64)| add(curve1.point(s), curve2.point(t));
+    let mut paths = Vec::new();
+    for cell in cells {
+        let mut path = Vec::new();
+        for (i, &(s0, t0)) in cell.iter().enumerate() {
+            let (s1, t1) = cell[(i + 1) % cell.len()];
+            let (start, end) = (image((s0, t0)), image((s1, t1)));
+            let (ds, dt) = ((s1 - s0).abs(), (t1 - t0).abs());
+            let piece = if dt <= 1e-12 * (ds + dt) {
+                (curve1.length(s0, s1) > tolerance.distance)
+                    .then(|| curve1.piece(s0, s1, curve2.point(t0), start, end))
+            } else if ds <= 1e-12 * (ds + dt) {
+                (curve2.length(t0, t1) > tolerance.distance)
+                    .then(|| curve2.piece(t0, t1, curve1.point(s0), start, end))
+            } else {
+                fold_piece(curve1, curve2, (s0, t0), s1, start, end, tolerance)
+            };
+            path.extend(piece);
+        }
+        if path.is_empty() {
+            continue;
+        }
+        path.push(PathSegment::ClosePath);
+        if signed_area_of_path(&path).abs() > tolerance.distance * tolerance.distance {
+            paths.push(orient_path(&path, true));
+        }
+    }
+    paths
+}
+
+/// Image of a cell side along a fold between two arcs, where the angles
+/// differ by a multiple of 180°
+fn fold_piece(
+    curve1: Curve,
+    curve2: Curve,
+    (s0, t0): (f64, f64),
+    s1: f64,
+    start: Point,
+    end: Point,
+    tolerance: Tolerance,
+) -> Option<PathSegment> {
+    let (Curve::Circle(center1, radius1), Curve::Circle(center2, radius2)) = (curve1, curve2)
+    else {
+        return None;
+    };
+    let opposite = ((s0 - t0) / 180.0).round().rem_euclid(2.0) != 0.0;
+    let radius = if opposite {
+        radius1 - radius2
+    } else {
+        radius1 + radius2
+    };
+    // A negative radius points the other way round the circle
+    let turn = if radius < 0.0 { 180.0 } else { 0.0 };
+    let radius = radius.abs();
+    (radius * (s1 - s0).to_radians().abs() > tolerance.distance).then(|| {
+        PathSegment::ConnectedArc(
+            add(center1, center2),
+            radius,
+            Angle::from_degrees(s0 + turn),
+            Angle::from_degrees(s1 + turn),
+            ArcDirection::of_sweep(s1 - s0),
+            start,
+            end,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean_ops::compute_area;
+    use crate::geometry::get_shape_bounding_box;
+    use crate::offset::offset;
+    use crate::resolver::resolve_shape;
+    use crate::test_fixtures::{l_shape, rectangle, rectangle_shape};
+    use crate::types::{JoinStyle, Shape};
+    use std::f64::consts::PI;
+
+    fn disc(x: f64, y: f64, radius: f64) -> ResolvedShape {
+        resolve_shape(&Shape::Circle {
+            center: Point { x, y },
+            radius,
+        })
+    }
+
+    #[test]
+    fn test_minkowski_sum_convex() {
+        let square = rectangle(0.0, 0.0, 2.0, 2.0);
+        let rounded = minkowski_sum(&square, &disc(0.0, 0.0, 1.0));
+        assert_eq!(rounded.contours.len(), 1);
+        assert!((compute_area(&rounded) - (4.0 + 8.0 + PI)).abs() < 1e-9);
+
+        let unit = rectangle(1.0, -1.0, 2.0, 0.0);
+        let grown = minkowski_sum(&square, &unit);
+        assert!((compute_area(&grown) - 9.0).abs() < 1e-9);
+        let bbox = get_shape_bounding_box(&grown);
+        assert!(bbox.min.distance_to(Point { x: 1.0, y: -1.0 }) < 1e-9);
+        assert!(bbox.max.distance_to(Point { x: 4.0, y: 2.0 }) < 1e-9);
+
+        let circles = minkowski_sum(&disc(1.0, 2.0, 1.5), &disc(-3.0, 1.0, 0.5));
+        match circles.contours[0].segments[0] {
+            PathSegment::ConnectedArc(center, radius, ..) => {
+                assert!(center.distance_to(Point { x: -2.0, y: 3.0 }) < 1e-9);
+                assert!((radius - 2.0).abs() < 1e-9);
+            }
+            _ => panic!("Expected an arc"),
+        }
+        assert!((compute_area(&circles) - 4.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_minkowski_sum_non_convex_matches_offset() {
+        // With a disc the sum is the round offset, computed independently
+        let bitten = resolve_shape(&Shape::Subtract(
+            Box::new(rectangle_shape(0.0, 0.0, 4.0, 4.0)),
+            Box::new(Shape::Circle {
+                center: Point { x: 2.0, y: 4.0 },
+                radius: 1.0,
+            }),
+        ));
+        let frame = resolve_shape(&Shape::Subtract(
+            Box::new(rectangle_shape(0.0, 0.0, 10.0, 10.0)),
+            Box::new(Shape::Circle {
+                center: Point { x: 5.0, y: 5.0 },
+                radius: 2.0,
+            }),
+        ));
+        for (shape, radius) in [(l_shape(), 0.25), (bitten, 0.25), (frame, 1.0)] {
+            let sum = minkowski_sum(&shape, &disc(0.0, 0.0, radius));
+            let expected = compute_area(&offset(&shape, radius, JoinStyle::Round));
+            assert!((compute_area(&sum) - expected).abs() < 1e-9);
+            // Moving the second operand moves the result
+            let moved = minkowski_sum(&disc(3.0, -1.0, radius), &shape);
+            assert!((compute_area(&moved) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_minkowski_sum_of_concave_arc_and_square() {
+        // A quarter-disc bite out of a corner, grown by a small square,
+        // sweeps the square's far corner along the bite
+        let bitten = resolve_shape(&Shape::Subtract(
+            Box::new(rectangle_shape(0.0, 0.0, 2.0, 2.0)),
+            Box::new(Shape::Circle {
+                center: Point { x: 2.0, y: 2.0 },
+                radius: 1.0,
+            }),
+        ));
+        let sum = minkowski_sum(&bitten, &rectangle(0.0, 0.0, 0.5, 0.5));
+        // 2.5 × 2.5 minus the bite, now a quarter disc around (2.5, 2.5)
+        // moved in by the square's lower-left corner
+        let expected = 2.5 * 2.5 - PI / 4.0;
+        assert!((compute_area(&sum) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_minkowski_sum_of_two_non_convex_shapes() {
+        // Each L is a 2×1 bar and a 1×2 bar, so the sum is the union of the
+        // bars' pairwise sums: 4×2, 3×3 and 2×4 rectangles
+        let sum = minkowski_sum(&l_shape(), &l_shape());
+        assert_eq!(sum.contours.len(), 1);
+        assert!((compute_area(&sum) - 13.0).abs() < 1e-9);
+    }
+}
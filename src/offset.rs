@@ -0,0 +1,582 @@
+use crate::bezier::{cubic_control_points, cubic_point, cubic_tangent};
+use crate::boolean_ops::simplify;
+use crate::geometry::{
+    arc_span, get_segment_end_point, get_segment_start_point, orient_path, reverse_path,
+    segment_tangent, signed_area_of_path,
+};
+use crate::types::{
+    Angle, ArcDirection, CapStyle, Contour, ContourRole, FillRule, JoinStyle, PathSegment, Point,
+    ResolvedShape, Tolerance,
+};
+
+/// Number of lines each elliptical arc or Bézier curve is offset as
+const CURVE_PIECES: usize = 32;
+
+/// Grow a shape by `distance`, or shrink it when `distance` is negative
+///
+/// See `offset_with_tolerance`.
+pub fn offset(shape: &ResolvedShape, distance: f64, join: JoinStyle) -> ResolvedShape {
+    offset_with_tolerance(shape, distance, join, Tolerance::default())
+}
+
+/// Grow a shape by `distance`, or shrink it when `distance` is negative
+///
+/// Every segment is moved `distance` away from the filled side: lines stay
+/// lines and circular arcs stay arcs of the same center, while elliptical
+/// arcs and Bézier curves, whose offsets are not conics, are followed with
+/// short lines. Where neighbouring segments move apart, at convex corners
+/// when growing, the gap is closed with `join`; where they cross, the raw
+/// path is routed back through the original corner. The raw contours are
+/// then simplified with the positive fill rule, which trims the loops left
+/// at concave corners and drops whatever turned inside out.
+pub fn offset_with_tolerance(
+    shape: &ResolvedShape,
+    distance: f64,
+    join: JoinStyle,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    if !distance.is_finite() || distance == 0.0 {
+        return shape.clone();
+    }
+    let contours: Vec<Contour> = shape
+        .contours
+        .iter()
+        .filter_map(|contour| {
+            let oriented = orient_path(&contour.segments, contour.role == ContourRole::Outer);
+            offset_contour(&oriented, distance, join, tolerance)
+        })
+        .collect();
+    simplify(&ResolvedShape { contours }, FillRule::Positive, tolerance)
+}
+
+/// One segment moved sideways, with what the joins to its neighbours need
+struct OffsetPiece {
+    segments: Vec<PathSegment>,
+    start: Point,         // offset start point
+    end: Point,           // offset end point
+    start_corner: Point,  // original start point
+    corner: Point,        // original end point
+    start_tangent: Point, // unit direction of travel at the start
+    end_tangent: Point,   // unit direction of travel at the end
+}
+
+fn unit(vector: Point) -> Option<Point> {
+    let length = vector.x.hypot(vector.y);
+    (length > 0.0).then(|| Point {
+        x: vector.x / length,
+        y: vector.y / length,
+    })
+}
+
+/// The unit normal on the right of a direction of travel, which points away
+/// from the filled side of a correctly oriented contour
+fn right_normal(tangent: Point) -> Point {
+    Point {
+        x: tangent.y,
+        y: -tangent.x,
+    }
+}
+
+fn moved(point: Point, normal: Point, distance: f64) -> Point {
+    Point {
+        x: point.x + distance * normal.x,
+        y: point.y + distance * normal.y,
+    }
+}
+
+/// Lines through `samples + 1` offset points of a curve given by its point
+/// and direction of travel at a parameter running from 0 to 1
+fn sampled_offset(curve: impl Fn(f64) -> (Point, Point), distance: f64) -> Vec<Point> {
+    (0..=CURVE_PIECES)
+        .filter_map(|i| {
+            let (point, tangent) = curve(i as f64 / CURVE_PIECES as f64);
+            Some(moved(point, right_normal(unit(tangent)?), distance))
+        })
+        .collect()
+}
+
+fn polyline(points: &[Point]) -> Vec<PathSegment> {
+    points
+        .windows(2)
+        .map(|pair| PathSegment::Line(pair[0], pair[1]))
+        .collect()
+}
+
+fn offset_segment(
+    segment: &PathSegment,
+    distance: f64,
+    tolerance: Tolerance,
+) -> Option<OffsetPiece> {
+    let start = get_segment_start_point(segment)?;
+    let end = get_segment_end_point(segment)?;
+    if matches!(segment, PathSegment::Line(..)) && start.distance_to(end) <= tolerance.distance {
+        return None;
+    }
+    let start_tangent = unit(segment_tangent(segment, start)?)?;
+    let end_tangent = unit(segment_tangent(segment, end)?)?;
+    let mut piece = OffsetPiece {
+        segments: Vec::new(),
+        start: moved(start, right_normal(start_tangent), distance),
+        end: moved(end, right_normal(end_tangent), distance),
+        start_corner: start,
+        corner: end,
+        start_tangent,
+        end_tangent,
+    };
+
+    match segment {
+        PathSegment::Line(..) => piece
+            .segments
+            .push(PathSegment::Line(piece.start, piece.end)),
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            // The right of a counter-clockwise arc faces away from its center
+            let new_radius = radius + distance * direction.sign();
+            if new_radius > tolerance.distance {
+                piece.segments.push(match segment {
+                    PathSegment::Arc(..) => {
+                        PathSegment::Arc(*center, new_radius, *start_angle, *end_angle, *direction)
+                    }
+                    _ => PathSegment::ConnectedArc(
+                        *center,
+                        new_radius,
+                        *start_angle,
+                        *end_angle,
+                        *direction,
+                        piece.start,
+                        piece.end,
+                    ),
+                });
+            } else if piece.start.distance_to(piece.end) > tolerance.distance {
+                // The arc shrank past its center; keep its ends connected and
+                // let the fill rule drop the inverted part
+                piece
+                    .segments
+                    .push(PathSegment::Line(piece.start, piece.end));
+            }
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+            let points = sampled_offset(
+                |t| {
+                    let angle = Angle::from_degrees(from + (to - from) * t);
+                    let derivative = ellipse.derivative_at(angle);
+                    (
+                        ellipse.point_at(angle),
+                        Point {
+                            x: direction.sign() * derivative.x,
+                            y: direction.sign() * derivative.y,
+                        },
+                    )
+                },
+                distance,
+            );
+            piece.segments = polyline(&points);
+            (piece.start, piece.end) = (*points.first()?, *points.last()?);
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            let curve = cubic_control_points(segment)?;
+            let points = sampled_offset(
+                |t| (cubic_point(&curve, t), cubic_tangent(&curve, t)),
+                distance,
+            );
+            piece.segments = polyline(&points);
+            (piece.start, piece.end) = (*points.first()?, *points.last()?);
+        }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => return None,
+    }
+    Some(piece)
+}
+
+/// Segments taking the offset path from the end of `previous` to the start
+/// of `next`
+fn join_pieces(
+    previous: &OffsetPiece,
+    next: &OffsetPiece,
+    distance: f64,
+    join: JoinStyle,
+    tolerance: Tolerance,
+) -> Vec<PathSegment> {
+    let (from, to, corner) = (previous.end, next.start, previous.corner);
+    if from.distance_to(to) <= tolerance.distance {
+        return Vec::new();
+    }
+    let (t1, t2) = (previous.end_tangent, next.start_tangent);
+    let cross = t1.x * t2.y - t1.y * t2.x;
+    let dot = t1.x * t2.x + t1.y * t2.y;
+    // The offsets move apart when the path turns away from the offset side,
+    // and always when it doubles back on itself
+    let opens = cross * distance > 0.0 || (cross.abs() <= 1e-12 && dot < 0.0);
+    if !opens {
+        return vec![
+            PathSegment::Line(from, corner),
+            PathSegment::Line(corner, to),
+        ];
+    }
+
+    let bevel = vec![PathSegment::Line(from, to)];
+    match join {
+        JoinStyle::Round => {
+            let angle_of =
+                |point: Point| Angle::from_radians((point.y - corner.y).atan2(point.x - corner.x));
+            let direction = if distance > 0.0 {
+                ArcDirection::CounterClockwise
+            } else {
+                ArcDirection::Clockwise
+            };
+            vec![PathSegment::ConnectedArc(
+                corner,
+                distance.abs(),
+                angle_of(from),
+                angle_of(to),
+                direction,
+                from,
+                to,
+            )]
+        }
+        JoinStyle::Miter(limit) => {
+            let (n1, n2) = (right_normal(t1), right_normal(t2));
+            let denominator = 1.0 + n1.x * n2.x + n1.y * n2.y;
+            if denominator <= 1e-12 {
+                return bevel;
+            }
+            let tip = Point {
+                x: corner.x + distance * (n1.x + n2.x) / denominator,
+                y: corner.y + distance * (n1.y + n2.y) / denominator,
+            };
+            if tip.distance_to(corner) > limit * distance.abs() {
+                return bevel;
+            }
+            vec![PathSegment::Line(from, tip), PathSegment::Line(tip, to)]
+        }
+        JoinStyle::Bevel => bevel,
+    }
+}
+
+/// Every segment of a path moved `distance` to its right, skipping
+/// degenerate ones
+fn offset_pieces(
+    segments: &[PathSegment],
+    distance: f64,
+    tolerance: Tolerance,
+) -> Vec<OffsetPiece> {
+    segments
+        .iter()
+        .filter_map(|segment| offset_segment(segment, distance, tolerance))
+        .collect()
+}
+
+/// Offset pieces linked by joins, including the one from the last piece back
+/// to the first when `closed`
+fn joined_path(
+    pieces: &[OffsetPiece],
+    distance: f64,
+    join: JoinStyle,
+    tolerance: Tolerance,
+    closed: bool,
+) -> Vec<PathSegment> {
+    let mut path = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        path.extend(piece.segments.iter().copied());
+        if closed || i + 1 < pieces.len() {
+            let next = &pieces[(i + 1) % pieces.len()];
+            path.extend(join_pieces(piece, next, distance, join, tolerance));
+        }
+    }
+    path
+}
+
+/// Close a raw offset path into a contour
+///
+/// The path may cross itself and even enclose no net area. It is labelled
+/// by its own orientation, so `shape_winding_number` counts its windings as
+/// traced instead of flipping them to suit a role.
+fn raw_contour(mut path: Vec<PathSegment>) -> Option<Contour> {
+    if path.is_empty() {
+        return None;
+    }
+    path.push(PathSegment::ClosePath);
+    Some(if signed_area_of_path(&path) >= 0.0 {
+        Contour::outer(path)
+    } else {
+        Contour::hole(path)
+    })
+}
+
+/// Raw offset of one contour, oriented so the filled side is on its left
+fn offset_contour(
+    segments: &[PathSegment],
+    distance: f64,
+    join: JoinStyle,
+    tolerance: Tolerance,
+) -> Option<Contour> {
+    let pieces = offset_pieces(segments, distance, tolerance);
+    raw_contour(joined_path(&pieces, distance, join, tolerance, true))
+}
+
+/// Region covered by a pen of `width` drawn along every contour of a shape
+///
+/// See `stroke_outline_with_tolerance`.
+pub fn stroke_outline(
+    shape: &ResolvedShape,
+    width: f64,
+    cap: CapStyle,
+    join: JoinStyle,
+) -> ResolvedShape {
+    stroke_outline_with_tolerance(shape, width, cap, join, Tolerance::default())
+}
+
+/// Region covered by a pen of `width` drawn along every contour of a shape
+///
+/// Contours ending in `ClosePath` or whose last point meets their first are
+/// stroked as loops, giving a band between their offsets on either side.
+/// Other contours are open paths: the offsets on both sides are linked into
+/// one outline by a `cap` at each end. Corners are handled as by `offset`,
+/// and the result is a closed shape ready for boolean operations. A width
+/// that is not positive and finite gives an empty shape.
+pub fn stroke_outline_with_tolerance(
+    shape: &ResolvedShape,
+    width: f64,
+    cap: CapStyle,
+    join: JoinStyle,
+    tolerance: Tolerance,
+) -> ResolvedShape {
+    if !width.is_finite() || width <= 0.0 {
+        return ResolvedShape::default();
+    }
+    let half_width = width / 2.0;
+    let mut contours = Vec::new();
+    for contour in &shape.contours {
+        let right = offset_pieces(&contour.segments, half_width, tolerance);
+        let left = offset_pieces(&reverse_path(&contour.segments), half_width, tolerance);
+        let (Some(first), Some(last)) = (right.first(), right.last()) else {
+            continue;
+        };
+        let closed = matches!(contour.segments.last(), Some(PathSegment::ClosePath))
+            || first.start_corner.distance_to(last.corner) <= tolerance.distance;
+        if closed {
+            contours.extend(raw_contour(joined_path(
+                &right, half_width, join, tolerance, true,
+            )));
+            contours.extend(raw_contour(joined_path(
+                &left, half_width, join, tolerance, true,
+            )));
+        } else {
+            let mut path = joined_path(&right, half_width, join, tolerance, false);
+            path.extend(cap_pieces(last, &left[0], half_width, cap));
+            path.extend(joined_path(&left, half_width, join, tolerance, false));
+            path.extend(cap_pieces(&left[left.len() - 1], first, half_width, cap));
+            contours.extend(raw_contour(path));
+        }
+    }
+    simplify(&ResolvedShape { contours }, FillRule::Positive, tolerance)
+}
+
+/// Segments closing off the end of `previous`, leading onto `next`, the
+/// same path traversed backwards
+fn cap_pieces(
+    previous: &OffsetPiece,
+    next: &OffsetPiece,
+    half_width: f64,
+    cap: CapStyle,
+) -> Vec<PathSegment> {
+    let (from, to, end) = (previous.end, next.start, previous.corner);
+    let tangent = previous.end_tangent;
+    match cap {
+        CapStyle::Butt => vec![PathSegment::Line(from, to)],
+        CapStyle::Round => {
+            let angle_of =
+                |point: Point| Angle::from_radians((point.y - end.y).atan2(point.x - end.x));
+            // From the right side around the front to the left side
+            vec![PathSegment::ConnectedArc(
+                end,
+                half_width,
+                angle_of(from),
+                angle_of(to),
+                ArcDirection::CounterClockwise,
+                from,
+                to,
+            )]
+        }
+        CapStyle::Square => {
+            let from_ahead = moved(from, tangent, half_width);
+            let to_ahead = moved(to, tangent, half_width);
+            vec![
+                PathSegment::Line(from, from_ahead),
+                PathSegment::Line(from_ahead, to_ahead),
+                PathSegment::Line(to_ahead, to),
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean_ops::compute_area;
+    use crate::resolver::resolve_shape;
+    use crate::test_fixtures::{l_shape, rectangle, rectangle_shape};
+    use crate::types::Shape;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_offset_square_joins() {
+        let shape = rectangle(0.0, 0.0, 2.0, 2.0);
+        let d: f64 = 0.25;
+        let round = compute_area(&offset(&shape, d, JoinStyle::Round));
+        assert!((round - (4.0 + 8.0 * d + PI * d * d)).abs() < 1e-9);
+        let miter = compute_area(&offset(&shape, d, JoinStyle::Miter(4.0)));
+        assert!((miter - (2.0 + 2.0 * d).powi(2)).abs() < 1e-9);
+        let bevel = compute_area(&offset(&shape, d, JoinStyle::Bevel));
+        assert!((bevel - ((2.0 + 2.0 * d).powi(2) - 2.0 * d * d)).abs() < 1e-9);
+        // A square corner reaches √2 offsets out, past a limit of 1.2
+        let limited = compute_area(&offset(&shape, d, JoinStyle::Miter(1.2)));
+        assert!((limited - bevel).abs() < 1e-9);
+
+        // Shrinking keeps the corners sharp whatever the join
+        for join in [JoinStyle::Round, JoinStyle::Miter(4.0), JoinStyle::Bevel] {
+            let shrunk = offset(&rectangle(0.0, 0.0, 4.0, 4.0), -1.0, join);
+            assert!((compute_area(&shrunk) - 4.0).abs() < 1e-9);
+        }
+        assert!(offset(&shape, -1.5, JoinStyle::Round).is_empty());
+
+        // Distances that are not finite leave the shape alone
+        for d in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!((compute_area(&offset(&shape, d, JoinStyle::Round)) - 4.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_offset_trims_concave_corner() {
+        let d: f64 = 0.25;
+        let grown = offset(&l_shape(), d, JoinStyle::Round);
+        assert_eq!(grown.contours.len(), 1);
+        // Edge strips overlap in a d×d square at the reflex corner, and the
+        // five convex corners each add a quarter disc
+        let expected = 3.0 + 8.0 * d - d * d + 5.0 * PI / 4.0 * d * d;
+        assert!((compute_area(&grown) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_offset_arcs_and_holes() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 2.0,
+        });
+        let shrunk = offset(&circle, -0.5, JoinStyle::Round);
+        assert!((compute_area(&shrunk) - PI * 1.5 * 1.5).abs() < 1e-9);
+        assert!(offset(&circle, -2.5, JoinStyle::Round).is_empty());
+
+        let frame = || {
+            Shape::Subtract(
+                Box::new(rectangle_shape(0.0, 0.0, 10.0, 10.0)),
+                Box::new(Shape::Circle {
+                    center: Point { x: 5.0, y: 5.0 },
+                    radius: 2.0,
+                }),
+            )
+        };
+        let shape = resolve_shape(&frame());
+        // Growing rounds the outer corners and shrinks the hole
+        let grown = offset(&shape, 1.0, JoinStyle::Round);
+        assert!((compute_area(&grown) - 140.0).abs() < 1e-9);
+        // Shrinking pulls the outline in and widens the hole
+        let shrunk = offset(&shape, -1.0, JoinStyle::Miter(4.0));
+        assert!((compute_area(&shrunk) - (64.0 - 9.0 * PI)).abs() < 1e-9);
+        // Until the hole swallows what is left
+        assert!(offset(&shape, -3.0, JoinStyle::Round).is_empty());
+        // A hole that closes up leaves a solid shape
+        let filled = offset(&shape, 2.5, JoinStyle::Miter(4.0));
+        assert!((compute_area(&filled) - 15.0 * 15.0).abs() < 1e-9);
+        assert_eq!(filled.contours.len(), 1);
+    }
+
+    #[test]
+    fn test_offset_curves_follow_within_sampling() {
+        let ellipse = resolve_shape(&Shape::Ellipse {
+            center: Point { x: 0.0, y: 0.0 },
+            radius_x: 3.0,
+            radius_y: 1.0,
+            rotation: Angle::from_degrees(30.0),
+        });
+        let grown = offset(&ellipse, 0.5, JoinStyle::Round);
+        // Steiner's formula: area + perimeter·d + π·d²
+        let perimeter = crate::geometry::elliptical_arc_length(
+            &crate::types::Ellipse {
+                center: Point { x: 0.0, y: 0.0 },
+                radius_x: 3.0,
+                radius_y: 1.0,
+                rotation: Angle::ZERO,
+            },
+            0.0,
+            360.0,
+        );
+        let expected = 3.0 * PI + perimeter * 0.5 + PI * 0.25;
+        assert!((compute_area(&grown) - expected).abs() / expected < 1e-2);
+    }
+
+    fn open_path(points: &[Point]) -> ResolvedShape {
+        ResolvedShape {
+            contours: vec![Contour::outer(
+                points
+                    .windows(2)
+                    .map(|pair| PathSegment::Line(pair[0], pair[1]))
+                    .collect(),
+            )],
+        }
+    }
+
+    #[test]
+    fn test_stroke_open_path_caps() {
+        let line = open_path(&[Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }]);
+        let area = |cap| compute_area(&stroke_outline(&line, 2.0, cap, JoinStyle::Round));
+        assert!((area(CapStyle::Butt) - 8.0).abs() < 1e-9);
+        assert!((area(CapStyle::Square) - 12.0).abs() < 1e-9);
+        assert!((area(CapStyle::Round) - (8.0 + PI)).abs() < 1e-9);
+        for width in [0.0, f64::NAN, f64::INFINITY] {
+            assert!(stroke_outline(&line, width, CapStyle::Butt, JoinStyle::Round).is_empty());
+        }
+
+        // A right-angle bend: two 4×2 strips overlapping in a unit square,
+        // plus the outer corner
+        let bend = open_path(&[
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 4.0 },
+        ]);
+        let mitered = stroke_outline(&bend, 2.0, CapStyle::Butt, JoinStyle::Miter(4.0));
+        assert_eq!(mitered.contours.len(), 1);
+        assert!((compute_area(&mitered) - 16.0).abs() < 1e-9);
+        let rounded = stroke_outline(&bend, 2.0, CapStyle::Butt, JoinStyle::Round);
+        assert!((compute_area(&rounded) - (15.0 + PI / 4.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stroke_closed_path_is_a_band() {
+        let circle = resolve_shape(&Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 3.0,
+        });
+        let ring = stroke_outline(&circle, 1.0, CapStyle::Butt, JoinStyle::Round);
+        assert_eq!(ring.contours.len(), 2);
+        assert!((compute_area(&ring) - 6.0 * PI).abs() < 1e-9);
+
+        let frame = stroke_outline(
+            &rectangle(0.0, 0.0, 4.0, 4.0),
+            1.0,
+            CapStyle::Round,
+            JoinStyle::Miter(4.0),
+        );
+        assert!((compute_area(&frame) - 16.0).abs() < 1e-9);
+
+        // The outline is an ordinary shape in booleans
+        let cut = resolve_shape(&Shape::Subtract(
+            Box::new(Shape::Path(frame)),
+            Box::new(Shape::Rectangle {
+                top_left: Point { x: 2.0, y: -1.0 },
+                bottom_right: Point { x: 5.0, y: 5.0 },
+            }),
+        ));
+        assert!((compute_area(&cut) - 8.0).abs() < 1e-9);
+    }
+}
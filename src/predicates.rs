@@ -0,0 +1,360 @@
+//! Robust geometric predicates
+//!
+//! Each predicate first evaluates its determinant in plain floating point and
+//! returns that value when it is larger than the rounding error bound. Only
+//! near-degenerate inputs fall back to exact evaluation with floating-point
+//! expansions (Shewchuk, "Adaptive Precision Floating-Point Arithmetic and
+//! Fast Robust Geometric Predicates"). The sign of the result is always exact.
+
+use crate::types::Point;
+
+/// Half the machine epsilon, the relative rounding error of one operation
+const EPSILON: f64 = f64::EPSILON / 2.0;
+const ORIENT2D_ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const INCIRCLE_ERROR_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+const CIRCLE_SIDE_ERROR_BOUND: f64 = 8.0 * EPSILON;
+
+/// How two circles are placed relative to each other, decided exactly
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CircleRelation {
+    Separate,          // no common point, neither contains the other
+    ExternallyTangent, // touching from outside at one point
+    Crossing,          // two intersection points
+    InternallyTangent, // touching from inside at one point
+    Nested,            // one circle strictly inside the other
+    Coincident,        // same center and radius
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`
+///
+/// Positive when the points are in counter-clockwise order, negative when
+/// clockwise and zero when they are collinear.
+pub fn orient2d(a: Point, b: Point, c: Point) -> f64 {
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    if det.abs() >= ORIENT2D_ERROR_BOUND * det_sum {
+        return det;
+    }
+
+    let acx = difference(a.x, c.x);
+    let acy = difference(a.y, c.y);
+    let bcx = difference(b.x, c.x);
+    let bcy = difference(b.y, c.y);
+    estimate(&expansion_diff(
+        &expansion_product(&acx, &bcy),
+        &expansion_product(&acy, &bcx),
+    ))
+}
+
+/// Whether `d` lies inside the circle through `a`, `b` and `c`
+///
+/// Positive when `d` is inside and `a`, `b`, `c` are counter-clockwise,
+/// negative when it is outside and zero when the four points are cocircular.
+/// The sign flips when `a`, `b`, `c` are clockwise.
+pub fn incircle(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let adx = a.x - d.x;
+    let ady = a.y - d.y;
+    let bdx = b.x - d.x;
+    let bdy = b.y - d.y;
+    let cdx = c.x - d.x;
+    let cdy = c.y - d.y;
+
+    let bdx_cdy = bdx * cdy;
+    let cdx_bdy = cdx * bdy;
+    let a_lift = adx * adx + ady * ady;
+    let cdx_ady = cdx * ady;
+    let adx_cdy = adx * cdy;
+    let b_lift = bdx * bdx + bdy * bdy;
+    let adx_bdy = adx * bdy;
+    let bdx_ady = bdx * ady;
+    let c_lift = cdx * cdx + cdy * cdy;
+
+    let det =
+        a_lift * (bdx_cdy - cdx_bdy) + b_lift * (cdx_ady - adx_cdy) + c_lift * (adx_bdy - bdx_ady);
+    let permanent = (bdx_cdy.abs() + cdx_bdy.abs()) * a_lift
+        + (cdx_ady.abs() + adx_cdy.abs()) * b_lift
+        + (adx_bdy.abs() + bdx_ady.abs()) * c_lift;
+    if det.abs() > INCIRCLE_ERROR_BOUND * permanent {
+        return det;
+    }
+
+    let adx = difference(a.x, d.x);
+    let ady = difference(a.y, d.y);
+    let bdx = difference(b.x, d.x);
+    let bdy = difference(b.y, d.y);
+    let cdx = difference(c.x, d.x);
+    let cdy = difference(c.y, d.y);
+
+    let a_lift = squared_length(&adx, &ady);
+    let b_lift = squared_length(&bdx, &bdy);
+    let c_lift = squared_length(&cdx, &cdy);
+    let bc = expansion_diff(
+        &expansion_product(&bdx, &cdy),
+        &expansion_product(&cdx, &bdy),
+    );
+    let ca = expansion_diff(
+        &expansion_product(&cdx, &ady),
+        &expansion_product(&adx, &cdy),
+    );
+    let ab = expansion_diff(
+        &expansion_product(&adx, &bdy),
+        &expansion_product(&bdx, &ady),
+    );
+    estimate(&expansion_sum(
+        &expansion_sum(
+            &expansion_product(&a_lift, &bc),
+            &expansion_product(&b_lift, &ca),
+        ),
+        &expansion_product(&c_lift, &ab),
+    ))
+}
+
+/// Side of the circle at `center` with `radius` that `point` lies on
+///
+/// Returns `radius² - |point - center|²`: positive inside, negative outside
+/// and zero exactly on the circle.
+pub fn circle_side(center: Point, radius: f64, point: Point) -> f64 {
+    let dx = point.x - center.x;
+    let dy = point.y - center.y;
+    let squared_distance = dx * dx + dy * dy;
+    let squared_radius = radius * radius;
+    let det = squared_radius - squared_distance;
+    if det.abs() > CIRCLE_SIDE_ERROR_BOUND * (squared_radius + squared_distance) {
+        return det;
+    }
+
+    let r = [radius];
+    estimate(&expansion_diff(
+        &expansion_product(&r, &r),
+        &squared_length(
+            &difference(point.x, center.x),
+            &difference(point.y, center.y),
+        ),
+    ))
+}
+
+/// Exact placement of two circles relative to each other
+pub fn circle_relation(c1: Point, r1: f64, c2: Point, r2: f64) -> CircleRelation {
+    if c1 == c2 {
+        return if r1 == r2 {
+            CircleRelation::Coincident
+        } else {
+            CircleRelation::Nested
+        };
+    }
+
+    let squared_distance = squared_length(&difference(c2.x, c1.x), &difference(c2.y, c1.y));
+    let radius_sum = two_sum(r1, r2);
+    let radius_difference = difference(r1, r2);
+    let outer = sign(&expansion_diff(
+        &expansion_product(&radius_sum, &radius_sum),
+        &squared_distance,
+    ));
+    let inner = sign(&expansion_diff(
+        &squared_distance,
+        &expansion_product(&radius_difference, &radius_difference),
+    ));
+
+    if outer < 0.0 {
+        CircleRelation::Separate
+    } else if outer == 0.0 {
+        CircleRelation::ExternallyTangent
+    } else if inner > 0.0 {
+        CircleRelation::Crossing
+    } else if inner == 0.0 {
+        CircleRelation::InternallyTangent
+    } else {
+        CircleRelation::Nested
+    }
+}
+
+// Expansion arithmetic. An expansion is a sum of non-overlapping floats
+// stored in increasing order of magnitude, without zero components.
+
+/// `a + b` as an exact expansion
+fn two_sum(a: f64, b: f64) -> Vec<f64> {
+    let (sum, error) = two_sum_parts(a, b);
+    [error, sum].into_iter().filter(|&x| x != 0.0).collect()
+}
+
+fn two_sum_parts(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+/// `a - b` as an exact expansion
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    two_sum(a, -b)
+}
+
+/// Add a single float to an expansion
+fn grow_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum_parts(q, component);
+        if error != 0.0 {
+            result.push(error);
+        }
+        q = sum;
+    }
+    if q != 0.0 {
+        result.push(q);
+    }
+    result
+}
+
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, &component| {
+        grow_expansion(&sum, component)
+    })
+}
+
+fn expansion_diff(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let negated: Vec<f64> = f.iter().map(|x| -x).collect();
+    expansion_sum(e, &negated)
+}
+
+/// Multiply an expansion by a single float
+fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut result = Vec::new();
+    for &component in e {
+        let product = component * b;
+        let error = component.mul_add(b, -product);
+        result = grow_expansion(&grow_expansion(&result, error), product);
+    }
+    result
+}
+
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(Vec::new(), |sum, &component| {
+        expansion_sum(&sum, &scale_expansion(e, component))
+    })
+}
+
+fn squared_length(dx: &[f64], dy: &[f64]) -> Vec<f64> {
+    expansion_sum(&expansion_product(dx, dx), &expansion_product(dy, dy))
+}
+
+/// Floating-point approximation with the exact sign of the expansion
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+/// Sign of an expansion, given by its largest component
+fn sign(e: &[f64]) -> f64 {
+    e.last().map_or(0.0, |x| x.signum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d_basic() {
+        let a = Point { x: 0.0, y: 0.0 };
+        let b = Point { x: 1.0, y: 0.0 };
+        assert!(orient2d(a, b, Point { x: 0.0, y: 1.0 }) > 0.0);
+        assert!(orient2d(a, b, Point { x: 0.0, y: -1.0 }) < 0.0);
+        assert_eq!(orient2d(a, b, Point { x: 7.0, y: 0.0 }), 0.0);
+    }
+
+    #[test]
+    fn test_orient2d_near_degenerate() {
+        // Points on the line y = x, and points one ulp off it
+        let a = Point { x: 0.5, y: 0.5 };
+        let b = Point { x: 12.0, y: 12.0 };
+        let c = Point { x: 24.0, y: 24.0 };
+        assert_eq!(orient2d(a, b, c), 0.0);
+
+        let above = Point {
+            x: 0.5,
+            y: f64::from_bits(0.5f64.to_bits() + 1),
+        };
+        assert!(orient2d(b, c, above) > 0.0);
+        let below = Point {
+            x: 0.5,
+            y: f64::from_bits(0.5f64.to_bits() - 1),
+        };
+        assert!(orient2d(b, c, below) < 0.0);
+
+        // Swapping two points flips the sign exactly
+        assert_eq!(
+            orient2d(b, c, above).signum(),
+            -orient2d(c, b, above).signum()
+        );
+    }
+
+    #[test]
+    fn test_incircle() {
+        let a = Point { x: 1.0, y: 0.0 };
+        let b = Point { x: 0.0, y: 1.0 };
+        let c = Point { x: -1.0, y: 0.0 };
+        assert!(incircle(a, b, c, Point { x: 0.0, y: 0.0 }) > 0.0);
+        assert!(incircle(a, b, c, Point { x: 2.0, y: 0.0 }) < 0.0);
+        assert_eq!(incircle(a, b, c, Point { x: 0.0, y: -1.0 }), 0.0);
+        // Clockwise order flips the sign
+        assert!(incircle(c, b, a, Point { x: 0.0, y: 0.0 }) < 0.0);
+
+        let nudged = Point {
+            x: 0.0,
+            y: f64::from_bits(1.0f64.to_bits() + 1),
+        };
+        assert!(incircle(c, Point { x: 0.0, y: -1.0 }, a, nudged) < 0.0);
+    }
+
+    #[test]
+    fn test_circle_side() {
+        let center = Point { x: 0.1, y: 0.2 };
+        assert!(circle_side(center, 1.0, center) > 0.0);
+        assert!(circle_side(center, 1.0, Point { x: 5.0, y: 0.0 }) < 0.0);
+        assert_eq!(
+            circle_side(Point { x: 0.0, y: 0.0 }, 5.0, Point { x: 3.0, y: 4.0 }),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_circle_relation() {
+        let origin = Point { x: 0.0, y: 0.0 };
+        let relation = |x: f64, r2: f64| circle_relation(origin, 5.0, Point { x, y: 0.0 }, r2);
+        assert_eq!(relation(20.0, 5.0), CircleRelation::Separate);
+        assert_eq!(relation(10.0, 5.0), CircleRelation::ExternallyTangent);
+        assert_eq!(relation(6.0, 5.0), CircleRelation::Crossing);
+        assert_eq!(relation(3.0, 2.0), CircleRelation::InternallyTangent);
+        assert_eq!(relation(1.0, 1.0), CircleRelation::Nested);
+        assert_eq!(relation(0.0, 5.0), CircleRelation::Coincident);
+        // The rounded sum of the radii equals the distance, but the exact
+        // sum is slightly smaller
+        assert_eq!(
+            circle_relation(
+                origin,
+                0.1,
+                Point {
+                    x: 0.1 + 0.2,
+                    y: 0.0
+                },
+                0.2
+            ),
+            CircleRelation::Separate
+        );
+    }
+}
@@ -1,11 +1,48 @@
-use crate::boolean_ops::{compute_subtract, compute_union, compute_xor, find_shape_intersections};
-use crate::geometry::get_starting_point;
-use crate::types::{PathSegment, Point, ResolvedShape, Shape};
+use crate::boolean_ops::{
+    compute_xor, find_self_intersections, find_shape_intersections, intersect_all, simplify,
+    subtract_many, union_all,
+};
+use crate::fillet::{chamfer_with_tolerance, fillet_with_tolerance};
+use crate::geometry::orient_path;
+use crate::transform::transform_shape;
+use crate::types::{
+    Affine2, Angle, ArcDirection, Contour, Ellipse, FillRule, PathSegment, Point, ResolvedShape,
+    Shape, Tolerance,
+};
 
 pub fn resolve_shape(shape: &Shape) -> ResolvedShape {
+    resolve_shape_with_tolerance(shape, Tolerance::default())
+}
+
+/// Resolve a shape tree, using `tolerance` for every boolean operation in it
+pub fn resolve_shape_with_tolerance(shape: &Shape, tolerance: Tolerance) -> ResolvedShape {
     match shape {
         Shape::Circle { center, radius } => ResolvedShape {
-            segments: vec![PathSegment::Arc(*center, *radius, 0.0, 360.0)],
+            contours: vec![Contour::outer(vec![PathSegment::Arc(
+                *center,
+                *radius,
+                Angle::ZERO,
+                Angle::FULL_TURN,
+                ArcDirection::CounterClockwise,
+            )])],
+        },
+        Shape::Ellipse {
+            center,
+            radius_x,
+            radius_y,
+            rotation,
+        } => ResolvedShape {
+            contours: vec![Contour::outer(vec![PathSegment::EllipticalArc(
+                Ellipse {
+                    center: *center,
+                    radius_x: *radius_x,
+                    radius_y: *radius_y,
+                    rotation: *rotation,
+                },
+                Angle::ZERO,
+                Angle::FULL_TURN,
+                ArcDirection::CounterClockwise,
+            )])],
         },
         Shape::Rectangle {
             top_left,
@@ -19,75 +56,177 @@ pub fn resolve_shape(shape: &Shape) -> ResolvedShape {
                 x: top_left.x,
                 y: bottom_right.y,
             };
+            let segments = [
+                PathSegment::Line(*top_left, top_right),
+                PathSegment::Line(top_right, *bottom_right),
+                PathSegment::Line(*bottom_right, bottom_left),
+                PathSegment::Line(bottom_left, *top_left),
+                PathSegment::ClosePath,
+            ];
             ResolvedShape {
-                segments: vec![
-                    PathSegment::Line(*top_left, top_right),
-                    PathSegment::Line(top_right, *bottom_right),
-                    PathSegment::Line(*bottom_right, bottom_left),
-                    PathSegment::Line(bottom_left, *top_left),
-                    PathSegment::ClosePath,
-                ],
+                contours: vec![Contour::outer(orient_path(&segments, true))],
             }
         }
-        Shape::Scale(shape, factor) => {
-            let scale_center = get_starting_point(&resolve_shape(shape).segments)
-                .unwrap_or(Point { x: 0.0, y: 0.0 });
-            let mut resolved = resolve_shape(shape);
-            for segment in &mut resolved.segments {
-                match segment {
-                    PathSegment::Line(start, end) => {
-                        start.x = scale_center.x + (*factor) * (start.x - scale_center.x);
-                        start.y = scale_center.y + (*factor) * (start.y - scale_center.y);
-                        end.x = scale_center.x + (*factor) * (end.x - scale_center.x);
-                        end.y = scale_center.y + (*factor) * (end.y - scale_center.y);
-                    }
-                    PathSegment::Arc(center, radius, _, _) => {
-                        center.x = scale_center.x + (*factor) * (center.x - scale_center.x);
-                        center.y = scale_center.y + (*factor) * (center.y - scale_center.y);
-                        *radius *= *factor;
-                    }
-                    PathSegment::ConnectedArc(center, radius, _, _, start_pt, end_pt) => {
-                        center.x = scale_center.x + (*factor) * (center.x - scale_center.x);
-                        center.y = scale_center.y + (*factor) * (center.y - scale_center.y);
-                        *radius *= *factor;
-                        start_pt.x = scale_center.x + (*factor) * (start_pt.x - scale_center.x);
-                        start_pt.y = scale_center.y + (*factor) * (start_pt.y - scale_center.y);
-                        end_pt.x = scale_center.x + (*factor) * (end_pt.x - scale_center.x);
-                        end_pt.y = scale_center.y + (*factor) * (end_pt.y - scale_center.y);
-                    }
-                    PathSegment::ClosePath => {}
-                    PathSegment::DrawPoint(point) => {
-                        point.x = scale_center.x + (*factor) * (point.x - scale_center.x);
-                        point.y = scale_center.y + (*factor) * (point.y - scale_center.y);
-                    }
-                }
-            }
-            resolved
+        Shape::Polygon(points) => resolve_polygon(points, tolerance),
+        Shape::Path(resolved) => resolved.clone(),
+        Shape::Scale(shape, factor, pivot) => transform_shape(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            &Affine2::scaling(*pivot, *factor, *factor),
+        ),
+        Shape::ScaleXY(shape, factor_x, factor_y, pivot) => transform_shape(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            &Affine2::scaling(*pivot, *factor_x, *factor_y),
+        ),
+        Shape::Translate(shape, offset) => transform_shape(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            &Affine2::translation(*offset),
+        ),
+        Shape::Rotate {
+            shape,
+            pivot,
+            angle,
+        } => transform_shape(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            &Affine2::rotation(*pivot, *angle),
+        ),
+        Shape::Mirror(shape, axis) => transform_shape(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            &Affine2::reflection(*axis),
+        ),
+        Shape::Transform(shape, affine) => {
+            transform_shape(&resolve_shape_with_tolerance(shape, tolerance), affine)
         }
-        Shape::Union(shape1, shape2) => {
-            let resolved1 = resolve_shape(shape1);
-            let resolved2 = resolve_shape(shape2);
-            let intersections = find_shape_intersections(&resolved1, &resolved2);
-            compute_union(&resolved1, &resolved2, &intersections)
+        Shape::Fillet {
+            shape,
+            radius,
+            corners,
+        } => fillet_with_tolerance(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            *radius,
+            corners,
+            tolerance,
+        ),
+        Shape::Chamfer {
+            shape,
+            distance,
+            corners,
+        } => chamfer_with_tolerance(
+            &resolve_shape_with_tolerance(shape, tolerance),
+            *distance,
+            corners,
+            tolerance,
+        ),
+        Shape::Union(..) => {
+            let mut operands = Vec::new();
+            collect_union_operands(shape, &mut operands);
+            union_all(&resolve_all(&operands, tolerance), tolerance)
         }
-        Shape::Subtract(shape1, shape2) => {
-            let resolved1 = resolve_shape(shape1);
-            let resolved2 = resolve_shape(shape2);
-            let intersections = find_shape_intersections(&resolved1, &resolved2);
-            compute_subtract(&resolved1, &resolved2, &intersections)
+        Shape::Subtract(..) => {
+            // Subtract(Subtract(a, b), c) and Subtract(a, Union(b, c)) both
+            // cut b and c out of a in one pass
+            let mut base = shape;
+            let mut cutters = Vec::new();
+            while let Shape::Subtract(minuend, subtrahend) = base {
+                collect_union_operands(subtrahend, &mut cutters);
+                base = minuend;
+            }
+            subtract_many(
+                &resolve_shape_with_tolerance(base, tolerance),
+                &resolve_all(&cutters, tolerance),
+                tolerance,
+            )
         }
         Shape::Xor(shape1, shape2) => {
-            let resolved1 = resolve_shape(shape1);
-            let resolved2 = resolve_shape(shape2);
-            let intersections = find_shape_intersections(&resolved1, &resolved2);
-            compute_xor(&resolved1, &resolved2, &intersections)
+            let resolved1 = resolve_shape_with_tolerance(shape1, tolerance);
+            let resolved2 = resolve_shape_with_tolerance(shape2, tolerance);
+            let intersections = find_shape_intersections(&resolved1, &resolved2, tolerance);
+            compute_xor(&resolved1, &resolved2, &intersections, tolerance)
+        }
+        Shape::Intersect(..) => {
+            let mut operands = Vec::new();
+            collect_intersect_operands(shape, &mut operands);
+            intersect_all(&resolve_all(&operands, tolerance), tolerance)
+        }
+    }
+}
+
+/// Close a polygon into a counter-clockwise line path
+///
+/// Repeated vertices, including a last vertex repeating the first, are
+/// dropped. A polygon that crosses itself is simplified with the non-zero
+/// rule, so its boundary is split into simple contours. Fewer than three
+/// distinct vertices give an empty shape.
+fn resolve_polygon(points: &[Point], tolerance: Tolerance) -> ResolvedShape {
+    let mut vertices: Vec<Point> = Vec::with_capacity(points.len());
+    for point in points {
+        if vertices
+            .last()
+            .is_none_or(|last| last.distance_to(*point) > tolerance.distance)
+        {
+            vertices.push(*point);
+        }
+    }
+    while vertices.len() > 1
+        && vertices[0].distance_to(vertices[vertices.len() - 1]) <= tolerance.distance
+    {
+        vertices.pop();
+    }
+    if vertices.len() < 3 {
+        return ResolvedShape::default();
+    }
+
+    let mut segments: Vec<PathSegment> = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(start, end)| PathSegment::Line(*start, *end))
+        .collect();
+    segments.push(PathSegment::ClosePath);
+    let polygon = ResolvedShape {
+        contours: vec![Contour::outer(orient_path(&segments, true))],
+    };
+
+    if find_self_intersections(&polygon, tolerance).is_empty() {
+        polygon
+    } else {
+        simplify(&polygon, FillRule::NonZero, tolerance)
+    }
+}
+
+fn resolve_all(shapes: &[&Shape], tolerance: Tolerance) -> Vec<ResolvedShape> {
+    shapes
+        .iter()
+        .map(|shape| resolve_shape_with_tolerance(shape, tolerance))
+        .collect()
+}
+
+/// Collect the operands of a chain of nested unions, so that unioning many
+/// parts resolves each of them once
+fn collect_union_operands<'a>(shape: &'a Shape, operands: &mut Vec<&'a Shape>) {
+    match shape {
+        Shape::Union(shape1, shape2) => {
+            collect_union_operands(shape1, operands);
+            collect_union_operands(shape2, operands);
+        }
+        _ => operands.push(shape),
+    }
+}
+
+/// Collect the operands of a chain of nested intersections
+fn collect_intersect_operands<'a>(shape: &'a Shape, operands: &mut Vec<&'a Shape>) {
+    match shape {
+        Shape::Intersect(shape1, shape2) => {
+            collect_intersect_operands(shape1, operands);
+            collect_intersect_operands(shape2, operands);
         }
+        _ => operands.push(shape),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::rectangle_shape;
+    use crate::types::CornerSelector;
 
     #[test]
     fn test_resolve_circle() {
@@ -96,19 +235,48 @@ mod tests {
             radius: 5.0,
         };
         let resolved = resolve_shape(&circle);
-        assert_eq!(resolved.segments.len(), 1);
-        match resolved.segments[0] {
-            PathSegment::Arc(center, radius, start, end) => {
+        assert_eq!(resolved.contours.len(), 1);
+        assert_eq!(resolved.contours[0].segments.len(), 1);
+        match resolved.contours[0].segments[0] {
+            PathSegment::Arc(center, radius, start, end, direction) => {
                 assert_eq!(center.x, 10.0);
                 assert_eq!(center.y, 10.0);
                 assert_eq!(radius, 5.0);
-                assert_eq!(start, 0.0);
-                assert_eq!(end, 360.0);
+                assert_eq!(start, Angle::ZERO);
+                assert_eq!(end, Angle::FULL_TURN);
+                assert_eq!(direction, ArcDirection::CounterClockwise);
             }
             _ => panic!("Expected Arc segment"),
         }
     }
 
+    #[test]
+    fn test_resolve_ellipse() {
+        let ellipse = Shape::Ellipse {
+            center: Point { x: 1.0, y: 2.0 },
+            radius_x: 3.0,
+            radius_y: 1.0,
+            rotation: Angle::from_degrees(30.0),
+        };
+        let scaled = Shape::Scale(Box::new(ellipse), 2.0, Point { x: 0.0, y: 0.0 });
+        let resolved = resolve_shape(&scaled);
+        assert_eq!(resolved.contours.len(), 1);
+        match resolved.contours[0].segments[0] {
+            PathSegment::EllipticalArc(ellipse, start, end, direction) => {
+                assert!((ellipse.radius_x - 6.0).abs() < 1e-9);
+                assert!((ellipse.radius_y - 2.0).abs() < 1e-9);
+                assert!(ellipse.center.distance_to(Point { x: 2.0, y: 4.0 }) < 1e-9);
+                let rotation = (ellipse.rotation.degrees() - 30.0).rem_euclid(180.0);
+                assert!(rotation.min(180.0 - rotation) < 1e-9);
+                assert!(((end - start).degrees() - 360.0).abs() < 1e-9);
+                assert_eq!(direction, ArcDirection::CounterClockwise);
+            }
+            _ => panic!("Expected EllipticalArc segment"),
+        }
+        let area = crate::boolean_ops::compute_area(&resolved);
+        assert!((area - std::f64::consts::PI * 12.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_resolve_rectangle() {
         let rectangle = Shape::Rectangle {
@@ -117,7 +285,68 @@ mod tests {
         };
         let resolved = resolve_shape(&rectangle);
         // 4 line segments + 1 ClosePath
-        assert_eq!(resolved.segments.len(), 5);
+        assert_eq!(resolved.contours.len(), 1);
+        assert_eq!(resolved.contours[0].segments.len(), 5);
+    }
+
+    #[test]
+    fn test_resolve_polygon() {
+        // Clockwise L shape with the first vertex repeated at the end
+        let l_shape = Shape::Polygon(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.0, y: 2.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 3.0, y: 0.0 },
+            Point { x: 0.0, y: 0.0 },
+        ]);
+        let resolved = resolve_shape(&l_shape);
+        assert_eq!(resolved.contours.len(), 1);
+        // 6 line segments + 1 ClosePath
+        assert_eq!(resolved.contours[0].segments.len(), 7);
+        assert!(crate::boolean_ops::is_shape_counter_clockwise(&resolved));
+        assert!((crate::boolean_ops::compute_area(&resolved) - 4.0).abs() < 1e-12);
+
+        // A bow tie is split into its two triangles
+        let bow_tie = Shape::Polygon(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        let resolved = resolve_shape(&bow_tie);
+        assert_eq!(resolved.contours.len(), 2);
+        assert!((crate::boolean_ops::compute_area(&resolved) - 2.0).abs() < 1e-9);
+
+        let degenerate = Shape::Polygon(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }]);
+        assert!(resolve_shape(&degenerate).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_path_in_boolean_ops() {
+        // A resolved triangle taken back into the tree and cut by a square
+        let triangle = resolve_shape(&Shape::Polygon(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]));
+        let cut = Shape::Subtract(
+            Box::new(Shape::Path(triangle.clone())),
+            Box::new(rectangle_shape(0.0, 0.0, 1.0, 1.0)),
+        );
+        let resolved = resolve_shape(&cut);
+        assert!((crate::boolean_ops::compute_area(&resolved) - 7.0).abs() < 1e-9);
+
+        let xor = Shape::Xor(
+            Box::new(Shape::Path(triangle)),
+            Box::new(rectangle_shape(0.0, 0.0, 3.0, 3.0)),
+        );
+        // The triangle keeps all of the 3×3 square but a corner of area 2, so
+        // they share 7 and the rest is 8 + 9 - 2·7
+        let resolved = resolve_shape(&xor);
+        assert!((crate::boolean_ops::compute_area(&resolved) - 3.0).abs() < 1e-9);
     }
 
     #[test]
@@ -126,17 +355,108 @@ mod tests {
             center: Point { x: 10.0, y: 10.0 },
             radius: 5.0,
         };
-        let scaled = Shape::Scale(Box::new(circle), 2.0);
+        let scaled = Shape::Scale(Box::new(circle), 2.0, Point { x: 5.0, y: 10.0 });
         let resolved = resolve_shape(&scaled);
 
-        match resolved.segments[0] {
-            PathSegment::Arc(center, radius, _, _) => {
+        match resolved.contours[0].segments[0] {
+            PathSegment::Arc(center, radius, _, _, _) => {
                 assert_eq!(radius, 10.0); // Should be doubled
+                assert_eq!(center, Point { x: 15.0, y: 10.0 }); // Pivot stays put
             }
             _ => panic!("Expected Arc segment"),
         }
     }
 
+    #[test]
+    fn test_resolve_scale_xy() {
+        let circle = || Shape::Circle {
+            center: Point { x: 1.0, y: 1.0 },
+            radius: 2.0,
+        };
+        let pivot = Point { x: 1.0, y: 1.0 };
+        let stretched = resolve_shape(&Shape::ScaleXY(Box::new(circle()), 3.0, 0.5, pivot));
+        match stretched.contours[0].segments[0] {
+            PathSegment::EllipticalArc(ellipse, start, end, direction) => {
+                assert!(ellipse.center.distance_to(pivot) < 1e-12);
+                assert!((ellipse.radius_x - 6.0).abs() < 1e-12);
+                assert!((ellipse.radius_y - 1.0).abs() < 1e-12);
+                assert!(ellipse.rotation.radians().sin().abs() < 1e-12);
+                assert!(((end - start).degrees() - 360.0).abs() < 1e-9);
+                assert_eq!(direction, ArcDirection::CounterClockwise);
+            }
+            _ => panic!("Expected EllipticalArc segment"),
+        }
+        let area = crate::boolean_ops::compute_area(&stretched);
+        assert!((area - std::f64::consts::PI * 6.0).abs() < 1e-9);
+        assert!(crate::boolean_ops::point_inside_shape(
+            Point { x: 6.5, y: 1.0 },
+            &stretched
+        ));
+        assert!(!crate::boolean_ops::point_inside_shape(
+            Point { x: 1.0, y: 2.1 },
+            &stretched
+        ));
+
+        // Cutting the ellipse in half along its minor axis is still exact
+        let half = resolve_shape(&Shape::Intersect(
+            Box::new(Shape::ScaleXY(Box::new(circle()), 3.0, 0.5, pivot)),
+            Box::new(Shape::Rectangle {
+                top_left: Point { x: 1.0, y: -5.0 },
+                bottom_right: Point { x: 10.0, y: 5.0 },
+            }),
+        ));
+        let half_area = crate::boolean_ops::compute_area(&half);
+        assert!((half_area - std::f64::consts::PI * 3.0).abs() < 1e-9);
+
+        // A negative factor mirrors, and the outer boundary stays counter-clockwise
+        let flipped = resolve_shape(&Shape::ScaleXY(Box::new(circle()), -3.0, 0.5, pivot));
+        let signed = crate::geometry::signed_area_of_path(&flipped.contours[0].segments);
+        assert!((signed - std::f64::consts::PI * 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resolve_rotate_and_mirror() {
+        let half_disc = || {
+            Shape::Subtract(
+                Box::new(Shape::Circle {
+                    center: Point { x: 4.0, y: 0.0 },
+                    radius: 2.0,
+                }),
+                Box::new(Shape::Rectangle {
+                    top_left: Point { x: 0.0, y: -3.0 },
+                    bottom_right: Point { x: 8.0, y: 0.0 },
+                }),
+            )
+        };
+        let area = crate::boolean_ops::compute_area(&resolve_shape(&half_disc()));
+
+        let rotated = resolve_shape(&Shape::Rotate {
+            shape: Box::new(half_disc()),
+            pivot: Point { x: 0.0, y: 0.0 },
+            angle: Angle::from_degrees(90.0),
+        });
+        let arc_center = rotated.contours[0]
+            .segments
+            .iter()
+            .find_map(|segment| match segment {
+                PathSegment::Arc(center, ..) | PathSegment::ConnectedArc(center, ..) => {
+                    Some(*center)
+                }
+                _ => None,
+            })
+            .expect("Expected an arc");
+        assert!(arc_center.distance_to(Point { x: 0.0, y: 4.0 }) < 1e-9);
+        assert!((crate::boolean_ops::compute_area(&rotated) - area).abs() < 1e-9);
+
+        // Mirroring keeps the outer boundary counter-clockwise
+        let mirrored = resolve_shape(&Shape::Mirror(
+            Box::new(half_disc()),
+            crate::types::Axis::horizontal(0.0),
+        ));
+        let signed = crate::geometry::signed_area_of_path(&mirrored.contours[0].segments);
+        assert!((signed - area).abs() < 1e-9);
+    }
+
     #[test]
     fn test_resolve_union() {
         let circle = Shape::Circle {
@@ -151,6 +471,99 @@ mod tests {
         let resolved = resolve_shape(&union);
 
         // Should have segments from both shapes
-        assert!(resolved.segments.len() > 0);
+        assert!(!resolved.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_intersect() {
+        let circle = Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5.0,
+        };
+        let rectangle = Shape::Rectangle {
+            top_left: Point { x: 0.0, y: 0.0 },
+            bottom_right: Point { x: 10.0, y: 10.0 },
+        };
+        let intersect = Shape::Intersect(Box::new(circle), Box::new(rectangle));
+        let resolved = resolve_shape(&intersect);
+
+        // Quarter disk in the first quadrant
+        let area = crate::boolean_ops::compute_area(&resolved);
+        assert!((area - std::f64::consts::PI * 25.0 / 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resolve_with_tolerance_at_micrometre_scale() {
+        // Same configuration as `test_resolve_intersect`, a million times smaller
+        let circle = Shape::Circle {
+            center: Point { x: 0.0, y: 0.0 },
+            radius: 5e-6,
+        };
+        let rectangle = Shape::Rectangle {
+            top_left: Point { x: 0.0, y: 0.0 },
+            bottom_right: Point { x: 1e-5, y: 1e-5 },
+        };
+        let intersect = Shape::Intersect(Box::new(circle), Box::new(rectangle));
+        let resolved = resolve_shape_with_tolerance(&intersect, Tolerance::for_scale(1e-5));
+
+        let area = crate::boolean_ops::compute_area(&resolved);
+        let expected = std::f64::consts::PI * 25e-12 / 4.0;
+        assert!((area - expected).abs() < expected * 1e-9);
+    }
+
+    #[test]
+    fn test_resolve_nested_unions_and_subtractions() {
+        // Union(Union(Union(a, b), c), d) of four overlapping squares
+        let union = (1..4).fold(rectangle_shape(0.0, 0.0, 2.0, 2.0), |acc, k| {
+            Shape::Union(
+                Box::new(acc),
+                Box::new(rectangle_shape(k as f64, 0.0, k as f64 + 2.0, 2.0)),
+            )
+        });
+        let resolved = resolve_shape(&union);
+        assert_eq!(resolved.contours.len(), 1);
+        assert!((crate::boolean_ops::compute_area(&resolved) - 10.0).abs() < 1e-9);
+
+        // Subtract(Subtract(base, a), Union(b, c)) cuts three holes
+        let cut = Shape::Subtract(
+            Box::new(Shape::Subtract(
+                Box::new(rectangle_shape(0.0, 0.0, 10.0, 10.0)),
+                Box::new(rectangle_shape(1.0, 1.0, 2.0, 2.0)),
+            )),
+            Box::new(Shape::Union(
+                Box::new(rectangle_shape(4.0, 4.0, 5.0, 5.0)),
+                Box::new(rectangle_shape(7.0, 7.0, 8.0, 8.0)),
+            )),
+        );
+        let resolved = resolve_shape(&cut);
+        assert_eq!(resolved.contours.len(), 4);
+        assert!((crate::boolean_ops::compute_area(&resolved) - 97.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resolve_fillet_and_chamfer() {
+        // L shape with five convex corners and a concave one at (1, 1)
+        let l_shape = || {
+            Box::new(Shape::Subtract(
+                Box::new(rectangle_shape(0.0, 0.0, 2.0, 2.0)),
+                Box::new(rectangle_shape(1.0, 1.0, 2.0, 2.0)),
+            ))
+        };
+        let rounded = resolve_shape(&Shape::Fillet {
+            shape: l_shape(),
+            radius: 0.25,
+            corners: CornerSelector::Concave,
+        });
+        let area = crate::boolean_ops::compute_area(&rounded);
+        let fillet_area = (1.0 - std::f64::consts::PI / 4.0) * 0.0625;
+        assert!((area - (3.0 + fillet_area)).abs() < 1e-9);
+
+        let cut = resolve_shape(&Shape::Chamfer {
+            shape: l_shape(),
+            distance: 0.25,
+            corners: CornerSelector::Convex,
+        });
+        let area = crate::boolean_ops::compute_area(&cut);
+        assert!((area - (3.0 - 5.0 * 0.03125)).abs() < 1e-9);
     }
 }
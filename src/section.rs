@@ -0,0 +1,535 @@
+use crate::bezier::cubic_control_points;
+use crate::geometry::{
+    arc_span, get_segment_end_point, get_segment_start_point, get_shape_bounding_box,
+    signed_area_of_path,
+};
+use crate::types::{Angle, ContourRole, PathSegment, Point, ResolvedShape, SectionProperties};
+
+/// Area, centroid, second moments and principal axes of a shape
+///
+/// The area integrals are turned into integrals along the boundary with
+/// Green's theorem and evaluated in closed form: lines and Bézier curves are
+/// polynomials in their parameter, and circular and elliptical arcs are
+/// polynomials in the cosine and sine of their angle. Holes count
+/// negatively whichever way their contours run, as with `compute_area`.
+/// A shape without area has no centroid and gives `None`.
+pub fn section_properties(shape: &ResolvedShape) -> Option<SectionProperties> {
+    // Integrating relative to a point near the shape keeps the subtraction
+    // of the parallel axis terms from cancelling away the precision
+    let bounds = get_shape_bounding_box(shape);
+    let origin = Point {
+        x: (bounds.min.x + bounds.max.x) / 2.0,
+        y: (bounds.min.y + bounds.max.y) / 2.0,
+    };
+
+    let mut total = Moments::default();
+    for contour in &shape.contours {
+        let moments = path_moments(&contour.segments, origin);
+        let counterclockwise = signed_area_of_path(&contour.segments) >= 0.0;
+        let sign = if counterclockwise == (contour.role == ContourRole::Outer) {
+            1.0
+        } else {
+            -1.0
+        };
+        total = total.plus(&moments.scaled(sign));
+    }
+    let extent = (bounds.max.x - bounds.min.x).max(bounds.max.y - bounds.min.y);
+    if total.area.abs() <= f64::EPSILON * extent * extent {
+        return None;
+    }
+
+    let area = total.area;
+    let cx = total.x / area;
+    let cy = total.y / area;
+    // Parallel axis theorem, back to axes through the centroid
+    let ixx = total.yy - area * cy * cy;
+    let iyy = total.xx - area * cx * cx;
+    let ixy = total.xy - area * cx * cy;
+    let mean = (ixx + iyy) / 2.0;
+    let radius = ((ixx - iyy) / 2.0).hypot(ixy);
+    // The moment about the axis at angle θ is mean + radius·cos(2θ - 2θp)
+    let mut principal_angle = (-ixy).atan2((ixx - iyy) / 2.0).to_degrees() / 2.0;
+    if principal_angle <= -90.0 {
+        principal_angle += 180.0;
+    }
+    Some(SectionProperties {
+        area,
+        centroid: Point {
+            x: origin.x + cx,
+            y: origin.y + cy,
+        },
+        ixx,
+        iyy,
+        ixy,
+        polar: ixx + iyy,
+        i_max: mean + radius,
+        i_min: mean - radius,
+        principal_angle: Angle::from_degrees(principal_angle),
+    })
+}
+
+/// Integrals of 1, x, y, x², y² and xy over the region a path winds around,
+/// counted positively where it runs counter-clockwise
+#[derive(Copy, Clone, Debug, Default)]
+struct Moments {
+    area: f64,
+    x: f64,
+    y: f64,
+    xx: f64,
+    yy: f64,
+    xy: f64,
+}
+
+impl Moments {
+    fn plus(&self, other: &Moments) -> Moments {
+        Moments {
+            area: self.area + other.area,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            xx: self.xx + other.xx,
+            yy: self.yy + other.yy,
+            xy: self.xy + other.xy,
+        }
+    }
+
+    fn scaled(&self, factor: f64) -> Moments {
+        Moments {
+            area: factor * self.area,
+            x: factor * self.x,
+            y: factor * self.y,
+            xx: factor * self.xx,
+            yy: factor * self.yy,
+            xy: factor * self.xy,
+        }
+    }
+}
+
+/// A function of the curve parameter that can be multiplied and integrated
+/// over the parameter range exactly
+trait Series: Sized {
+    fn times(&self, other: &Self) -> Self;
+    fn integral(&self) -> f64;
+}
+
+/// Contribution of a curve `(x, y)` with derivatives `(dx, dy)` to the
+/// moments, from Green's theorem:
+///
+/// ∬ 1 = ½∮ x dy - y dx, ∬ x = ½∮ x² dy, ∬ y = -½∮ y² dx,
+/// ∬ x² = ⅓∮ x³ dy, ∬ y² = -⅓∮ y³ dx, ∬ xy = ½∮ x²y dy
+fn curve_moments<S: Series>(x: &S, y: &S, dx: &S, dy: &S) -> Moments {
+    let xx = x.times(x);
+    let yy = y.times(y);
+    Moments {
+        area: (x.times(dy).integral() - y.times(dx).integral()) / 2.0,
+        x: xx.times(dy).integral() / 2.0,
+        y: -yy.times(dx).integral() / 2.0,
+        xx: xx.times(x).times(dy).integral() / 3.0,
+        yy: -yy.times(y).times(dx).integral() / 3.0,
+        xy: xx.times(y).times(dy).integral() / 2.0,
+    }
+}
+
+/// Polynomial in `t` over `[0, 1]`, lowest power first
+struct Polynomial(Vec<f64>);
+
+impl Polynomial {
+    fn derivative(&self) -> Polynomial {
+        Polynomial(
+            self.0
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(power, coefficient)| power as f64 * coefficient)
+                .collect(),
+        )
+    }
+}
+
+impl Series for Polynomial {
+    fn times(&self, other: &Self) -> Self {
+        if self.0.is_empty() || other.0.is_empty() {
+            return Polynomial(Vec::new());
+        }
+        let mut product = vec![0.0; self.0.len() + other.0.len() - 1];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                product[i + j] += a * b;
+            }
+        }
+        Polynomial(product)
+    }
+
+    fn integral(&self) -> f64 {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(power, coefficient)| coefficient / (power as f64 + 1.0))
+            .sum()
+    }
+}
+
+/// Highest total power of cosine and sine a `TrigPolynomial` holds, enough
+/// for x³ dy on an arc
+const TRIG_DEGREE: usize = 4;
+
+/// Polynomial in `cos θ` and `sin θ` over `θ` from `from` to `to` radians,
+/// with `terms[m][n]` the coefficient of `cos^m θ · sin^n θ`
+struct TrigPolynomial {
+    terms: [[f64; TRIG_DEGREE + 1]; TRIG_DEGREE + 1],
+    from: f64,
+    to: f64,
+}
+
+impl TrigPolynomial {
+    /// `constant + a·cos θ + b·sin θ`
+    fn linear(constant: f64, a: f64, b: f64, from: f64, to: f64) -> TrigPolynomial {
+        let mut terms = [[0.0; TRIG_DEGREE + 1]; TRIG_DEGREE + 1];
+        terms[0][0] = constant;
+        terms[1][0] = a;
+        terms[0][1] = b;
+        TrigPolynomial { terms, from, to }
+    }
+}
+
+/// ∫ cos^m θ · sin^n θ dθ from `from` to `to`, by the usual reduction
+/// formulas down to powers of at most one
+fn trig_monomial_integral(m: usize, n: usize, from: f64, to: f64) -> f64 {
+    let power = |m: usize, n: usize, angle: f64| {
+        let (sin, cos) = angle.sin_cos();
+        cos.powi(m as i32) * sin.powi(n as i32)
+    };
+    let total = (m + n) as f64;
+    if m >= 2 {
+        (power(m - 1, n + 1, to) - power(m - 1, n + 1, from)) / total
+            + (m - 1) as f64 / total * trig_monomial_integral(m - 2, n, from, to)
+    } else if n >= 2 {
+        -(power(m + 1, n - 1, to) - power(m + 1, n - 1, from)) / total
+            + (n - 1) as f64 / total * trig_monomial_integral(m, n - 2, from, to)
+    } else {
+        match (m, n) {
+            (0, 0) => to - from,
+            (1, 0) => to.sin() - from.sin(),
+            (0, 1) => from.cos() - to.cos(),
+            _ => (to.sin().powi(2) - from.sin().powi(2)) / 2.0,
+        }
+    }
+}
+
+impl Series for TrigPolynomial {
+    fn times(&self, other: &Self) -> Self {
+        let mut terms = [[0.0; TRIG_DEGREE + 1]; TRIG_DEGREE + 1];
+        for (m1, row) in self.terms.iter().enumerate() {
+            for (n1, a) in row.iter().enumerate() {
+                if *a == 0.0 {
+                    continue;
+                }
+                for (m2, other_row) in other.terms.iter().enumerate() {
+                    for (n2, b) in other_row.iter().enumerate() {
+                        if *b != 0.0 {
+                            terms[m1 + m2][n1 + n2] += a * b;
+                        }
+                    }
+                }
+            }
+        }
+        TrigPolynomial {
+            terms,
+            from: self.from,
+            to: self.to,
+        }
+    }
+
+    fn integral(&self) -> f64 {
+        let mut total = 0.0;
+        for (m, row) in self.terms.iter().enumerate() {
+            for (n, coefficient) in row.iter().enumerate() {
+                if *coefficient != 0.0 {
+                    total += coefficient * trig_monomial_integral(m, n, self.from, self.to);
+                }
+            }
+        }
+        total
+    }
+}
+
+fn line_moments(start: Point, end: Point) -> Moments {
+    let x = Polynomial(vec![start.x, end.x - start.x]);
+    let y = Polynomial(vec![start.y, end.y - start.y]);
+    curve_moments(&x, &y, &x.derivative(), &y.derivative())
+}
+
+/// Moments of the curve `center + (a·cos θ + b·sin θ, c·cos θ + d·sin θ)`
+/// for `θ` from `from` to `to` degrees, which covers circular and elliptical
+/// arcs alike
+fn conic_moments(center: Point, [a, b, c, d]: [f64; 4], from: f64, to: f64) -> Moments {
+    let (from, to) = (from.to_radians(), to.to_radians());
+    let x = TrigPolynomial::linear(center.x, a, b, from, to);
+    let y = TrigPolynomial::linear(center.y, c, d, from, to);
+    let dx = TrigPolynomial::linear(0.0, b, -a, from, to);
+    let dy = TrigPolynomial::linear(0.0, d, -c, from, to);
+    curve_moments(&x, &y, &dx, &dy)
+}
+
+fn segment_moments(segment: &PathSegment, origin: Point) -> Moments {
+    let relative = |point: Point| Point {
+        x: point.x - origin.x,
+        y: point.y - origin.y,
+    };
+    match segment {
+        PathSegment::Line(start, end) => line_moments(relative(*start), relative(*end)),
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+            conic_moments(relative(*center), [*radius, 0.0, 0.0, *radius], from, to)
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (from, to) = arc_span(*start_angle, *end_angle, *direction);
+            let (sin, cos) = ellipse.rotation.radians().sin_cos();
+            let axes = [
+                ellipse.radius_x * cos,
+                -ellipse.radius_y * sin,
+                ellipse.radius_x * sin,
+                ellipse.radius_y * cos,
+            ];
+            conic_moments(relative(ellipse.center), axes, from, to)
+        }
+        PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+            let Some(curve) = cubic_control_points(segment) else {
+                return Moments::default();
+            };
+            let [p0, p1, p2, p3] = curve.map(relative);
+            let power_basis = |a: f64, b: f64, c: f64, d: f64| {
+                Polynomial(vec![
+                    a,
+                    3.0 * (b - a),
+                    3.0 * (a - 2.0 * b + c),
+                    d - 3.0 * c + 3.0 * b - a,
+                ])
+            };
+            let x = power_basis(p0.x, p1.x, p2.x, p3.x);
+            let y = power_basis(p0.y, p1.y, p2.y, p3.y);
+            curve_moments(&x, &y, &x.derivative(), &y.derivative())
+        }
+        PathSegment::ClosePath | PathSegment::DrawPoint(_) => Moments::default(),
+    }
+}
+
+/// Moments of a path, closing gaps and `ClosePath` with straight lines the
+/// way `signed_area_of_path` does
+fn path_moments(segments: &[PathSegment], origin: Point) -> Moments {
+    let relative = |point: Point| Point {
+        x: point.x - origin.x,
+        y: point.y - origin.y,
+    };
+    let mut total = Moments::default();
+    let mut subpath_start: Option<Point> = None;
+    let mut current_point = Point { x: 0.0, y: 0.0 };
+
+    for segment in segments {
+        if let PathSegment::ClosePath = segment {
+            if let Some(start) = subpath_start.take() {
+                total = total.plus(&line_moments(relative(current_point), relative(start)));
+            }
+            continue;
+        }
+        if let PathSegment::DrawPoint(_) = segment {
+            continue;
+        }
+        let (Some(start_point), Some(end_point)) = (
+            get_segment_start_point(segment),
+            get_segment_end_point(segment),
+        ) else {
+            continue;
+        };
+        if subpath_start.is_none() {
+            subpath_start = Some(start_point);
+            current_point = start_point;
+        }
+        total = total
+            .plus(&line_moments(
+                relative(current_point),
+                relative(start_point),
+            ))
+            .plus(&segment_moments(segment, origin));
+        current_point = end_point;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::resolve_shape;
+    use crate::test_fixtures::{polygon_path, rectangle};
+    use crate::types::{ArcDirection, Contour, Ellipse, Shape};
+    use std::f64::consts::PI;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * (1.0 + b.abs())
+    }
+
+    /// Second moment about the axis through the centroid in `direction`
+    fn moment_about(properties: &SectionProperties, direction: Angle) -> f64 {
+        let (s, c) = direction.radians().sin_cos();
+        properties.ixx * c * c + properties.iyy * s * s - 2.0 * properties.ixy * s * c
+    }
+
+    #[test]
+    fn test_rectangle_and_rotated_rectangle() {
+        // 4 × 2 rectangle away from the origin
+        let shape = rectangle(10.0, -5.0, 14.0, -3.0);
+        let properties = section_properties(&shape).unwrap();
+        assert!(close(properties.area, 8.0));
+        assert!(close(properties.centroid.x, 12.0));
+        assert!(close(properties.centroid.y, -4.0));
+        assert!(close(properties.ixx, 4.0 * 8.0 / 12.0));
+        assert!(close(properties.iyy, 2.0 * 64.0 / 12.0));
+        assert!(properties.ixy.abs() < 1e-9);
+        assert!(close(properties.polar, 8.0 / 3.0 + 32.0 / 3.0));
+        assert!(close(properties.i_max, 32.0 / 3.0));
+        assert!(close(properties.i_min, 8.0 / 3.0));
+        // The largest moment is about the vertical axis
+        assert!(close(properties.principal_angle.degrees().abs(), 90.0));
+
+        // Turning the rectangle turns its principal axes with it
+        let turned = resolve_shape(&Shape::Rotate {
+            shape: Box::new(Shape::Path(shape)),
+            pivot: Point { x: 0.0, y: 0.0 },
+            angle: Angle::from_degrees(30.0),
+        });
+        let turned = section_properties(&turned).unwrap();
+        assert!(close(turned.area, 8.0));
+        assert!(close(turned.i_max, 32.0 / 3.0));
+        assert!(close(turned.i_min, 8.0 / 3.0));
+        assert!(close(turned.principal_angle.degrees(), -60.0));
+        // The angle is the direction of the i_max axis, counter-clockwise
+        // from +x, so the long sides now run across it at 30°
+        assert!(close(
+            moment_about(&turned, turned.principal_angle),
+            turned.i_max
+        ));
+        let across = turned.principal_angle + Angle::from_degrees(90.0);
+        assert!(close(moment_about(&turned, across), turned.i_min));
+        assert!(close(turned.polar, properties.polar));
+    }
+
+    #[test]
+    fn test_disc_with_hole() {
+        // Annulus of radii 3 and 1 around (2, 1); the hole runs the same way
+        // as the outer circle and still counts negatively
+        let center = Point { x: 2.0, y: 1.0 };
+        let circle = |radius| {
+            vec![PathSegment::Arc(
+                center,
+                radius,
+                Angle::ZERO,
+                Angle::FULL_TURN,
+                ArcDirection::CounterClockwise,
+            )]
+        };
+        let annulus = ResolvedShape {
+            contours: vec![Contour::outer(circle(3.0)), Contour::hole(circle(1.0))],
+        };
+        let properties = section_properties(&annulus).unwrap();
+        assert!(close(properties.area, 8.0 * PI));
+        assert!(close(properties.centroid.x, 2.0));
+        assert!(close(properties.centroid.y, 1.0));
+        let i = PI / 4.0 * (81.0 - 1.0);
+        assert!(close(properties.ixx, i));
+        assert!(close(properties.iyy, i));
+        assert!(properties.ixy.abs() < 1e-9);
+        assert!(close(properties.polar, 2.0 * i));
+
+        // A square hole off to one side moves the centroid the other way
+        let holed = ResolvedShape {
+            contours: vec![
+                Contour::outer(polygon_path(&[
+                    (0.0, 0.0),
+                    (4.0, 0.0),
+                    (4.0, 4.0),
+                    (0.0, 4.0),
+                ])),
+                Contour::hole(polygon_path(&[
+                    (2.0, 2.0),
+                    (3.0, 2.0),
+                    (3.0, 3.0),
+                    (2.0, 3.0),
+                ])),
+            ],
+        };
+        let properties = section_properties(&holed).unwrap();
+        assert!(close(properties.area, 15.0));
+        assert!(close(properties.centroid.x, (16.0 * 2.0 - 2.5) / 15.0));
+        let cx = properties.centroid.x;
+        let iyy = 64.0 / 3.0 + 16.0 * (2.0 - cx).powi(2) - (1.0 / 12.0 + (2.5 - cx).powi(2));
+        assert!(close(properties.iyy, iyy));
+        let ixy = 16.0 * (2.0 - cx).powi(2) - (2.5 - cx).powi(2);
+        assert!(close(properties.ixy, ixy));
+
+        assert!(section_properties(&ResolvedShape::default()).is_none());
+    }
+
+    #[test]
+    fn test_half_disc_ellipse_and_curves() {
+        // Half disc of radius 2 over the x axis: centroid at 4r/3π,
+        // Ixx about the centroid (π/8 - 8/9π)·r⁴
+        let half_disc = ResolvedShape::from_segments(vec![
+            PathSegment::Arc(
+                Point { x: 0.0, y: 0.0 },
+                2.0,
+                Angle::ZERO,
+                Angle::from_degrees(180.0),
+                ArcDirection::CounterClockwise,
+            ),
+            PathSegment::ClosePath,
+        ]);
+        let properties = section_properties(&half_disc).unwrap();
+        assert!(close(properties.area, 2.0 * PI));
+        assert!(properties.centroid.x.abs() < 1e-12);
+        assert!(close(properties.centroid.y, 8.0 / (3.0 * PI)));
+        assert!(close(properties.ixx, (PI / 8.0 - 8.0 / (9.0 * PI)) * 16.0));
+        assert!(close(properties.iyy, PI / 8.0 * 16.0));
+
+        // Ellipse with semi-axes 3 and 1 turned by 40°
+        let ellipse = ResolvedShape::from_segments(vec![PathSegment::EllipticalArc(
+            Ellipse {
+                center: Point { x: -1.0, y: 4.0 },
+                radius_x: 3.0,
+                radius_y: 1.0,
+                rotation: Angle::from_degrees(40.0),
+            },
+            Angle::ZERO,
+            Angle::FULL_TURN,
+            ArcDirection::Clockwise,
+        )]);
+        let properties = section_properties(&ellipse).unwrap();
+        assert!(close(properties.area, 3.0 * PI));
+        assert!(close(properties.centroid.x, -1.0));
+        assert!(close(properties.centroid.y, 4.0));
+        assert!(close(properties.i_max, PI / 4.0 * 27.0));
+        assert!(close(properties.i_min, PI / 4.0 * 27.0 / 9.0));
+        assert!(close(properties.principal_angle.degrees(), -50.0));
+        assert!(close(
+            moment_about(&properties, properties.principal_angle),
+            properties.i_max
+        ));
+
+        // The parabola y = x·(2 - x) over [0, 2] closed along the x axis:
+        // area 4/3, centroid (1, 2/5), ∬ x² = 8/5
+        let parabola = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }),
+            PathSegment::QuadBezier(
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 1.0, y: 2.0 },
+                Point { x: 0.0, y: 0.0 },
+            ),
+            PathSegment::ClosePath,
+        ]);
+        let properties = section_properties(&parabola).unwrap();
+        assert!(close(properties.area, 4.0 / 3.0));
+        assert!(close(properties.centroid.x, 1.0));
+        assert!(close(properties.centroid.y, 0.4));
+        assert!(close(properties.iyy, 8.0 / 5.0 - 4.0 / 3.0));
+    }
+}
@@ -0,0 +1,45 @@
+//! Shapes shared by the unit tests of several modules
+
+use crate::resolver::resolve_shape;
+use crate::types::{PathSegment, Point, ResolvedShape, Shape};
+
+/// Closed path through `points` in order
+pub fn polygon_path(points: &[(f64, f64)]) -> Vec<PathSegment> {
+    let points: Vec<Point> = points.iter().map(|&(x, y)| Point { x, y }).collect();
+    let mut segments: Vec<PathSegment> = (0..points.len())
+        .map(|k| PathSegment::Line(points[k], points[(k + 1) % points.len()]))
+        .collect();
+    segments.push(PathSegment::ClosePath);
+    segments
+}
+
+/// Closed polygon through `points` in order
+pub fn polygon(points: &[(f64, f64)]) -> ResolvedShape {
+    ResolvedShape::from_segments(polygon_path(points))
+}
+
+/// Axis-aligned rectangle between the corners `(x0, y0)` and `(x1, y1)`
+pub fn rectangle_shape(x0: f64, y0: f64, x1: f64, y1: f64) -> Shape {
+    Shape::Rectangle {
+        top_left: Point { x: x0, y: y0 },
+        bottom_right: Point { x: x1, y: y1 },
+    }
+}
+
+/// `rectangle_shape`, resolved
+pub fn rectangle(x0: f64, y0: f64, x1: f64, y1: f64) -> ResolvedShape {
+    resolve_shape(&rectangle_shape(x0, y0, x1, y1))
+}
+
+/// Counter-clockwise L shape, a 2×2 square without its upper right quarter,
+/// with five convex corners and a concave one at (1, 1)
+pub fn l_shape() -> ResolvedShape {
+    polygon(&[
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ])
+}
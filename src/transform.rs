@@ -0,0 +1,287 @@
+use crate::geometry::reverse_path;
+use crate::types::{Affine2, Angle, Contour, Ellipse, PathSegment, Point, ResolvedShape};
+
+/// Image of the curve `center + N·(cos t, sin t)` under `affine`, where `N`
+/// is the linear part of `curve`, as an ellipse
+///
+/// Also returns how parametric angles carry over: the point at `t` (degrees)
+/// ends up at `sign·t + offset` on the new ellipse, with `sign` negative
+/// when the map mirrors the curve.
+///
+/// The combined matrix is split as `R(φ)·diag(sx, sy)·R(θ)` with the closed
+/// form 2×2 singular value decomposition; `φ` is the new rotation and `sx`,
+/// `|sy|` the new semi-axes.
+fn map_ellipse(center: Point, curve: &Affine2, affine: &Affine2) -> (Ellipse, f64, f64) {
+    let m = curve.then(&Affine2 {
+        tx: 0.0,
+        ty: 0.0,
+        ..*affine
+    });
+    let e = (m.xx + m.yy) / 2.0;
+    let f = (m.xx - m.yy) / 2.0;
+    let g = (m.yx + m.xy) / 2.0;
+    let h = (m.yx - m.xy) / 2.0;
+    let q = e.hypot(h);
+    let r = f.hypot(g);
+    let (sx, sy) = (q + r, q - r);
+    let a1 = g.atan2(f);
+    let a2 = h.atan2(e);
+    let theta = ((a2 - a1) / 2.0).to_degrees();
+    let phi = ((a2 + a1) / 2.0).to_degrees();
+
+    let ellipse = Ellipse {
+        center: affine.apply(center),
+        radius_x: sx,
+        radius_y: sy.abs(),
+        rotation: Angle::from_degrees(phi),
+    };
+    if sy >= 0.0 {
+        (ellipse, 1.0, theta)
+    } else {
+        // diag(sx, sy) = diag(sx, |sy|)·diag(1, -1), which runs t backwards
+        (ellipse, -1.0, -theta)
+    }
+}
+
+/// The linear map taking the unit circle to a circle of `radius`
+fn circle_matrix(radius: f64) -> Affine2 {
+    Affine2 {
+        xx: radius,
+        yy: radius,
+        ..Affine2::IDENTITY
+    }
+}
+
+/// The linear map taking the unit circle to `ellipse` centred at the origin
+fn ellipse_matrix(ellipse: &Ellipse) -> Affine2 {
+    let (sin, cos) = ellipse.rotation.radians().sin_cos();
+    Affine2 {
+        xx: cos * ellipse.radius_x,
+        xy: -sin * ellipse.radius_y,
+        yx: sin * ellipse.radius_x,
+        yy: cos * ellipse.radius_y,
+        tx: 0.0,
+        ty: 0.0,
+    }
+}
+
+/// Whether an ellipse is a circle, up to rounding
+fn is_circle(ellipse: &Ellipse) -> bool {
+    (ellipse.radius_x - ellipse.radius_y).abs() <= ellipse.radius_x * 1e-12
+}
+
+/// Apply an affine map to a segment
+///
+/// Lines, Bézier curves and points map through their defining points.
+/// Circle arcs stay arcs when the map keeps circles round, with their center
+/// and angles carried along, and become elliptical arcs otherwise. A map
+/// that mirrors reverses the direction of arcs, so each still sweeps across
+/// the image of its original span.
+pub fn transform_segment(segment: &PathSegment, affine: &Affine2) -> PathSegment {
+    match segment {
+        PathSegment::Line(start, end) => {
+            PathSegment::Line(affine.apply(*start), affine.apply(*end))
+        }
+        PathSegment::Arc(center, radius, start_angle, end_angle, direction)
+        | PathSegment::ConnectedArc(center, radius, start_angle, end_angle, direction, _, _) => {
+            let (ellipse, sign, offset) = map_ellipse(*center, &circle_matrix(*radius), affine);
+            let map = |angle: Angle| Angle::from_degrees(sign * angle.degrees() + offset);
+            let direction = if sign < 0.0 {
+                direction.reversed()
+            } else {
+                *direction
+            };
+            if !is_circle(&ellipse) {
+                return PathSegment::EllipticalArc(
+                    ellipse,
+                    map(*start_angle),
+                    map(*end_angle),
+                    direction,
+                );
+            }
+            // On a circle the polar angle is the parametric angle plus the rotation
+            let polar = |angle: Angle| map(angle) + ellipse.rotation;
+            match segment {
+                PathSegment::ConnectedArc(_, _, _, _, _, start_pt, end_pt) => {
+                    PathSegment::ConnectedArc(
+                        ellipse.center,
+                        ellipse.radius_x,
+                        polar(*start_angle),
+                        polar(*end_angle),
+                        direction,
+                        affine.apply(*start_pt),
+                        affine.apply(*end_pt),
+                    )
+                }
+                _ => PathSegment::Arc(
+                    ellipse.center,
+                    ellipse.radius_x,
+                    polar(*start_angle),
+                    polar(*end_angle),
+                    direction,
+                ),
+            }
+        }
+        PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+            let (image, sign, offset) =
+                map_ellipse(ellipse.center, &ellipse_matrix(ellipse), affine);
+            let map = |angle: Angle| Angle::from_degrees(sign * angle.degrees() + offset);
+            let direction = if sign < 0.0 {
+                direction.reversed()
+            } else {
+                *direction
+            };
+            PathSegment::EllipticalArc(image, map(*start_angle), map(*end_angle), direction)
+        }
+        PathSegment::QuadBezier(start, control, end) => PathSegment::QuadBezier(
+            affine.apply(*start),
+            affine.apply(*control),
+            affine.apply(*end),
+        ),
+        PathSegment::CubicBezier(start, control1, control2, end) => PathSegment::CubicBezier(
+            affine.apply(*start),
+            affine.apply(*control1),
+            affine.apply(*control2),
+            affine.apply(*end),
+        ),
+        PathSegment::ClosePath => PathSegment::ClosePath,
+        PathSegment::DrawPoint(point) => PathSegment::DrawPoint(affine.apply(*point)),
+    }
+}
+
+/// Apply an affine map to every contour of a shape
+///
+/// A mirroring map turns counter-clockwise contours clockwise, so contours
+/// are then traversed backwards to keep outer boundaries counter-clockwise
+/// and holes clockwise. A map that flattens the plane onto a line leaves
+/// nothing with any area and gives an empty shape.
+pub fn transform_shape(shape: &ResolvedShape, affine: &Affine2) -> ResolvedShape {
+    let determinant = affine.determinant();
+    let size = affine
+        .xx
+        .abs()
+        .max(affine.xy.abs())
+        .max(affine.yx.abs())
+        .max(affine.yy.abs());
+    if determinant.abs() <= size * size * 1e-12 {
+        return ResolvedShape::default();
+    }
+
+    let contours = shape
+        .contours
+        .iter()
+        .map(|contour| {
+            let segments: Vec<PathSegment> = contour
+                .segments
+                .iter()
+                .map(|segment| transform_segment(segment, affine))
+                .collect();
+            Contour {
+                segments: if determinant < 0.0 {
+                    reverse_path(&segments)
+                } else {
+                    segments
+                },
+                role: contour.role,
+            }
+        })
+        .collect();
+    ResolvedShape { contours }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{distance, get_segment_midpoint, signed_area_of_path};
+    use crate::types::{ArcDirection, Axis};
+
+    fn maps() -> Vec<Affine2> {
+        let pivot = Point { x: 1.0, y: -2.0 };
+        vec![
+            Affine2::rotation(pivot, Angle::from_degrees(35.0)),
+            Affine2::scaling(pivot, 2.0, 2.0),
+            Affine2::reflection(Axis::through(pivot, Point { x: 3.0, y: 0.5 })),
+            Affine2::scaling(pivot, 3.0, 0.5),
+            Affine2 {
+                xx: 1.0,
+                xy: 0.7,
+                yx: -0.2,
+                yy: -1.3,
+                tx: 4.0,
+                ty: 1.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_transform_curves_pointwise() {
+        let arc = PathSegment::Arc(
+            Point { x: 2.0, y: 1.0 },
+            1.5,
+            Angle::from_degrees(-30.0),
+            Angle::from_degrees(200.0),
+            ArcDirection::CounterClockwise,
+        );
+        let ellipse = PathSegment::EllipticalArc(
+            Ellipse {
+                center: Point { x: -1.0, y: 0.5 },
+                radius_x: 3.0,
+                radius_y: 1.0,
+                rotation: Angle::from_degrees(20.0),
+            },
+            Angle::from_degrees(150.0),
+            Angle::from_degrees(10.0),
+            ArcDirection::Clockwise,
+        );
+        let start = |segment: &PathSegment| crate::geometry::get_segment_start_point(segment);
+        let end = |segment: &PathSegment| crate::geometry::get_segment_end_point(segment);
+
+        // Ends and middles of the image are the images of the ends and middles
+        for affine in maps() {
+            for segment in [arc, ellipse] {
+                let image = transform_segment(&segment, &affine);
+                let same = |p: Point, q: Point| distance(p, q) < 1e-9;
+                assert!(same(
+                    start(&image).unwrap(),
+                    affine.apply(start(&segment).unwrap())
+                ));
+                assert!(same(
+                    end(&image).unwrap(),
+                    affine.apply(end(&segment).unwrap())
+                ));
+                assert!(same(
+                    get_segment_midpoint(&image),
+                    affine.apply(get_segment_midpoint(&segment))
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_shape_keeps_orientation() {
+        let square = ResolvedShape::from_segments(vec![
+            PathSegment::Line(Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }),
+            PathSegment::Line(Point { x: 2.0, y: 0.0 }, Point { x: 2.0, y: 2.0 }),
+            PathSegment::Arc(
+                Point { x: 1.0, y: 2.0 },
+                1.0,
+                Angle::ZERO,
+                Angle::from_degrees(180.0),
+                ArcDirection::CounterClockwise,
+            ),
+            PathSegment::Line(Point { x: 0.0, y: 2.0 }, Point { x: 0.0, y: 0.0 }),
+            PathSegment::ClosePath,
+        ]);
+        let area = signed_area_of_path(&square.contours[0].segments);
+        assert!(area > 0.0);
+
+        for affine in maps() {
+            let image = transform_shape(&square, &affine);
+            let image_area = signed_area_of_path(&image.contours[0].segments);
+            assert!((image_area - area * affine.determinant().abs()).abs() < 1e-9);
+        }
+
+        let flat = Affine2::scaling(Point { x: 0.0, y: 0.0 }, 1.0, 0.0);
+        assert!(transform_shape(&square, &flat).is_empty());
+    }
+}
@@ -15,27 +15,625 @@ pub enum Shape {
         center: Point,
         radius: f64,
     },
+    Ellipse {
+        center: Point,
+        radius_x: f64, // semi-axis along the rotated x axis
+        radius_y: f64, // semi-axis along the rotated y axis
+        rotation: Angle,
+    },
     Rectangle {
         top_left: Point,
         bottom_right: Point,
     },
+    Polygon(Vec<Point>), // vertices in order, implicitly closed
+    Path(ResolvedShape), // already resolved geometry used as a leaf
     Union(Box<Shape>, Box<Shape>),
-    Scale(Box<Shape>, f64),
+    Scale(Box<Shape>, f64, Point),        // factor, pivot
+    ScaleXY(Box<Shape>, f64, f64, Point), // x factor, y factor, pivot
+    Translate(Box<Shape>, Point),         // offset
+    Rotate {
+        shape: Box<Shape>,
+        pivot: Point,
+        angle: Angle, // counter-clockwise
+    },
+    Mirror(Box<Shape>, Axis),
+    Transform(Box<Shape>, Affine2),
+    Fillet {
+        shape: Box<Shape>,
+        radius: f64,
+        corners: CornerSelector,
+    },
+    Chamfer {
+        shape: Box<Shape>,
+        distance: f64, // measured along each segment from the corner
+        corners: CornerSelector,
+    },
     Subtract(Box<Shape>, Box<Shape>),
     Xor(Box<Shape>, Box<Shape>),
+    Intersect(Box<Shape>, Box<Shape>),
+}
+
+/// An angle, with the unit spelled out wherever one is created or read
+///
+/// The value is kept in degrees, so angles given in degrees such as `360.0`
+/// are stored exactly.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Angle(f64);
+
+impl Angle {
+    pub const ZERO: Angle = Angle(0.0);
+    pub const FULL_TURN: Angle = Angle(360.0);
+
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self(degrees)
+    }
+
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians.to_degrees())
+    }
+
+    pub fn degrees(self) -> f64 {
+        self.0
+    }
+
+    pub fn radians(self) -> f64 {
+        self.0.to_radians()
+    }
+
+    /// The same direction expressed in `[0°, 360°)`
+    pub fn normalized(self) -> Self {
+        Self(self.0.rem_euclid(360.0))
+    }
+}
+
+impl std::ops::Add for Angle {
+    type Output = Angle;
+
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0 + other.0)
+    }
+}
+
+impl std::ops::Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0 - other.0)
+    }
+}
+
+impl std::ops::Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+impl std::ops::Mul<f64> for Angle {
+    type Output = Angle;
+
+    fn mul(self, factor: f64) -> Angle {
+        Angle(self.0 * factor)
+    }
+}
+
+/// Direction an arc is traversed in, from its start angle to its end angle
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ArcDirection {
+    #[default]
+    CounterClockwise,
+    Clockwise,
+}
+
+impl ArcDirection {
+    /// `1.0` for counter-clockwise and `-1.0` for clockwise travel
+    pub fn sign(self) -> f64 {
+        match self {
+            ArcDirection::CounterClockwise => 1.0,
+            ArcDirection::Clockwise => -1.0,
+        }
+    }
+
+    pub fn reversed(self) -> Self {
+        match self {
+            ArcDirection::CounterClockwise => ArcDirection::Clockwise,
+            ArcDirection::Clockwise => ArcDirection::CounterClockwise,
+        }
+    }
+
+    /// Direction of a signed sweep, counter-clockwise when it is not negative
+    pub fn of_sweep(sweep: f64) -> Self {
+        if sweep >= 0.0 {
+            ArcDirection::CounterClockwise
+        } else {
+            ArcDirection::Clockwise
+        }
+    }
+}
+
+/// A line to mirror shapes in, through `origin` at `angle` from the x axis
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Axis {
+    pub origin: Point,
+    pub angle: Angle,
 }
 
+impl Axis {
+    /// The horizontal line at height `y`
+    pub fn horizontal(y: f64) -> Self {
+        Self {
+            origin: Point { x: 0.0, y },
+            angle: Angle::ZERO,
+        }
+    }
+
+    /// The vertical line at `x`
+    pub fn vertical(x: f64) -> Self {
+        Self {
+            origin: Point { x, y: 0.0 },
+            angle: Angle::from_degrees(90.0),
+        }
+    }
+
+    /// The line through two points
+    pub fn through(p1: Point, p2: Point) -> Self {
+        Self {
+            origin: p1,
+            angle: Angle::from_radians((p2.y - p1.y).atan2(p2.x - p1.x)),
+        }
+    }
+}
+
+/// An affine map of the plane, taking `(x, y)` to
+/// `(xx·x + xy·y + tx, yx·x + yy·y + ty)`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine2 {
+    pub xx: f64,
+    pub xy: f64,
+    pub yx: f64,
+    pub yy: f64,
+    pub tx: f64,
+    pub ty: f64,
+}
+
+impl Affine2 {
+    pub const IDENTITY: Affine2 = Affine2 {
+        xx: 1.0,
+        xy: 0.0,
+        yx: 0.0,
+        yy: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    /// Shift by `offset`
+    pub fn translation(offset: Point) -> Self {
+        Self {
+            tx: offset.x,
+            ty: offset.y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Turn counter-clockwise by `angle` about `pivot`
+    pub fn rotation(pivot: Point, angle: Angle) -> Self {
+        let (sin, cos) = angle.radians().sin_cos();
+        Self::linear_about(pivot, cos, -sin, sin, cos)
+    }
+
+    /// Scale by `sx` along x and `sy` along y, keeping `pivot` in place
+    pub fn scaling(pivot: Point, sx: f64, sy: f64) -> Self {
+        Self::linear_about(pivot, sx, 0.0, 0.0, sy)
+    }
+
+    /// Mirror in `axis`
+    pub fn reflection(axis: Axis) -> Self {
+        let (sin, cos) = (2.0 * axis.angle.radians()).sin_cos();
+        Self::linear_about(axis.origin, cos, sin, sin, -cos)
+    }
+
+    /// The linear map with the given matrix, with `pivot` kept in place
+    fn linear_about(pivot: Point, xx: f64, xy: f64, yx: f64, yy: f64) -> Self {
+        Self {
+            xx,
+            xy,
+            yx,
+            yy,
+            tx: pivot.x - (xx * pivot.x + xy * pivot.y),
+            ty: pivot.y - (yx * pivot.x + yy * pivot.y),
+        }
+    }
+
+    /// This map followed by `next`
+    pub fn then(&self, next: &Affine2) -> Self {
+        Self {
+            xx: next.xx * self.xx + next.xy * self.yx,
+            xy: next.xx * self.xy + next.xy * self.yy,
+            yx: next.yx * self.xx + next.yy * self.yx,
+            yy: next.yx * self.xy + next.yy * self.yy,
+            tx: next.xx * self.tx + next.xy * self.ty + next.tx,
+            ty: next.yx * self.tx + next.yy * self.ty + next.ty,
+        }
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        Point {
+            x: self.xx * point.x + self.xy * point.y + self.tx,
+            y: self.yx * point.x + self.yy * point.y + self.ty,
+        }
+    }
+
+    /// Apply only the linear part, as to a direction
+    pub fn apply_vector(&self, vector: Point) -> Point {
+        Point {
+            x: self.xx * vector.x + self.xy * vector.y,
+            y: self.yx * vector.x + self.yy * vector.y,
+        }
+    }
+
+    /// Area scale factor, negative when the map mirrors
+    pub fn determinant(&self) -> f64 {
+        self.xx * self.yy - self.xy * self.yx
+    }
+}
+
+/// An ellipse with semi-axes `radius_x` and `radius_y`, turned
+/// counter-clockwise by `rotation` about its center
+///
+/// Points on it are addressed by the parametric angle `t`, at which the point
+/// is `center + R(rotation)·(radius_x·cos t, radius_y·sin t)`. For a circle
+/// the parametric angle is the polar angle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ellipse {
+    pub center: Point,
+    pub radius_x: f64,
+    pub radius_y: f64,
+    pub rotation: Angle,
+}
+
+impl Ellipse {
+    /// The circle of `radius` around `center` as an ellipse
+    pub fn circle(center: Point, radius: f64) -> Self {
+        Self {
+            center,
+            radius_x: radius,
+            radius_y: radius,
+            rotation: Angle::ZERO,
+        }
+    }
+
+    /// Point at parametric angle `t`
+    pub fn point_at(&self, t: Angle) -> Point {
+        self.from_unit_frame(Point {
+            x: t.radians().cos(),
+            y: t.radians().sin(),
+        })
+    }
+
+    /// Derivative of `point_at` with respect to `t` in radians
+    pub fn derivative_at(&self, t: Angle) -> Point {
+        let (sin_r, cos_r) = self.rotation.radians().sin_cos();
+        let dx = -self.radius_x * t.radians().sin();
+        let dy = self.radius_y * t.radians().cos();
+        Point {
+            x: dx * cos_r - dy * sin_r,
+            y: dx * sin_r + dy * cos_r,
+        }
+    }
+
+    /// Parametric angle in `(-180°, 180°]` of the point of the ellipse in
+    /// the direction of `point` as seen from the unit frame
+    pub fn parameter_of(&self, point: Point) -> Angle {
+        let unit = self.to_unit_frame(point);
+        Angle::from_radians(unit.y.atan2(unit.x))
+    }
+
+    /// Map a point into the frame where the ellipse is the unit circle at the
+    /// origin
+    pub fn to_unit_frame(&self, point: Point) -> Point {
+        let (sin_r, cos_r) = self.rotation.radians().sin_cos();
+        let dx = point.x - self.center.x;
+        let dy = point.y - self.center.y;
+        Point {
+            x: (dx * cos_r + dy * sin_r) / self.radius_x,
+            y: (-dx * sin_r + dy * cos_r) / self.radius_y,
+        }
+    }
+
+    /// Inverse of `to_unit_frame`
+    pub fn from_unit_frame(&self, point: Point) -> Point {
+        let (sin_r, cos_r) = self.rotation.radians().sin_cos();
+        let x = point.x * self.radius_x;
+        let y = point.y * self.radius_y;
+        Point {
+            x: self.center.x + x * cos_r - y * sin_r,
+            y: self.center.y + x * sin_r + y * cos_r,
+        }
+    }
+}
+
+/// A piece of a path
+///
+/// An arc runs from its start angle to its end angle in its direction, so
+/// `(0°, 90°)` is a quarter circle counter-clockwise but three quarters
+/// clockwise. Equal angles a full turn apart describe the whole circle.
+/// Elliptical arcs work the same way with parametric angles.
 #[derive(Copy, Clone, Debug)]
 pub enum PathSegment {
     Line(Point, Point),
-    Arc(Point, f64, f64, f64), // center, radius, start_angle, end_angle
-    ConnectedArc(Point, f64, f64, f64, Point, Point), // center, radius, start_angle, end_angle, start_point, end_point
+    Arc(Point, f64, Angle, Angle, ArcDirection), // center, radius, start_angle, end_angle, direction
+    ConnectedArc(Point, f64, Angle, Angle, ArcDirection, Point, Point), // center, radius, start_angle, end_angle, direction, start_point, end_point
+    EllipticalArc(Ellipse, Angle, Angle, ArcDirection), // ellipse, start_angle, end_angle, direction
+    QuadBezier(Point, Point, Point),                    // start, control, end
+    CubicBezier(Point, Point, Point, Point),            // start, control1, control2, end
     ClosePath,        // closes the current path to the starting point using a straight line
     DrawPoint(Point), // draws a single point (useful for marking intersection points)
 }
 
-pub struct ResolvedShape {
+/// Rule deciding which points are inside a set of possibly overlapping contours
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    EvenOdd, // inside when the winding number is odd
+    #[default]
+    NonZero, // inside when the winding number is not zero
+    Positive, // inside when the winding number is above zero
+}
+
+impl FillRule {
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0,
+            FillRule::Positive => winding_number > 0,
+        }
+    }
+}
+
+/// How offset or stroked boundaries are continued around a convex corner
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JoinStyle {
+    Round,      // circular arc around the corner
+    Miter(f64), // sharp tip, bevelled once it reaches past this many offset distances
+    Bevel,      // straight line across the corner
+}
+
+/// Which corners of a shape a fillet or chamfer applies to
+///
+/// A corner is convex when it points out of the filled region, as every
+/// corner of a rectangle does, and concave when it points into it, as the
+/// inner corner of an L does.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CornerSelector {
+    All,
+    Convex,
+    Concave,
+    Near(Vec<Point>, f64), // corners within this distance of any of the points
+}
+
+impl CornerSelector {
+    pub fn selects(&self, corner: Point, convex: bool) -> bool {
+        match self {
+            CornerSelector::All => true,
+            CornerSelector::Convex => convex,
+            CornerSelector::Concave => !convex,
+            CornerSelector::Near(points, distance) => points
+                .iter()
+                .any(|point| point.distance_to(corner) <= *distance),
+        }
+    }
+}
+
+/// How the ends of a stroked open path are closed off
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CapStyle {
+    #[default]
+    Butt, // flat, flush with the end point
+    Round,  // half disc around the end point
+    Square, // flat, half the width past the end point
+}
+
+/// Numeric tolerances used by intersections, containment and boolean ops
+///
+/// `distance` is in world units and `angle` in degrees. Drawings at very
+/// small or very large scales should use `for_scale` so the distance
+/// tolerance follows the size of the geometry.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tolerance {
+    pub distance: f64,
+    pub angle: f64,
+}
+
+impl Tolerance {
+    /// Distance tolerance relative to the extent of the drawing
+    const RELATIVE_DISTANCE: f64 = 1e-9;
+
+    pub fn new(distance: f64, angle: f64) -> Self {
+        Self { distance, angle }
+    }
+
+    /// Tolerance for geometry whose overall size is about `extent`
+    pub fn for_scale(extent: f64) -> Self {
+        let extent = extent.abs();
+        if extent > 0.0 && extent.is_finite() {
+            Self {
+                distance: extent * Self::RELATIVE_DISTANCE,
+                ..Self::default()
+            }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Tolerance for geometry spanning `bbox`
+    pub fn for_bounding_box(bbox: &BoundingBox) -> Self {
+        Self::for_scale((bbox.max.x - bbox.min.x).hypot(bbox.max.y - bbox.min.y))
+    }
+
+    /// Angular tolerance in degrees on a circle of `radius`, covering both the
+    /// angle epsilon and the arc length of the distance epsilon
+    pub fn angle_at_radius(&self, radius: f64) -> f64 {
+        if radius > 0.0 {
+            self.angle.max((self.distance / radius).to_degrees())
+        } else {
+            self.angle
+        }
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            distance: 1e-9,
+            angle: 1e-6,
+        }
+    }
+}
+
+/// Whether two boundaries pass through each other at an intersection
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IntersectionKind {
+    Crossing, // the segments cross at an interior point of both
+    Touching, // tangency, end of an overlap, or contact at a segment end
+}
+
+/// A point where the boundaries of two shapes meet
+///
+/// Segment indices refer to the edges of each shape as listed by
+/// `boolean_ops::shape_edges`. A parameter is the position on its segment
+/// as given by `geometry::segment_parameter`: `t` in `[0, 1]` for lines, and
+/// for arcs the absolute angle in degrees, unwrapped to lie between the
+/// arc's `start_angle` and `end_angle` rather than measured from the start.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Intersection {
+    pub point: Point,
+    pub segment1: usize,
+    pub segment2: usize,
+    pub param1: f64,
+    pub param2: f64,
+    pub kind: IntersectionKind,
+}
+
+impl Intersection {
+    /// The same intersection with the roles of the two shapes exchanged
+    pub fn swapped(&self) -> Self {
+        Self {
+            point: self.point,
+            segment1: self.segment2,
+            segment2: self.segment1,
+            param1: self.param2,
+            param2: self.param1,
+            kind: self.kind,
+        }
+    }
+}
+
+/// Location of a point relative to a shape
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    OnBoundary, // within the tolerance of a boundary segment
+}
+
+/// Role of a closed contour inside a resolved shape
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContourRole {
+    Outer, // boundary of a filled region, counter-clockwise
+    Hole,  // boundary of a hole inside an outer contour, clockwise
+}
+
+/// A single closed path of a resolved shape
+///
+/// The path may end with `ClosePath`; the contour is closed either way.
+#[derive(Clone, Debug)]
+pub struct Contour {
     pub segments: Vec<PathSegment>,
+    pub role: ContourRole,
+}
+
+impl Contour {
+    pub fn outer(segments: Vec<PathSegment>) -> Self {
+        Self {
+            segments,
+            role: ContourRole::Outer,
+        }
+    }
+
+    pub fn hole(segments: Vec<PathSegment>) -> Self {
+        Self {
+            segments,
+            role: ContourRole::Hole,
+        }
+    }
+}
+
+/// Geometry made of several closed contours, each either an outer boundary
+/// or a hole
+#[derive(Clone, Debug, Default)]
+pub struct ResolvedShape {
+    pub contours: Vec<Contour>,
+}
+
+impl ResolvedShape {
+    /// Build a shape from a flat segment list, starting a new contour after
+    /// every `ClosePath`
+    ///
+    /// Every contour is taken as an outer boundary; use
+    /// `boolean_ops::build_shape_from_contours` to detect holes by nesting.
+    pub fn from_segments(segments: Vec<PathSegment>) -> Self {
+        let mut contours = Vec::new();
+        let mut current = Vec::new();
+        for segment in segments {
+            let closes = matches!(segment, PathSegment::ClosePath);
+            current.push(segment);
+            if closes {
+                contours.push(Contour::outer(std::mem::take(&mut current)));
+            }
+        }
+        if !current.is_empty() {
+            contours.push(Contour::outer(current));
+        }
+        Self { contours }
+    }
+
+    /// Iterate over the segments of all contours
+    pub fn segments(&self) -> impl Iterator<Item = &PathSegment> {
+        self.contours
+            .iter()
+            .flat_map(|contour| contour.segments.iter())
+    }
+
+    /// Iterate over the outer contours
+    pub fn outer_contours(&self) -> impl Iterator<Item = &Contour> {
+        self.contours
+            .iter()
+            .filter(|contour| contour.role == ContourRole::Outer)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments().next().is_none()
+    }
+}
+
+/// Area properties of a shape, as used for section calculations
+///
+/// Second moments are taken about axes through the centroid: `ixx` about the
+/// horizontal one, `iyy` about the vertical one. `principal_angle` is the
+/// direction of the `i_max` axis, the axis through the centroid about which
+/// the second moment is largest, in degrees counter-clockwise from +x. The
+/// second moment is smallest, `i_min`, about the perpendicular axis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SectionProperties {
+    pub area: f64,
+    pub centroid: Point,
+    pub ixx: f64,   // ∬ (y - cy)² dA
+    pub iyy: f64,   // ∬ (x - cx)² dA
+    pub ixy: f64,   // ∬ (x - cx)·(y - cy) dA
+    pub polar: f64, // ixx + iyy, about the centroid itself
+    pub i_max: f64,
+    pub i_min: f64,
+    pub principal_angle: Angle, // in (-90°, 90°]
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -77,4 +675,26 @@ impl BoundingBox {
             && point.y >= self.min.y
             && point.y <= self.max.y
     }
+
+    /// Whether the two boxes share at least one point
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// The box grown by `margin` on every side
+    pub fn expanded(&self, margin: f64) -> Self {
+        Self {
+            min: Point {
+                x: self.min.x - margin,
+                y: self.min.y - margin,
+            },
+            max: Point {
+                x: self.max.x + margin,
+                y: self.max.y + margin,
+            },
+        }
+    }
 }
@@ -1,6 +1,11 @@
-use crate::boolean_ops::{compute_subtract, compute_union, compute_xor, find_shape_intersections};
-use crate::geometry::get_shape_bounding_box;
-use crate::types::{PathSegment, Point, ResolvedShape};
+use crate::bezier::{cubic_control_points, cubic_point};
+use crate::boolean_ops::{
+    compute_area, compute_xor, find_shape_intersections, intersect_all, subtract_many, union_all,
+};
+use crate::geometry::{arc_span, contour_lengths, get_shape_bounding_box, perimeter};
+use crate::types::{
+    Angle, ArcDirection, BoundingBox, ContourRole, PathSegment, Point, ResolvedShape, Tolerance,
+};
 use eframe::egui;
 use std::sync::Arc;
 
@@ -9,11 +14,13 @@ enum Tool {
     Hand, // For panning and selecting
     Circle,
     Rectangle,
+    IntersectionPoints,
     Intersection,
     Union,
     Difference,
     Xor,
     MeasureArea,
+    MeasureLength,
 }
 
 #[derive(PartialEq, Clone, Copy, Debug)]
@@ -139,7 +146,7 @@ impl ShapeViewer {
     fn get_all_draw_points(&self) -> Vec<Point> {
         let mut all_draw_points = Vec::new();
         for (shape, _, _) in &self.shapes {
-            for segment in &shape.segments {
+            for segment in shape.segments() {
                 if let PathSegment::DrawPoint(point) = segment {
                     all_draw_points.push(*point);
                 }
@@ -179,7 +186,8 @@ impl ShapeViewer {
                 painter.line_segment([p1, p2], stroke);
                 *current_point = *end;
             }
-            PathSegment::Arc(center, radius, start_angle, end_angle) => {
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
                 let steps = ((end_angle - start_angle).abs() / 5.0).max(30.0) as usize;
                 let angle_step = (end_angle - start_angle) / steps as f64;
 
@@ -211,9 +219,11 @@ impl ShapeViewer {
                 radius,
                 start_angle,
                 end_angle,
+                direction,
                 _start_pt,
                 end_pt,
             ) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
                 let steps = ((end_angle - start_angle).abs() / 5.0).max(10.0) as usize;
                 let angle_step = (end_angle - start_angle) / steps as f64;
 
@@ -237,6 +247,41 @@ impl ShapeViewer {
 
                 *current_point = *end_pt;
             }
+            PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                let steps = ((end_angle - start_angle).abs() / 5.0).max(30.0) as usize;
+                let angle_step = (end_angle - start_angle) / steps as f64;
+
+                for i in 0..steps {
+                    let a1 = start_angle + angle_step * i as f64;
+                    let a2 = start_angle + angle_step * (i + 1) as f64;
+
+                    let p1 = ellipse.point_at(Angle::from_degrees(a1));
+                    let p2 = ellipse.point_at(Angle::from_degrees(a2));
+
+                    let screen_p1 = self.world_to_screen(p1, rect);
+                    let screen_p2 = self.world_to_screen(p2, rect);
+                    painter.line_segment([screen_p1, screen_p2], stroke);
+                }
+
+                *current_point = ellipse.point_at(Angle::from_degrees(end_angle));
+            }
+            PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                let Some(curve) = cubic_control_points(segment) else {
+                    return;
+                };
+                let steps = 32;
+                for i in 0..steps {
+                    let p1 = cubic_point(&curve, i as f64 / steps as f64);
+                    let p2 = cubic_point(&curve, (i + 1) as f64 / steps as f64);
+
+                    let screen_p1 = self.world_to_screen(p1, rect);
+                    let screen_p2 = self.world_to_screen(p2, rect);
+                    painter.line_segment([screen_p1, screen_p2], stroke);
+                }
+
+                *current_point = curve[3];
+            }
             PathSegment::ClosePath => {}
             PathSegment::DrawPoint(point) => {
                 let screen_pos = self.world_to_screen(*point, rect);
@@ -265,7 +310,8 @@ impl ShapeViewer {
                 painter.line_segment([p1, p2], egui::Stroke::new(2.0, color));
                 *current_point = *end;
             }
-            PathSegment::Arc(center, radius, start_angle, end_angle) => {
+            PathSegment::Arc(center, radius, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
                 // Draw arc using line segments
                 let steps = ((end_angle - start_angle).abs() / 30.0 * self.scale as f64) as usize;
                 let angle_step = (end_angle - start_angle) / steps as f64;
@@ -298,9 +344,11 @@ impl ShapeViewer {
                 radius,
                 start_angle,
                 end_angle,
+                direction,
                 _start_pt,
                 end_pt,
             ) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
                 // Similar to Arc
                 let steps = ((end_angle - start_angle).abs() / 5.0).max(10.0) as usize;
                 let angle_step = (end_angle - start_angle) / steps as f64;
@@ -325,6 +373,41 @@ impl ShapeViewer {
 
                 *current_point = *end_pt;
             }
+            PathSegment::EllipticalArc(ellipse, start_angle, end_angle, direction) => {
+                let (start_angle, end_angle) = arc_span(*start_angle, *end_angle, *direction);
+                let steps = ((end_angle - start_angle).abs() / 5.0).max(30.0) as usize;
+                let angle_step = (end_angle - start_angle) / steps as f64;
+
+                for i in 0..steps {
+                    let a1 = start_angle + angle_step * i as f64;
+                    let a2 = start_angle + angle_step * (i + 1) as f64;
+
+                    let p1 = ellipse.point_at(Angle::from_degrees(a1));
+                    let p2 = ellipse.point_at(Angle::from_degrees(a2));
+
+                    let screen_p1 = self.world_to_screen(p1, rect);
+                    let screen_p2 = self.world_to_screen(p2, rect);
+                    painter.line_segment([screen_p1, screen_p2], egui::Stroke::new(2.0, color));
+                }
+
+                *current_point = ellipse.point_at(Angle::from_degrees(end_angle));
+            }
+            PathSegment::QuadBezier(..) | PathSegment::CubicBezier(..) => {
+                let Some(curve) = cubic_control_points(segment) else {
+                    return;
+                };
+                let steps = 32;
+                for i in 0..steps {
+                    let p1 = cubic_point(&curve, i as f64 / steps as f64);
+                    let p2 = cubic_point(&curve, (i + 1) as f64 / steps as f64);
+
+                    let screen_p1 = self.world_to_screen(p1, rect);
+                    let screen_p2 = self.world_to_screen(p2, rect);
+                    painter.line_segment([screen_p1, screen_p2], egui::Stroke::new(2.0, color));
+                }
+
+                *current_point = curve[3];
+            }
             PathSegment::ClosePath => {
                 // Close path is handled automatically by tracking first point
             }
@@ -345,12 +428,16 @@ impl ShapeViewer {
         match self.drawing_state {
             DrawingState::CircleFirstClick(center) => {
                 let radius = center.distance_to(end_point);
-                let new_shape = ResolvedShape {
-                    segments: vec![
-                        PathSegment::Arc(center, radius, 0.0, 360.0),
-                        PathSegment::DrawPoint(center),
-                    ],
-                };
+                let new_shape = ResolvedShape::from_segments(vec![
+                    PathSegment::Arc(
+                        center,
+                        radius,
+                        Angle::ZERO,
+                        Angle::FULL_TURN,
+                        ArcDirection::CounterClockwise,
+                    ),
+                    PathSegment::DrawPoint(center),
+                ]);
                 self.add_shape(
                     new_shape,
                     egui::Color32::from_rgb(255, 0, 0),
@@ -376,18 +463,16 @@ impl ShapeViewer {
                     y: bottom_right.y,
                 };
 
-                let new_shape = ResolvedShape {
-                    segments: vec![
-                        PathSegment::Line(top_left, top_right),
-                        PathSegment::Line(top_right, bottom_right),
-                        PathSegment::Line(bottom_right, bottom_left),
-                        PathSegment::Line(bottom_left, top_left),
-                        PathSegment::DrawPoint(top_left),
-                        PathSegment::DrawPoint(top_right),
-                        PathSegment::DrawPoint(bottom_right),
-                        PathSegment::DrawPoint(bottom_left),
-                    ],
-                };
+                let new_shape = ResolvedShape::from_segments(vec![
+                    PathSegment::Line(top_left, top_right),
+                    PathSegment::Line(top_right, bottom_right),
+                    PathSegment::Line(bottom_right, bottom_left),
+                    PathSegment::Line(bottom_left, top_left),
+                    PathSegment::DrawPoint(top_left),
+                    PathSegment::DrawPoint(top_right),
+                    PathSegment::DrawPoint(bottom_right),
+                    PathSegment::DrawPoint(bottom_left),
+                ]);
                 self.add_shape(
                     new_shape,
                     egui::Color32::from_rgb(0, 255, 0),
@@ -398,6 +483,47 @@ impl ShapeViewer {
         }
     }
 
+    /// The topmost shape whose bounding box contains `world_pos`
+    fn shape_at(&self, world_pos: Point) -> Option<usize> {
+        self.shapes
+            .iter()
+            .rposition(|(shape, _, _)| get_shape_bounding_box(shape).contains(world_pos))
+    }
+
+    /// Measurements of the selected shape for the measure tools
+    fn show_measurements(&self, ui: &mut egui::Ui) {
+        let Some((shape, color, name)) = self
+            .selected_shapes
+            .first()
+            .and_then(|&idx| self.shapes.get(idx))
+        else {
+            ui.label("点击图形进行测量");
+            return;
+        };
+        ui.colored_label(*color, name);
+        match self.selected_tool {
+            Tool::MeasureArea => {
+                ui.label(format!("面积: {:.4}", compute_area(shape)));
+            }
+            Tool::MeasureLength => {
+                ui.label(format!("周长: {:.4}", perimeter(shape)));
+                for (i, (contour, length)) in shape
+                    .contours
+                    .iter()
+                    .zip(contour_lengths(shape))
+                    .enumerate()
+                {
+                    let role = match contour.role {
+                        ContourRole::Outer => "外轮廓",
+                        ContourRole::Hole => "孔",
+                    };
+                    ui.label(format!("  {} {}: {:.4}", role, i + 1, length));
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn draw(&mut self, ui: &mut egui::Ui) {
         let (response, painter) = ui.allocate_painter(
             egui::Vec2::new(ui.available_width(), ui.available_height()),
@@ -410,87 +536,117 @@ impl ShapeViewer {
             self.draw_grid(&painter, rect);
         }
 
-        if self.selected_tool == Tool::Intersection
+        if self.selected_tool == Tool::IntersectionPoints
+            || self.selected_tool == Tool::Intersection
             || self.selected_tool == Tool::Union
             || self.selected_tool == Tool::Difference
             || self.selected_tool == Tool::Xor
         {
-            if response.clicked() {
-                if let Some(mouse_pos) = response.hover_pos() {
-                    let world_pos = self.screen_to_world(mouse_pos, rect);
-
-                    let mut clicked_shape_idx = None;
-                    for (i, (shape, _, _)) in self.shapes.iter().enumerate().rev() {
-                        let bbox = get_shape_bounding_box(shape);
-                        if bbox.contains(world_pos) {
-                            clicked_shape_idx = Some(i);
-                            break;
-                        }
+            if response.clicked()
+                && let Some(mouse_pos) = response.hover_pos()
+            {
+                let world_pos = self.screen_to_world(mouse_pos, rect);
+
+                if let Some(idx) = self.shape_at(world_pos) {
+                    if let Some(pos) = self.selected_shapes.iter().position(|&x| x == idx) {
+                        self.selected_shapes.remove(pos);
+                    } else {
+                        self.selected_shapes.push(idx);
                     }
 
-                    if let Some(idx) = clicked_shape_idx {
-                        if let Some(pos) = self.selected_shapes.iter().position(|&x| x == idx) {
-                            self.selected_shapes.remove(pos);
-                        } else {
-                            self.selected_shapes.push(idx);
-                            if self.selected_shapes.len() > 2 {
-                                self.selected_shapes.remove(0);
-                            }
-                        }
-
-                        if self.selected_shapes.len() == 2 {
-                            let shape1 = &self.shapes[self.selected_shapes[0]].0;
-                            let shape2 = &self.shapes[self.selected_shapes[1]].0;
-                            let intersections = find_shape_intersections(shape1, shape2);
-
-                            match self.selected_tool {
-                                Tool::Intersection => {
-                                    self.add_shape(
-                                        ResolvedShape {
-                                            segments: intersections
+                    if self.selected_shapes.len() >= 2 {
+                        let operands: Vec<ResolvedShape> = self
+                            .selected_shapes
+                            .iter()
+                            .map(|&i| self.shapes[i].0.clone())
+                            .collect();
+                        let corners: Vec<Point> = operands
+                            .iter()
+                            .flat_map(|shape| {
+                                let bbox = get_shape_bounding_box(shape);
+                                [bbox.min, bbox.max]
+                            })
+                            .collect();
+                        let tolerance =
+                            Tolerance::for_bounding_box(&BoundingBox::from_points(&corners));
+
+                        match self.selected_tool {
+                            Tool::IntersectionPoints => {
+                                let mut points = Vec::new();
+                                for (i, shape1) in operands.iter().enumerate() {
+                                    for shape2 in &operands[i + 1..] {
+                                        points.extend(
+                                            find_shape_intersections(shape1, shape2, tolerance)
                                                 .iter()
-                                                .map(|p| PathSegment::DrawPoint(*p))
-                                                .collect(),
-                                        },
-                                        egui::Color32::RED,
-                                        "Intersections".to_string(),
-                                    );
-                                }
-                                Tool::Union => {
-                                    self.boolean_op_result =
-                                        Some(compute_union(shape1, shape2, &intersections));
-                                }
-                                Tool::Difference => {
-                                    self.boolean_op_result =
-                                        Some(compute_subtract(shape1, shape2, &intersections));
+                                                .map(|x| PathSegment::DrawPoint(x.point)),
+                                        );
+                                    }
                                 }
-                                Tool::Xor => {
-                                    self.boolean_op_result =
-                                        Some(compute_xor(shape1, shape2, &intersections));
-                                }
-                                _ => {}
+                                self.add_shape(
+                                    ResolvedShape::from_segments(points),
+                                    egui::Color32::RED,
+                                    "Intersections".to_string(),
+                                );
+                            }
+                            Tool::Intersection => {
+                                self.boolean_op_result = Some(intersect_all(&operands, tolerance));
+                            }
+                            Tool::Union => {
+                                self.boolean_op_result = Some(union_all(&operands, tolerance));
                             }
-                        } else {
-                            self.boolean_op_result = None;
+                            Tool::Difference => {
+                                // The first selected shape minus all the others
+                                self.boolean_op_result =
+                                    Some(subtract_many(&operands[0], &operands[1..], tolerance));
+                            }
+                            Tool::Xor => {
+                                let result =
+                                    operands[1..]
+                                        .iter()
+                                        .fold(operands[0].clone(), |acc, shape| {
+                                            let intersections =
+                                                find_shape_intersections(&acc, shape, tolerance);
+                                            compute_xor(&acc, shape, &intersections, tolerance)
+                                        });
+                                self.boolean_op_result = Some(result);
+                            }
+                            _ => {}
                         }
+                    } else {
+                        self.boolean_op_result = None;
+                    }
+                }
+            }
+        } else if self.selected_tool == Tool::MeasureArea
+            || self.selected_tool == Tool::MeasureLength
+        {
+            // Measure one shape at a time; clicking it again deselects it
+            if response.clicked()
+                && let Some(mouse_pos) = response.hover_pos()
+            {
+                let world_pos = self.screen_to_world(mouse_pos, rect);
+                if let Some(idx) = self.shape_at(world_pos) {
+                    if self.selected_shapes == [idx] {
+                        self.selected_shapes.clear();
+                    } else {
+                        self.selected_shapes = vec![idx];
                     }
                 }
             }
         } else {
             if response.drag_started() {
                 if self.selected_tool == Tool::Hand {
-                } else if self.selected_tool == Tool::Circle
-                    || self.selected_tool == Tool::Rectangle
+                } else if (self.selected_tool == Tool::Circle
+                    || self.selected_tool == Tool::Rectangle)
+                    && let Some(mouse_pos) = response.hover_pos()
                 {
-                    if let Some(mouse_pos) = response.hover_pos() {
-                        let mut world_pos = self.screen_to_world(mouse_pos, rect);
-                        world_pos = self.snap_point(mouse_pos, world_pos, rect);
-                        self.drawing_state = match self.selected_tool {
-                            Tool::Circle => DrawingState::CircleFirstClick(world_pos),
-                            Tool::Rectangle => DrawingState::RectangleFirstClick(world_pos),
-                            _ => DrawingState::None,
-                        };
-                    }
+                    let mut world_pos = self.screen_to_world(mouse_pos, rect);
+                    world_pos = self.snap_point(mouse_pos, world_pos, rect);
+                    self.drawing_state = match self.selected_tool {
+                        Tool::Circle => DrawingState::CircleFirstClick(world_pos),
+                        Tool::Rectangle => DrawingState::RectangleFirstClick(world_pos),
+                        _ => DrawingState::None,
+                    };
                 }
             } else if response.drag_stopped() {
                 if self.was_dragged {
@@ -502,23 +658,23 @@ impl ShapeViewer {
                     }
                     self.was_dragged = false;
                 }
-            } else if response.clicked() {
-                if let Some(mouse_pos) = response.hover_pos() {
-                    let mut world_pos = self.screen_to_world(mouse_pos, rect);
-                    world_pos = self.snap_point(mouse_pos, world_pos, rect);
-
-                    match self.drawing_state {
-                        DrawingState::None => {
-                            self.drawing_state = match self.selected_tool {
-                                Tool::Circle => DrawingState::CircleFirstClick(world_pos),
-                                Tool::Rectangle => DrawingState::RectangleFirstClick(world_pos),
-                                _ => DrawingState::None,
-                            };
-                        }
-                        _ => {
-                            self.create_shape(world_pos);
-                            self.drawing_state = DrawingState::None;
-                        }
+            } else if response.clicked()
+                && let Some(mouse_pos) = response.hover_pos()
+            {
+                let mut world_pos = self.screen_to_world(mouse_pos, rect);
+                world_pos = self.snap_point(mouse_pos, world_pos, rect);
+
+                match self.drawing_state {
+                    DrawingState::None => {
+                        self.drawing_state = match self.selected_tool {
+                            Tool::Circle => DrawingState::CircleFirstClick(world_pos),
+                            Tool::Rectangle => DrawingState::RectangleFirstClick(world_pos),
+                            _ => DrawingState::None,
+                        };
+                    }
+                    _ => {
+                        self.create_shape(world_pos);
+                        self.drawing_state = DrawingState::None;
                     }
                 }
             }
@@ -562,9 +718,6 @@ impl ShapeViewer {
         }
 
         for (i, (shape, color, _name)) in self.shapes.iter().enumerate() {
-            let mut current_point = crate::geometry::get_starting_point(&shape.segments)
-                .unwrap_or(Point { x: 0.0, y: 0.0 });
-
             let stroke_width = if self.selected_shapes.contains(&i) {
                 4.0
             } else {
@@ -576,28 +729,37 @@ impl ShapeViewer {
                 *color
             };
 
-            for segment in &shape.segments {
-                self.draw_path_segment_with_stroke(
-                    &painter,
-                    rect,
-                    segment,
-                    &mut current_point,
-                    egui::Stroke::new(stroke_width, stroke_color),
-                );
+            for contour in &shape.contours {
+                let mut current_point = crate::geometry::get_starting_point(&contour.segments)
+                    .unwrap_or(Point { x: 0.0, y: 0.0 });
+                let contour_color = match contour.role {
+                    ContourRole::Outer => stroke_color,
+                    ContourRole::Hole => stroke_color.gamma_multiply(0.6),
+                };
+                for segment in &contour.segments {
+                    self.draw_path_segment_with_stroke(
+                        &painter,
+                        rect,
+                        segment,
+                        &mut current_point,
+                        egui::Stroke::new(stroke_width, contour_color),
+                    );
+                }
             }
         }
 
         if let Some(result_shape) = &self.boolean_op_result {
-            let mut current_point = crate::geometry::get_starting_point(&result_shape.segments)
-                .unwrap_or(Point { x: 0.0, y: 0.0 });
-            for segment in &result_shape.segments {
-                self.draw_path_segment(
-                    &painter,
-                    rect,
-                    segment,
-                    &mut current_point,
-                    egui::Color32::GREEN,
-                );
+            // Holes are drawn in a darker shade than outer boundaries
+            for contour in &result_shape.contours {
+                let mut current_point = crate::geometry::get_starting_point(&contour.segments)
+                    .unwrap_or(Point { x: 0.0, y: 0.0 });
+                let color = match contour.role {
+                    ContourRole::Outer => egui::Color32::GREEN,
+                    ContourRole::Hole => egui::Color32::DARK_GREEN,
+                };
+                for segment in &contour.segments {
+                    self.draw_path_segment(&painter, rect, segment, &mut current_point, color);
+                }
             }
         }
     }
@@ -621,17 +783,16 @@ impl eframe::App for ShapeViewer {
 
         // 缩放（捏合手势）
         // egui 的 zoom_delta 通常以 1.0 为无变化（或接近），当有捏合时会返回大于或小于 1.0 的值
-        let zoom = zoom;
-        if (zoom - 1.0).abs() > std::f32::EPSILON {
+        if (zoom - 1.0).abs() > f32::EPSILON {
             let old_scale = self.scale;
             self.scale *= zoom;
             // 同步 offset 以保持视觉中心（与 slider 的行为一致）
-            let scale_ratio = if old_scale.abs() > std::f32::EPSILON {
+            let scale_ratio = if old_scale.abs() > f32::EPSILON {
                 self.scale / old_scale
             } else {
                 1.0
             };
-            self.offset = self.offset * scale_ratio;
+            self.offset *= scale_ratio;
             self.previous_scale = self.scale;
         }
 
@@ -653,11 +814,13 @@ impl eframe::App for ShapeViewer {
                 ui.selectable_value(&mut self.selected_tool, Tool::Hand, "移动画布");
                 ui.selectable_value(&mut self.selected_tool, Tool::Circle, "画圆");
                 ui.selectable_value(&mut self.selected_tool, Tool::Rectangle, "画矩形");
-                ui.selectable_value(&mut self.selected_tool, Tool::Intersection, "取交点");
+                ui.selectable_value(&mut self.selected_tool, Tool::IntersectionPoints, "取交点");
+                ui.selectable_value(&mut self.selected_tool, Tool::Intersection, "取交集");
                 ui.selectable_value(&mut self.selected_tool, Tool::Union, "取并集");
                 ui.selectable_value(&mut self.selected_tool, Tool::Difference, "取差集");
                 ui.selectable_value(&mut self.selected_tool, Tool::Xor, "取异或");
                 ui.selectable_value(&mut self.selected_tool, Tool::MeasureArea, "测量面积");
+                ui.selectable_value(&mut self.selected_tool, Tool::MeasureLength, "测量长度");
 
                 if self.selected_tool != previous_tool {
                     self.drawing_state = DrawingState::None;
@@ -669,6 +832,13 @@ impl eframe::App for ShapeViewer {
 
                 // 显示当前选择的工具
                 ui.label(format!("当前工具: {:?}", self.selected_tool));
+
+                if self.selected_tool == Tool::MeasureArea
+                    || self.selected_tool == Tool::MeasureLength
+                {
+                    ui.separator();
+                    self.show_measurements(ui);
+                }
             });
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -692,7 +862,7 @@ impl eframe::App for ShapeViewer {
                 // 当 scale 改变时，调整 offset 以保持视觉中心不变
                 if (self.scale - old_scale).abs() > 0.001 {
                     let scale_ratio = self.scale / old_scale;
-                    self.offset = self.offset * scale_ratio;
+                    self.offset *= scale_ratio;
                     self.previous_scale = self.scale;
                 }
             });